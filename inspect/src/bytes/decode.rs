@@ -0,0 +1,433 @@
+use {
+  super::Endian,
+  crate::type_info::{Field, Primitive, Sequence, TypeInfo},
+  ::core::{error::Error, fmt},
+  ::disqualified::ShortName,
+};
+
+/// Read a value of the type described by `type_info` out of `bytes`, which
+/// must be exactly as long as the type
+///
+/// See the [module documentation](crate::bytes) for the types that can be read.
+pub fn decode(
+  bytes: &[u8],
+  type_info: &'static TypeInfo,
+  endian: Endian,
+) -> Result<Decoded, DecodeError> {
+  let size = type_info.size().ok_or(DecodeError::Unsupported {
+    type_name: type_info.type_name(),
+    reason: "it isn't statically sized",
+  })?;
+  if bytes.len() != size {
+    return Err(DecodeError::Length {
+      type_name: type_info.type_name(),
+      expected: size,
+      found: bytes.len(),
+    });
+  }
+  Decoder { bytes, endian }.decode(0, type_info)
+}
+
+/// A value read out of bytes by [`decode`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Decoded {
+  Primitive(PrimitiveValue),
+  Array(Vec<Decoded>),
+  Struct {
+    type_name: &'static str,
+    fields: Vec<DecodedField>,
+  },
+  Enum {
+    type_name: &'static str,
+    variant_name: &'static str,
+    fields: Vec<DecodedField>,
+  },
+}
+
+/// A field of a struct or enum variant read by [`decode`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedField {
+  /// The position of the field in its declaration
+  pub index: usize,
+  /// The name of the field, or `None` for the fields of tuple structs and
+  /// tuple variants
+  pub name: Option<&'static str>,
+  pub value: Decoded,
+}
+
+/// The value of a primitive read by [`decode`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[expect(non_camel_case_types)]
+#[non_exhaustive]
+pub enum PrimitiveValue {
+  u8(u8),
+  u16(u16),
+  u32(u32),
+  u64(u64),
+  u128(u128),
+
+  i8(i8),
+  i16(i16),
+  i32(i32),
+  i64(i64),
+  i128(i128),
+
+  f32(f32),
+  f64(f64),
+
+  usize(usize),
+  isize(isize),
+  Unit,
+  bool(bool),
+  char(char),
+}
+
+impl Decoded {
+  /// The field called `name` of a struct, or of the variant of an enum. The
+  /// fields of tuple structs and tuple variants are named by their index,
+  /// i.e. `"0"`
+  pub fn field(&self, name: &str) -> Option<&Decoded> {
+    let fields = match self {
+      Decoded::Struct { fields, .. } | Decoded::Enum { fields, .. } => fields,
+      Decoded::Primitive(_) | Decoded::Array(_) => return None,
+    };
+    fields
+      .iter()
+      .find(|field| match field.name {
+        Some(field_name) => field_name == name,
+        None => field.index.to_string() == name,
+      })
+      .map(|field| &field.value)
+  }
+
+  /// The items of an array
+  pub fn items(&self) -> Option<&[Decoded]> {
+    match self {
+      Decoded::Array(items) => Some(items),
+      _ => None,
+    }
+  }
+
+  /// The value of a primitive
+  pub fn primitive(&self) -> Option<PrimitiveValue> {
+    match self {
+      Decoded::Primitive(value) => Some(*value),
+      _ => None,
+    }
+  }
+}
+
+impl PrimitiveValue {
+  /// The value as a discriminant, in the way
+  /// [`EnumVariantInfo::discriminant_value`] gives it: with negative values
+  /// wrapping around
+  ///
+  /// [`EnumVariantInfo::discriminant_value`]:
+  /// crate::type_info::EnumVariantInfo::discriminant_value
+  fn discriminant(self) -> Option<usize> {
+    Some(match self {
+      PrimitiveValue::u8(value) => value as usize,
+      PrimitiveValue::u16(value) => value as usize,
+      PrimitiveValue::u32(value) => value as usize,
+      PrimitiveValue::u64(value) => value as usize,
+      PrimitiveValue::u128(value) => value as usize,
+      PrimitiveValue::i8(value) => value as usize,
+      PrimitiveValue::i16(value) => value as usize,
+      PrimitiveValue::i32(value) => value as usize,
+      PrimitiveValue::i64(value) => value as usize,
+      PrimitiveValue::i128(value) => value as usize,
+      PrimitiveValue::usize(value) => value,
+      PrimitiveValue::isize(value) => value as usize,
+      PrimitiveValue::f32(_)
+      | PrimitiveValue::f64(_)
+      | PrimitiveValue::Unit
+      | PrimitiveValue::bool(_)
+      | PrimitiveValue::char(_) => return None,
+    })
+  }
+}
+
+impl fmt::Display for Decoded {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let write_fields = |f: &mut fmt::Formatter<'_>,
+                        fields: &[DecodedField]| {
+      if fields.is_empty() {
+        return Ok(());
+      }
+      let named = fields.iter().any(|field| field.name.is_some());
+      f.write_str(if named { " { " } else { "(" })?;
+      for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+          f.write_str(", ")?;
+        }
+        if let Some(name) = field.name {
+          write!(f, "{name}: ")?;
+        }
+        write!(f, "{}", field.value)?;
+      }
+      f.write_str(if named { " }" } else { ")" })
+    };
+
+    match self {
+      Decoded::Primitive(value) => write!(f, "{value}"),
+      Decoded::Array(items) => {
+        f.write_str("[")?;
+        for (index, item) in items.iter().enumerate() {
+          if index > 0 {
+            f.write_str(", ")?;
+          }
+          write!(f, "{item}")?;
+        }
+        f.write_str("]")
+      },
+      Decoded::Struct { type_name, fields } => {
+        write!(f, "{}", ShortName(type_name))?;
+        write_fields(f, fields)
+      },
+      Decoded::Enum {
+        variant_name,
+        fields,
+        ..
+      } => {
+        f.write_str(variant_name)?;
+        write_fields(f, fields)
+      },
+    }
+  }
+}
+
+impl fmt::Display for PrimitiveValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PrimitiveValue::u8(value) => write!(f, "{value}"),
+      PrimitiveValue::u16(value) => write!(f, "{value}"),
+      PrimitiveValue::u32(value) => write!(f, "{value}"),
+      PrimitiveValue::u64(value) => write!(f, "{value}"),
+      PrimitiveValue::u128(value) => write!(f, "{value}"),
+
+      PrimitiveValue::i8(value) => write!(f, "{value}"),
+      PrimitiveValue::i16(value) => write!(f, "{value}"),
+      PrimitiveValue::i32(value) => write!(f, "{value}"),
+      PrimitiveValue::i64(value) => write!(f, "{value}"),
+      PrimitiveValue::i128(value) => write!(f, "{value}"),
+
+      PrimitiveValue::f32(value) => write!(f, "{value:?}"),
+      PrimitiveValue::f64(value) => write!(f, "{value:?}"),
+
+      PrimitiveValue::usize(value) => write!(f, "{value}"),
+      PrimitiveValue::isize(value) => write!(f, "{value}"),
+      PrimitiveValue::Unit => f.write_str("()"),
+      PrimitiveValue::bool(value) => write!(f, "{value}"),
+      PrimitiveValue::char(value) => write!(f, "{value:?}"),
+    }
+  }
+}
+
+struct Decoder<'b> {
+  bytes: &'b [u8],
+  endian: Endian,
+}
+
+impl Decoder<'_> {
+  /// Read the value of the type that starts `offset` bytes in
+  fn decode(
+    &self,
+    offset: usize,
+    type_info: &'static TypeInfo,
+  ) -> Result<Decoded, DecodeError> {
+    let unsupported = |reason| DecodeError::Unsupported {
+      type_name: type_info.type_name(),
+      reason,
+    };
+
+    match type_info {
+      TypeInfo::Primitive(primitive) => {
+        self.primitive(offset, *primitive).map(Decoded::Primitive)
+      },
+      TypeInfo::Sequence(Sequence::Array { info, .. }) => {
+        let item = type_info.item().unwrap();
+        let stride = item.size().unwrap();
+        (0..info.array_length)
+          .map(|index| self.decode(offset + index * stride, item))
+          .collect::<Result<_, _>>()
+          .map(Decoded::Array)
+      },
+      TypeInfo::Struct(_) => {
+        let repr = type_info.repr().unwrap();
+        if !(repr.c || repr.transparent) {
+          return Err(unsupported(
+            "its layout isn't defined by `#[repr(C)]` or \
+             `#[repr(transparent)]`",
+          ));
+        }
+        Ok(Decoded::Struct {
+          type_name: type_info.type_name(),
+          fields: self.fields(offset, type_info.fields())?,
+        })
+      },
+      TypeInfo::Enum(_) => {
+        let repr = type_info.repr().unwrap();
+        let Some(tag) = repr.primitive else {
+          return Err(unsupported(
+            "its discriminant isn't defined by a primitive representation, \
+             like `#[repr(u8)]`",
+          ));
+        };
+        let discriminant = self.primitive(offset, tag)?;
+        let variant = type_info
+          .variants()
+          .iter()
+          .find(|variant| {
+            variant.discriminant_value().is_some()
+              && variant.discriminant_value() == discriminant.discriminant()
+          })
+          .ok_or(DecodeError::UnknownDiscriminant {
+            type_name: type_info.type_name(),
+            offset,
+            discriminant,
+          })?;
+        Ok(Decoded::Enum {
+          type_name: type_info.type_name(),
+          variant_name: variant.name(),
+          fields: self.fields(offset, variant.fields())?,
+        })
+      },
+      TypeInfo::Pointer(_) => {
+        Err(unsupported("the addresses in pointers can't be read back"))
+      },
+      TypeInfo::Union(_) => {
+        Err(unsupported("which field of a union is active isn't known"))
+      },
+      _ => Err(unsupported("its layout isn't defined")),
+    }
+  }
+
+  fn fields(
+    &self,
+    offset: usize,
+    fields: impl Iterator<Item = Field>,
+  ) -> Result<Vec<DecodedField>, DecodeError> {
+    fields
+      .filter(|field| !field.attributes.skip)
+      .map(|field| {
+        Ok(DecodedField {
+          index: field.index,
+          name: field.name,
+          value: self.decode(offset + field.offset, field.type_info())?,
+        })
+      })
+      .collect()
+  }
+
+  fn primitive(
+    &self,
+    offset: usize,
+    primitive: Primitive,
+  ) -> Result<PrimitiveValue, DecodeError> {
+    let bytes = &self.bytes[offset..];
+    macro_rules! read {
+      ($ty:ident) => {{
+        let bytes = bytes[..size_of::<$ty>()].try_into().unwrap();
+        match self.endian {
+          Endian::Little => $ty::from_le_bytes(bytes),
+          Endian::Big => $ty::from_be_bytes(bytes),
+        }
+      }};
+    }
+    let invalid = |type_name| DecodeError::InvalidValue { type_name, offset };
+
+    Ok(match primitive {
+      Primitive::u8 => PrimitiveValue::u8(read!(u8)),
+      Primitive::u16 => PrimitiveValue::u16(read!(u16)),
+      Primitive::u32 => PrimitiveValue::u32(read!(u32)),
+      Primitive::u64 => PrimitiveValue::u64(read!(u64)),
+      Primitive::u128 => PrimitiveValue::u128(read!(u128)),
+
+      Primitive::i8 => PrimitiveValue::i8(read!(i8)),
+      Primitive::i16 => PrimitiveValue::i16(read!(i16)),
+      Primitive::i32 => PrimitiveValue::i32(read!(i32)),
+      Primitive::i64 => PrimitiveValue::i64(read!(i64)),
+      Primitive::i128 => PrimitiveValue::i128(read!(i128)),
+
+      Primitive::f32 => PrimitiveValue::f32(read!(f32)),
+      Primitive::f64 => PrimitiveValue::f64(read!(f64)),
+
+      Primitive::usize => PrimitiveValue::usize(read!(usize)),
+      Primitive::isize => PrimitiveValue::isize(read!(isize)),
+      Primitive::Unit => PrimitiveValue::Unit,
+      Primitive::bool => match read!(u8) {
+        0 => PrimitiveValue::bool(false),
+        1 => PrimitiveValue::bool(true),
+        _ => return Err(invalid("bool")),
+      },
+      Primitive::char => match char::from_u32(read!(u32)) {
+        Some(value) => PrimitiveValue::char(value),
+        None => return Err(invalid("char")),
+      },
+    })
+  }
+}
+
+/// The reasons [`decode`] can fail
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum DecodeError {
+  /// There weren't exactly as many bytes as the size of the type
+  Length {
+    type_name: &'static str,
+    expected: usize,
+    found: usize,
+  },
+  /// The type, or a type in it, doesn't have a defined layout
+  Unsupported {
+    type_name: &'static str,
+    reason: &'static str,
+  },
+  /// The bytes of a `bool` or `char` aren't a valid value
+  InvalidValue {
+    type_name: &'static str,
+    offset: usize,
+  },
+  /// No variant of the enum has the discriminant
+  UnknownDiscriminant {
+    type_name: &'static str,
+    offset: usize,
+    discriminant: PrimitiveValue,
+  },
+}
+
+impl fmt::Display for DecodeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DecodeError::Length {
+        type_name,
+        expected,
+        found,
+      } => write!(
+        f,
+        "`{type_name}` takes {expected} bytes, but {found} were given"
+      ),
+      DecodeError::Unsupported { type_name, reason } => {
+        write!(f, "`{type_name}` can't be decoded, because {reason}")
+      },
+      DecodeError::InvalidValue { type_name, offset } => {
+        write!(
+          f,
+          "the bytes at offset {offset} aren't a valid `{type_name}`"
+        )
+      },
+      DecodeError::UnknownDiscriminant {
+        type_name,
+        offset,
+        discriminant,
+      } => write!(
+        f,
+        "`{type_name}` has no variant with the discriminant {discriminant}, \
+         at offset {offset}"
+      ),
+    }
+  }
+}
+
+impl Error for DecodeError {}
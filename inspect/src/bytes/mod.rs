@@ -0,0 +1,62 @@
+//! Read and write values as bytes, using only the [`TypeInfo`] of their type
+//!
+//! [`decode`] reads values out of their representation in memory. This
+//! works for types whose layout is defined, so that bytes written by another
+//! build, or another program, can be read back: primitives, arrays, structs
+//! with `#[repr(C)]` or `#[repr(transparent)]`, and enums with a primitive
+//! representation like `#[repr(u8)]`, made only of such types. The offsets
+//! of the fields are taken from the `TypeInfo`, and the active variant of an
+//! enum from its discriminant.
+//!
+//! ```rust
+//! # use ::inspect::{TypeInfo, bytes::{self, Endian}};
+//! #[derive(TypeInfo, Default)]
+//! #[repr(u8)]
+//! enum Kind {
+//!   #[default]
+//!   Empty = 1,
+//!   Id(u16),
+//! }
+//!
+//! #[derive(TypeInfo)]
+//! #[repr(C)]
+//! struct Header {
+//!   flags: u16,
+//!   kind: Kind,
+//! }
+//!
+//! let data = [0x80, 0x01, 2, 0, 0, 42];
+//! let header = bytes::decode(&data, TypeInfo::of::<Header>(), Endian::Big)
+//!   .unwrap();
+//! assert_eq!(header.to_string(), "Header { flags: 32769, kind: Id(42) }");
+//! ```
+//!
+//! Values of any type can also be written to, and read back from, a compact
+//! binary format of their own, with [`pack`] and [`unpack`], without relying
+//! on their layout.
+//!
+//! [`TypeInfo`]: enum@crate::TypeInfo
+
+mod decode;
+mod packed;
+
+pub use self::{
+  decode::{decode, DecodeError, Decoded, DecodedField, PrimitiveValue},
+  packed::{pack, pack_into, unpack, unpack_from, PackError, MAX_DEPTH},
+};
+
+/// The order of the bytes of numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+  Little,
+  Big,
+}
+
+impl Endian {
+  /// The byte order of the target the program was compiled for
+  pub const NATIVE: Endian = if cfg!(target_endian = "big") {
+    Endian::Big
+  } else {
+    Endian::Little
+  };
+}
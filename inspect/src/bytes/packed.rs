@@ -0,0 +1,673 @@
+use {
+  super::Endian,
+  crate::{
+    type_info::{
+      Field, Pointer, Primitive, Sequence, Std, TypeInfo, TypeInfoProvider,
+    },
+    value::{DynValue, ValueBuilder, ValueRef},
+  },
+  ::core::{error::Error, fmt, ptr},
+};
+
+/// Write a value in the packed binary format, using only the [`TypeInfo`] of
+/// its type
+///
+/// The value is written field by field, with nothing between them:
+///
+/// - numbers are written in the byte order given by `endian`, with `usize`
+///   and `isize` written as 64 bits, `bool`s as a byte, and `char`s as a
+///   `u32`
+/// - strings, slices, `Vec`s and maps are written as their length, as a
+///   `u64`, followed by their bytes, items, or keys and values
+/// - arrays are written as their items, since their length is known
+/// - `Option`s and `Result`s are written as a byte, `0` for `None` and `Ok`,
+///   or `1` for `Some` and `Err`, followed by their contents
+/// - enums are written as the index of their variant, as a `u32`, followed by
+///   its fields
+/// - references and `Box`es are written as their targets
+///
+/// Fields marked `#[inspect(skip)]` are left out. Raw pointers and unions
+/// can't be written, since what they hold isn't known.
+///
+/// ```rust
+/// # use ::inspect::{TypeInfo, bytes::{self, Endian}};
+/// #[derive(TypeInfo, Debug, PartialEq)]
+/// #[inspect(constructible)]
+/// struct Frame {
+///   tick: u16,
+///   inputs: Vec<u8>,
+/// }
+///
+/// let frame = Frame { tick: 3, inputs: vec![7] };
+/// let packed = bytes::pack(&frame, Endian::Little).unwrap();
+/// assert_eq!(packed, [3, 0, 1, 0, 0, 0, 0, 0, 0, 0, 7]);
+///
+/// let unpacked =
+///   bytes::unpack(&packed, TypeInfo::of::<Frame>(), Endian::Little).unwrap();
+/// assert_eq!(unpacked.downcast::<Frame>().ok(), Some(frame));
+/// ```
+pub fn pack<T>(value: &T, endian: Endian) -> Result<Vec<u8>, PackError>
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  let mut bytes = Vec::new();
+  pack_into(ValueRef::new(value), endian, &mut bytes)?;
+  Ok(bytes)
+}
+
+/// Write a value in the packed binary format to the end of `bytes`. See
+/// [`pack`]
+///
+/// If the value can't be written, some of it may already have been.
+pub fn pack_into(
+  value: ValueRef<'_>,
+  endian: Endian,
+  bytes: &mut Vec<u8>,
+) -> Result<(), PackError> {
+  Packer { bytes, endian }.pack(value)
+}
+
+/// Read a value of the type described by `type_info` from the packed binary
+/// format written by [`pack`], which must take up all of `bytes`
+///
+/// Fields marked `#[inspect(skip)]` are given their type's [`Default`]
+/// value, when the type is known to implement it; see
+/// [`TypeInfo::default_fn`]. Only types that can be built from their parts
+/// can be read: not unsized types, references, raw pointers or unions, nor
+/// derived types that aren't [constructible](TypeInfo::is_constructible).
+///
+/// Since the bytes may come from anywhere, values nested more than
+/// [`MAX_DEPTH`] levels deep aren't read, and neither are sequences of
+/// zero-sized items that claim to have more items than there are bytes
+/// left.
+pub fn unpack(
+  bytes: &[u8],
+  type_info: &'static TypeInfo,
+  endian: Endian,
+) -> Result<DynValue, PackError> {
+  let mut rest = bytes;
+  let value = unpack_from(&mut rest, type_info, endian)?;
+  match rest.len() {
+    0 => Ok(value),
+    count => Err(PackError::TrailingBytes {
+      offset: bytes.len() - count,
+      count,
+    }),
+  }
+}
+
+/// Read a value of the type described by `type_info` from the start of
+/// `bytes`, and advance `bytes` past it. See [`unpack`]
+///
+/// This can read values one after another, from a stream of them.
+pub fn unpack_from(
+  bytes: &mut &[u8],
+  type_info: &'static TypeInfo,
+  endian: Endian,
+) -> Result<DynValue, PackError> {
+  let mut unpacker = Unpacker {
+    bytes,
+    offset: 0,
+    depth: 0,
+    endian,
+  };
+  let value = unpacker.unpack(type_info)?;
+  *bytes = &bytes[unpacker.offset..];
+  Ok(value)
+}
+
+/// How deeply values can be nested when they're unpacked, counting each
+/// field, item, and the contents of each `Option`, `Result` and `Box`, as a
+/// level
+pub const MAX_DEPTH: usize = 128;
+
+struct Packer<'b> {
+  bytes: &'b mut Vec<u8>,
+  endian: Endian,
+}
+
+impl Packer<'_> {
+  fn pack(&mut self, value: ValueRef<'_>) -> Result<(), PackError> {
+    let type_info = value.type_info();
+    let unsupported = |reason| PackError::Unsupported {
+      type_name: type_info.type_name(),
+      reason,
+    };
+
+    match type_info {
+      TypeInfo::Primitive(primitive) => unsafe {
+        self.primitive(value, *primitive)
+      },
+      TypeInfo::Sequence(Sequence::str) | TypeInfo::Std(Std::String) => {
+        let string = value.as_str().unwrap();
+        self.len(string.len());
+        self.bytes.extend_from_slice(string.as_bytes());
+      },
+      TypeInfo::Sequence(Sequence::Slice { .. })
+      | TypeInfo::Std(Std::Vec { .. }) => {
+        let items = value.items().unwrap();
+        self.len(items.len());
+        for item in items {
+          self.pack(item)?;
+        }
+      },
+      TypeInfo::Sequence(Sequence::Array { .. }) => {
+        for item in value.items().unwrap() {
+          self.pack(item)?;
+        }
+      },
+      TypeInfo::Std(Std::HashMap { .. } | Std::BTreeMap { .. }) => {
+        let entries = value.entries().unwrap();
+        self.len(entries.len());
+        for (key, value) in entries {
+          self.pack(key)?;
+          self.pack(value)?;
+        }
+      },
+      TypeInfo::Std(Std::Option { .. }) => match value.option().unwrap() {
+        None => self.bytes.push(0),
+        Some(some) => {
+          self.bytes.push(1);
+          self.pack(some)?;
+        },
+      },
+      TypeInfo::Std(Std::Result { .. }) => match value.result().unwrap() {
+        Ok(ok) => {
+          self.bytes.push(0);
+          self.pack(ok)?;
+        },
+        Err(err) => {
+          self.bytes.push(1);
+          self.pack(err)?;
+        },
+      },
+      TypeInfo::Std(Std::PhantomData { .. }) => {},
+      TypeInfo::Pointer(
+        Pointer::Ref { .. } | Pointer::RefMut { .. } | Pointer::Box { .. },
+      ) => self.pack(value.target().unwrap())?,
+      TypeInfo::Pointer(Pointer::RawConst { .. } | Pointer::RawMut { .. }) => {
+        return Err(unsupported("raw pointers aren't followed"))
+      },
+      TypeInfo::Tuple(_) | TypeInfo::Struct(_) => {
+        self.fields(value, type_info.fields())?
+      },
+      TypeInfo::Union(_) => {
+        return Err(unsupported(
+          "which field of a union is active isn't known",
+        ))
+      },
+      TypeInfo::Enum(_) => {
+        let (index, variant) = value.variant().unwrap();
+        (index as u32).write(self.endian, self.bytes);
+        self.fields(value, variant.fields())?
+      },
+    }
+    Ok(())
+  }
+
+  fn fields(
+    &mut self,
+    value: ValueRef<'_>,
+    fields: impl Iterator<Item = Field>,
+  ) -> Result<(), PackError> {
+    for field in fields.filter(|field| !field.attributes.skip) {
+      self.pack(unsafe { value.field_at(field.offset, field.type_info()) })?;
+    }
+    Ok(())
+  }
+
+  fn len(&mut self, len: usize) {
+    (len as u64).write(self.endian, self.bytes);
+  }
+
+  /// # Safety
+  ///
+  /// The value must be of the primitive type
+  unsafe fn primitive(&mut self, value: ValueRef<'_>, primitive: Primitive) {
+    let (endian, bytes) = (self.endian, &mut *self.bytes);
+    unsafe {
+      match primitive {
+        Primitive::u8 => value.cast::<u8>().write(endian, bytes),
+        Primitive::u16 => value.cast::<u16>().write(endian, bytes),
+        Primitive::u32 => value.cast::<u32>().write(endian, bytes),
+        Primitive::u64 => value.cast::<u64>().write(endian, bytes),
+        Primitive::u128 => value.cast::<u128>().write(endian, bytes),
+
+        Primitive::i8 => value.cast::<i8>().write(endian, bytes),
+        Primitive::i16 => value.cast::<i16>().write(endian, bytes),
+        Primitive::i32 => value.cast::<i32>().write(endian, bytes),
+        Primitive::i64 => value.cast::<i64>().write(endian, bytes),
+        Primitive::i128 => value.cast::<i128>().write(endian, bytes),
+
+        Primitive::f32 => value.cast::<f32>().write(endian, bytes),
+        Primitive::f64 => value.cast::<f64>().write(endian, bytes),
+
+        Primitive::usize => {
+          (*value.cast::<usize>() as u64).write(endian, bytes)
+        },
+        Primitive::isize => {
+          (*value.cast::<isize>() as i64).write(endian, bytes)
+        },
+        Primitive::Unit => {},
+        Primitive::bool => (*value.cast::<bool>() as u8).write(endian, bytes),
+        Primitive::char => (*value.cast::<char>() as u32).write(endian, bytes),
+      }
+    }
+  }
+}
+
+struct Unpacker<'b> {
+  bytes: &'b [u8],
+  /// The number of bytes read so far
+  offset: usize,
+  /// The number of values being read, each inside the last
+  depth: usize,
+  endian: Endian,
+}
+
+impl Unpacker<'_> {
+  fn unpack(
+    &mut self,
+    type_info: &'static TypeInfo,
+  ) -> Result<DynValue, PackError> {
+    if self.depth == MAX_DEPTH {
+      return Err(PackError::TooDeep {
+        type_name: type_info.type_name(),
+        offset: self.offset,
+      });
+    }
+    self.depth += 1;
+    let value = self.unpack_value(type_info);
+    self.depth -= 1;
+    value
+  }
+
+  fn unpack_value(
+    &mut self,
+    type_info: &'static TypeInfo,
+  ) -> Result<DynValue, PackError> {
+    let unsupported = |reason| PackError::Unsupported {
+      type_name: type_info.type_name(),
+      reason,
+    };
+    if type_info.layout().is_none() {
+      return Err(unsupported("it isn't statically sized"));
+    }
+    if !type_info.is_constructible() {
+      return Err(unsupported("it isn't marked `#[inspect(constructible)]`"));
+    }
+
+    Ok(match type_info {
+      TypeInfo::Primitive(primitive) => {
+        self.primitive(type_info, *primitive)?
+      },
+      TypeInfo::Std(Std::String) => {
+        let offset = self.offset;
+        let len = self.len(type_info)?;
+        let bytes = self.take(type_info, len)?.to_vec();
+        let string = String::from_utf8(bytes)
+          .map_err(|_| self.invalid(type_info, offset))?;
+        DynValue::new(string)
+      },
+      TypeInfo::Std(Std::Vec { item, info, .. }) => {
+        let len = self.items_len(type_info, &[(item.type_info_fn)()])?;
+        let mut vec = DynValue::new_default(type_info).unwrap();
+        let vec_ptr = vec.as_value_mut().as_mut_ptr();
+        for _ in 0..len {
+          let item = self.unpack((item.type_info_fn)())?;
+          unsafe { item.take_with(|item| (info.push_fn)(vec_ptr, item)) };
+        }
+        vec
+      },
+      TypeInfo::Sequence(Sequence::Array { info, .. }) => {
+        let item_type_info = type_info.item().unwrap();
+        let stride = item_type_info.size().unwrap();
+        let items = (0..info.array_length)
+          .map(|_| self.unpack(item_type_info))
+          .collect::<Result<Vec<_>, _>>()?;
+        unsafe {
+          DynValue::new_with(type_info, |array| {
+            for (index, item) in items.into_iter().enumerate() {
+              item.take_with(|item| {
+                ptr::copy_nonoverlapping(
+                  item,
+                  array.add(index * stride),
+                  stride,
+                )
+              });
+            }
+          })
+        }
+      },
+      TypeInfo::Std(
+        Std::HashMap { info, .. } | Std::BTreeMap { info, .. },
+      ) => {
+        let len = self.items_len(type_info, &type_info.generic_args())?;
+        let mut map = DynValue::new_default(type_info).unwrap();
+        let map_ptr = map.as_value_mut().as_mut_ptr();
+        for _ in 0..len {
+          let key = self.unpack((info.key_type_info_fn)())?;
+          let value = self.unpack((info.value_type_info_fn)())?;
+          unsafe {
+            key.take_with(|key| {
+              value.take_with(|value| (info.insert_fn)(map_ptr, key, value))
+            })
+          };
+        }
+        map
+      },
+      TypeInfo::Std(Std::Option { item, info, .. }) => {
+        match self.tag(type_info)? {
+          false => DynValue::new_default(type_info).unwrap(),
+          true => {
+            let some = self.unpack((item.type_info_fn)())?;
+            unsafe { some.wrap(type_info, info.some_fn) }
+          },
+        }
+      },
+      TypeInfo::Std(Std::Result { info, .. }) => match self.tag(type_info)? {
+        false => {
+          let ok = self.unpack((info.ok_type_info_fn)())?;
+          unsafe { ok.wrap(type_info, info.ok_fn) }
+        },
+        true => {
+          let err = self.unpack((info.err_type_info_fn)())?;
+          unsafe { err.wrap(type_info, info.err_fn) }
+        },
+      },
+      TypeInfo::Std(Std::PhantomData { .. }) => {
+        DynValue::new_default(type_info).unwrap()
+      },
+      TypeInfo::Pointer(Pointer::Box { item, .. })
+        if (item.type_info_fn)().layout().is_some() =>
+      {
+        // a `Box` of a sized type is a pointer to an allocation with the
+        // type's layout, made by the global allocator, just like a `DynValue`
+        let target = self.unpack((item.type_info_fn)())?.into_raw().0;
+        unsafe {
+          DynValue::new_with(type_info, |ptr| {
+            ptr.cast::<*mut u8>().write(target.as_ptr())
+          })
+        }
+      },
+      TypeInfo::Tuple(_) | TypeInfo::Struct(_) => {
+        let builder = ValueBuilder::new(type_info).unwrap();
+        self.fields(builder, type_info.fields())?
+      },
+      TypeInfo::Enum(_) => {
+        let offset = self.offset;
+        let index = self.number::<u32>(type_info)?;
+        let variant = type_info.variants().get(index as usize).ok_or(
+          PackError::UnknownVariant {
+            type_name: type_info.type_name(),
+            offset,
+            index,
+          },
+        )?;
+        let builder =
+          ValueBuilder::variant(type_info, variant.name()).unwrap();
+        self.fields(builder, variant.fields())?
+      },
+      TypeInfo::Pointer(_) => {
+        return Err(unsupported("only `Box`es of sized types can be built"))
+      },
+      TypeInfo::Union(_) => {
+        return Err(unsupported(
+          "which field of a union is active isn't known",
+        ))
+      },
+      TypeInfo::Sequence(_) => unreachable!(),
+    })
+  }
+
+  /// Read the fields that aren't skipped and build the value, filling in the
+  /// rest with their default values
+  fn fields(
+    &mut self,
+    mut builder: ValueBuilder,
+    fields: impl Iterator<Item = Field>,
+  ) -> Result<DynValue, PackError> {
+    for field in fields {
+      let value = match field.attributes.skip {
+        false => self.unpack(field.type_info())?,
+        true => DynValue::new_default(field.type_info()).ok_or(
+          PackError::MissingDefault {
+            type_name: field.type_info().type_name(),
+          },
+        )?,
+      };
+      builder.push(value).unwrap();
+    }
+    Ok(builder.build().unwrap())
+  }
+
+  fn primitive(
+    &mut self,
+    type_info: &'static TypeInfo,
+    primitive: Primitive,
+  ) -> Result<DynValue, PackError> {
+    let offset = self.offset;
+    macro_rules! read {
+      ($ty:ident) => {
+        self.number::<$ty>(type_info)?
+      };
+    }
+
+    Ok(match primitive {
+      Primitive::u8 => DynValue::new(read!(u8)),
+      Primitive::u16 => DynValue::new(read!(u16)),
+      Primitive::u32 => DynValue::new(read!(u32)),
+      Primitive::u64 => DynValue::new(read!(u64)),
+      Primitive::u128 => DynValue::new(read!(u128)),
+
+      Primitive::i8 => DynValue::new(read!(i8)),
+      Primitive::i16 => DynValue::new(read!(i16)),
+      Primitive::i32 => DynValue::new(read!(i32)),
+      Primitive::i64 => DynValue::new(read!(i64)),
+      Primitive::i128 => DynValue::new(read!(i128)),
+
+      Primitive::f32 => DynValue::new(read!(f32)),
+      Primitive::f64 => DynValue::new(read!(f64)),
+
+      Primitive::usize => DynValue::new(
+        usize::try_from(read!(u64))
+          .map_err(|_| self.invalid(type_info, offset))?,
+      ),
+      Primitive::isize => DynValue::new(
+        isize::try_from(read!(i64))
+          .map_err(|_| self.invalid(type_info, offset))?,
+      ),
+      Primitive::Unit => DynValue::new(()),
+      Primitive::bool => match read!(u8) {
+        0 => DynValue::new(false),
+        1 => DynValue::new(true),
+        _ => return Err(self.invalid(type_info, offset)),
+      },
+      Primitive::char => match char::from_u32(read!(u32)) {
+        Some(value) => DynValue::new(value),
+        None => return Err(self.invalid(type_info, offset)),
+      },
+    })
+  }
+
+  /// Read the length of a string, sequence or map
+  fn len(&mut self, type_info: &'static TypeInfo) -> Result<usize, PackError> {
+    let offset = self.offset;
+    let len = self.number::<u64>(type_info)?;
+    usize::try_from(len).map_err(|_| self.invalid(type_info, offset))
+  }
+
+  /// Read the length of a `Vec` or map, whose items are made of
+  /// `item_types`. Items that are all zero-sized take up no bytes, so there
+  /// can't be more of them than there are bytes left, or a few bytes could
+  /// claim any number of them
+  fn items_len(
+    &mut self,
+    type_info: &'static TypeInfo,
+    item_types: &[&'static TypeInfo],
+  ) -> Result<usize, PackError> {
+    let offset = self.offset;
+    let len = self.len(type_info)?;
+    let zero_sized = item_types.iter().all(|item| item.size() == Some(0));
+    if zero_sized && len > self.bytes.len() - self.offset {
+      return Err(self.invalid(type_info, offset));
+    }
+    Ok(len)
+  }
+
+  /// Read the tag of an `Option` or `Result`
+  fn tag(&mut self, type_info: &'static TypeInfo) -> Result<bool, PackError> {
+    let offset = self.offset;
+    match self.number::<u8>(type_info)? {
+      0 => Ok(false),
+      1 => Ok(true),
+      _ => Err(self.invalid(type_info, offset)),
+    }
+  }
+
+  fn number<N>(&mut self, type_info: &'static TypeInfo) -> Result<N, PackError>
+  where
+    N: Number,
+  {
+    let endian = self.endian;
+    Ok(N::read(self.take(type_info, size_of::<N>())?, endian))
+  }
+
+  fn take(
+    &mut self,
+    type_info: &'static TypeInfo,
+    len: usize,
+  ) -> Result<&[u8], PackError> {
+    let end = self
+      .offset
+      .checked_add(len)
+      .filter(|&end| end <= self.bytes.len())
+      .ok_or(PackError::UnexpectedEnd {
+        type_name: type_info.type_name(),
+        offset: self.offset,
+      })?;
+    let bytes = &self.bytes[self.offset..end];
+    self.offset = end;
+    Ok(bytes)
+  }
+
+  fn invalid(&self, type_info: &'static TypeInfo, offset: usize) -> PackError {
+    PackError::InvalidValue {
+      type_name: type_info.type_name(),
+      offset,
+    }
+  }
+}
+
+/// A number that's written in either byte order
+trait Number: Copy {
+  fn write(&self, endian: Endian, bytes: &mut Vec<u8>);
+
+  /// Read the number from exactly as many bytes as it takes up
+  fn read(bytes: &[u8], endian: Endian) -> Self;
+}
+
+macro_rules! impl_number {
+  ($($ty:ident),*) => {$(
+    impl Number for $ty {
+      fn write(&self, endian: Endian, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&match endian {
+          Endian::Little => self.to_le_bytes(),
+          Endian::Big => self.to_be_bytes(),
+        })
+      }
+
+      fn read(bytes: &[u8], endian: Endian) -> Self {
+        let bytes = bytes.try_into().unwrap();
+        match endian {
+          Endian::Little => $ty::from_le_bytes(bytes),
+          Endian::Big => $ty::from_be_bytes(bytes),
+        }
+      }
+    }
+  )*};
+}
+
+impl_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// The reasons [`pack`] and [`unpack`] can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PackError {
+  /// The type, or a type in it, can't be written or read
+  Unsupported {
+    type_name: &'static str,
+    reason: &'static str,
+  },
+  /// The bytes ended in the middle of a value
+  UnexpectedEnd {
+    type_name: &'static str,
+    offset: usize,
+  },
+  /// The bytes were read, but more were left after the value
+  TrailingBytes { offset: usize, count: usize },
+  /// The bytes at the offset aren't a valid value of the type, like a
+  /// `bool` other than `0` or `1`, or a string that isn't UTF-8
+  InvalidValue {
+    type_name: &'static str,
+    offset: usize,
+  },
+  /// The enum has no variant with the index
+  UnknownVariant {
+    type_name: &'static str,
+    offset: usize,
+    index: u32,
+  },
+  /// A field marked `#[inspect(skip)]` has a type with no known default
+  /// value
+  MissingDefault { type_name: &'static str },
+  /// The value at the offset is nested more than [`MAX_DEPTH`] levels deep
+  TooDeep {
+    type_name: &'static str,
+    offset: usize,
+  },
+}
+
+impl fmt::Display for PackError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PackError::Unsupported { type_name, reason } => {
+        write!(f, "`{type_name}` can't be packed, because {reason}")
+      },
+      PackError::UnexpectedEnd { type_name, offset } => write!(
+        f,
+        "the bytes ended at offset {offset}, in the middle of a \
+         `{type_name}`"
+      ),
+      PackError::TrailingBytes { offset, count } => {
+        write!(f, "{count} bytes were left over, from offset {offset}")
+      },
+      PackError::InvalidValue { type_name, offset } => {
+        write!(
+          f,
+          "the bytes at offset {offset} aren't a valid `{type_name}`"
+        )
+      },
+      PackError::UnknownVariant {
+        type_name,
+        offset,
+        index,
+      } => write!(
+        f,
+        "`{type_name}` has no variant with the index {index}, at offset \
+         {offset}"
+      ),
+      PackError::MissingDefault { type_name } => write!(
+        f,
+        "skipped field of type `{type_name}` has no default value"
+      ),
+      PackError::TooDeep { type_name, offset } => write!(
+        f,
+        "the `{type_name}` at offset {offset} is nested more than \
+         {MAX_DEPTH} levels deep"
+      ),
+    }
+  }
+}
+
+impl Error for PackError {}
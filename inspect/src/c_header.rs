@@ -0,0 +1,646 @@
+//! Generate C headers declaring `#[repr(C)]` types, so they can be shared with
+//! C code
+//!
+//! Structs and unions need `#[repr(C)]` (or `#[repr(transparent)]`), and
+//! enums need a primitive representation, such as `#[repr(u8)]`, and no
+//! fields. Every type is checked against its recorded layout with
+//! `_Static_assert`s, so a header that has gone stale fails to compile.
+//!
+//! Pointers to types that can't be represented in C are refused like any
+//! other field, unless [`CHeader::opaque_pointers`] declares them as pointers
+//! to `void`, so Rust types can be passed through C as opaque handles.
+//!
+//! ```rust
+//! # use ::inspect::{TypeInfo, c_header};
+//! /// A point on the screen
+//! #[derive(TypeInfo)]
+//! #[repr(C)]
+//! struct Point {
+//!   x: i32,
+//!   y: i32,
+//! }
+//!
+//! let header = c_header::generate::<Point>().unwrap();
+//! assert!(header.contains("typedef struct Point Point;"));
+//! assert!(header.contains("  int32_t y;"));
+//! assert!(header.contains("_Static_assert(offsetof(Point, y) == 4"));
+//! ```
+
+use {
+  crate::{
+    codegen::line_comment,
+    type_info::{
+      Enum, EnumVariantInfo, Field, Pointer, Primitive, Sequence, Std, Struct,
+      Tuple, TypeInfo, TypeInfoProvider,
+    },
+  },
+  ::core::{any::TypeId, error::Error, fmt},
+  ::std::collections::HashMap,
+};
+
+/// Generate a C header declaring `T`, and every type it refers to
+pub fn generate<T>() -> Result<String, CHeaderError>
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  let mut header = CHeader::new();
+  header.add::<T>()?;
+  Ok(header.to_string())
+}
+
+/// A C header that types can be added to one at a time. Formatting it with
+/// `Display` writes out the header
+#[derive(Clone, Default)]
+pub struct CHeader {
+  /// The C names of the types that have been declared
+  names: HashMap<TypeId, String>,
+  /// The type each C name was given to
+  named: HashMap<String, &'static str>,
+  /// `typedef`s, which come before any definitions so that types can point
+  /// to each other
+  declarations: Vec<String>,
+  /// Definitions, ordered so that types are defined before they're used by
+  /// value
+  definitions: Vec<String>,
+  /// Whether pointers to types that can't be declared point to `void`
+  opaque_pointers: bool,
+}
+
+impl CHeader {
+  pub fn new() -> Self {
+    CHeader::default()
+  }
+
+  /// Whether pointers to types that can't be declared in C are declared as
+  /// pointers to `void`, rather than refused. Off by default
+  pub fn opaque_pointers(&mut self, opaque_pointers: bool) -> &mut Self {
+    self.opaque_pointers = opaque_pointers;
+    self
+  }
+
+  /// Declare `T`, and every type it refers to, in the header
+  pub fn add<T>(&mut self) -> Result<&mut Self, CHeaderError>
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    self.add_type_info(TypeInfo::of::<T>())
+  }
+
+  /// Declare the type described by `type_info`, and every type it refers to,
+  /// in the header. The header is left unchanged if it returns an error
+  pub fn add_type_info(
+    &mut self,
+    type_info: &'static TypeInfo,
+  ) -> Result<&mut Self, CHeaderError> {
+    if !matches!(
+      type_info,
+      TypeInfo::Struct(..) | TypeInfo::Union(..) | TypeInfo::Enum(..)
+    ) {
+      return Err(CHeaderError::Type {
+        type_name: type_info.type_name(),
+        reason: "only structs, unions and enums can be declared",
+      });
+    }
+
+    let snapshot = self.clone();
+    match self.declare(type_info) {
+      Ok(_) => Ok(self),
+      Err(err) => {
+        *self = snapshot;
+        Err(err)
+      },
+    }
+  }
+
+  /// Declare a struct, union or enum, returning its name in C
+  fn declare(
+    &mut self,
+    type_info: &'static TypeInfo,
+  ) -> Result<String, CHeaderError> {
+    if let Some(name) = self.names.get(&type_info.type_id()) {
+      return Ok(name.clone());
+    }
+
+    let type_name = type_info.type_name();
+    let name = type_info.identifier();
+    if is_keyword(&name) {
+      return Err(CHeaderError::Keyword { type_name, name });
+    }
+    if let Some(other) = self.named.insert(name.clone(), type_name) {
+      return Err(CHeaderError::NameClash {
+        name,
+        first: other,
+        second: type_name,
+      });
+    }
+    self.names.insert(type_info.type_id(), name.clone());
+
+    let unsupported = |reason| CHeaderError::Type { type_name, reason };
+    let repr = type_info.repr().unwrap();
+    if repr.packed.is_some() {
+      return Err(unsupported("`#[repr(packed)]` can't be expressed in C"));
+    }
+
+    match type_info {
+      TypeInfo::Struct(..) | TypeInfo::Union(..) if repr.transparent => {
+        self.define_transparent(type_info, &name)?
+      },
+      TypeInfo::Struct(..) | TypeInfo::Union(..) if !repr.c => {
+        return Err(unsupported(
+          "it doesn't have `#[repr(C)]`, so its layout is unspecified",
+        ))
+      },
+      TypeInfo::Struct(Struct::UnitStruct { .. }) => {
+        return Err(unsupported("C has no zero-sized types"))
+      },
+      TypeInfo::Struct(..) => {
+        self.define_fields("struct", type_info, &name)?
+      },
+      TypeInfo::Union(..) => self.define_fields("union", type_info, &name)?,
+      TypeInfo::Enum(Enum::Enum { variants, .. }) => {
+        let Some(primitive) = repr.primitive else {
+          return Err(unsupported(
+            "enums need a primitive representation, such as `#[repr(u8)]`",
+          ));
+        };
+        let signed = matches!(
+          primitive,
+          Primitive::i8
+            | Primitive::i16
+            | Primitive::i32
+            | Primitive::i64
+            | Primitive::i128
+            | Primitive::isize
+        );
+        let int = primitive_c_type(primitive).map_err(unsupported)?;
+
+        let mut definition = line_comment(type_info.doc(), "");
+        definition += &format!("enum {name} {{\n");
+        for variant in variants.variant_infos {
+          let EnumVariantInfo::Unit {
+            variant_name,
+            variant_discriminant_value: Some(value),
+            variant_doc,
+            ..
+          } = variant
+          else {
+            return Err(unsupported(
+              "only enums without fields are supported",
+            ));
+          };
+          definition += &line_comment(*variant_doc, "  ");
+          if signed {
+            let value = *value as isize;
+            definition += &format!("  {name}_{variant_name} = {value},\n");
+          } else {
+            definition += &format!("  {name}_{variant_name} = {value},\n");
+          }
+        }
+        definition += "};\n";
+        definition += &size_asserts(type_info, &name);
+
+        self.declarations.push(format!("typedef {int} {name};"));
+        self.definitions.push(definition);
+      },
+      _ => unreachable!("only structs, unions and enums are declared"),
+    }
+
+    Ok(name)
+  }
+
+  /// Define a `#[repr(C)]` struct or union
+  fn define_fields(
+    &mut self,
+    keyword: &str,
+    type_info: &'static TypeInfo,
+    name: &str,
+  ) -> Result<(), CHeaderError> {
+    // declared before the fields, so that they can point back to the type
+    self
+      .declarations
+      .push(format!("typedef {keyword} {name} {name};"));
+
+    let mut body = String::new();
+    let mut offsets = String::new();
+    for field in type_info.fields() {
+      if is_zero_sized(field.type_info()) {
+        continue;
+      }
+      let field_name = c_name(&field);
+      if is_keyword(&field_name) {
+        return Err(CHeaderError::Keyword {
+          type_name: type_info.type_name(),
+          name: field_name,
+        });
+      }
+      let c_type = self
+        .c_type(field.type_info())
+        .map_err(|problem| problem.in_field(type_info.type_name(), &field))?;
+      let align = match type_info.repr().unwrap().align {
+        Some(align) if body.is_empty() => format!("_Alignas({align}) "),
+        _ => String::new(),
+      };
+      body += &line_comment(field.attributes.doc, "  ");
+      body += &format!("  {align}{};\n", c_type.declare(&field_name));
+      offsets += &format!(
+        "_Static_assert(offsetof({name}, {0}) == {1}, \"offset of \
+         {name}.{0}\");\n",
+        field_name, field.offset,
+      );
+    }
+    if body.is_empty() {
+      return Err(CHeaderError::Type {
+        type_name: type_info.type_name(),
+        reason: "C has no zero-sized types",
+      });
+    }
+
+    let mut definition = line_comment(type_info.doc(), "");
+    definition += &format!("{keyword} {name} {{\n{body}}};\n");
+    definition += &size_asserts(type_info, name);
+    definition += &offsets;
+    self.definitions.push(definition);
+    Ok(())
+  }
+
+  /// Define a `#[repr(transparent)]` struct or union as its only non
+  /// zero-sized field
+  fn define_transparent(
+    &mut self,
+    type_info: &'static TypeInfo,
+    name: &str,
+  ) -> Result<(), CHeaderError> {
+    let Some(field) = type_info
+      .fields()
+      .find(|field| !is_zero_sized(field.type_info()))
+    else {
+      return Err(CHeaderError::Type {
+        type_name: type_info.type_name(),
+        reason: "C has no zero-sized types",
+      });
+    };
+    let c_type = self
+      .c_type(field.type_info())
+      .map_err(|problem| problem.in_field(type_info.type_name(), &field))?;
+
+    let mut definition = line_comment(type_info.doc(), "");
+    definition += &format!("typedef {};\n", c_type.declare(name));
+    definition += &size_asserts(type_info, name);
+    self.definitions.push(definition);
+    Ok(())
+  }
+
+  /// The C type of a field, defining any types it refers to
+  fn c_type(
+    &mut self,
+    type_info: &'static TypeInfo,
+  ) -> Result<CType, Problem> {
+    let unspecified = Problem::Reason("its layout is unspecified");
+    match type_info {
+      TypeInfo::Primitive(primitive) => primitive_c_type(*primitive)
+        .map(|name| CType::Name(name.to_string()))
+        .map_err(Problem::Reason),
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::Array { info, .. } if info.array_length == 0 => {
+          Err(Problem::Reason("C has no zero-sized types"))
+        },
+        Sequence::Array { item, info, .. } => Ok(CType::Array {
+          item: Box::new(self.c_type((item.type_info_fn)())?),
+          length: info.array_length,
+        }),
+        Sequence::str | Sequence::Slice { .. } => {
+          Err(Problem::Reason("unsized types can't be stored by value"))
+        },
+      },
+      TypeInfo::Std(Std::Option { item, .. }) => {
+        // `None` is a null pointer
+        match (item.type_info_fn)() {
+          pointer @ TypeInfo::Pointer(
+            Pointer::Ref { .. } | Pointer::RefMut { .. } | Pointer::Box { .. },
+          ) => self.c_type(pointer),
+          _ => Err(Problem::Reason(
+            "only `Option`s of references and boxes have a C-compatible \
+             layout",
+          )),
+        }
+      },
+      TypeInfo::Std(..) => Err(unspecified),
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { item, .. } | Pointer::RawConst { item, .. } => {
+          self.pointer((item.type_info_fn)(), true)
+        },
+        Pointer::RefMut { item, .. }
+        | Pointer::RawMut { item, .. }
+        | Pointer::Box { item, .. } => {
+          self.pointer((item.type_info_fn)(), false)
+        },
+      },
+      TypeInfo::Tuple(Tuple::Tuple { .. }) => {
+        Err(Problem::Reason("tuples have an unspecified layout"))
+      },
+      TypeInfo::Struct(..) | TypeInfo::Union(..) | TypeInfo::Enum(..) => self
+        .declare(type_info)
+        .map(CType::Name)
+        .map_err(Problem::Nested),
+    }
+  }
+
+  fn pointer(
+    &mut self,
+    target: &'static TypeInfo,
+    is_const: bool,
+  ) -> Result<CType, Problem> {
+    if target.size().is_none() {
+      return Err(Problem::Reason(
+        "pointers to unsized types are wide pointers, which C has no \
+         equivalent of",
+      ));
+    }
+    let snapshot = self.clone();
+    let target = match self.c_type(target) {
+      Ok(target) => target,
+      Err(_) if self.opaque_pointers => {
+        // the target is opaque to C
+        *self = snapshot;
+        CType::Name("void".to_string())
+      },
+      Err(problem) => return Err(problem),
+    };
+    Ok(CType::Pointer {
+      target: Box::new(target),
+      is_const,
+    })
+  }
+}
+
+impl fmt::Display for CHeader {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "#pragma once")?;
+    writeln!(f)?;
+    writeln!(f, "#include <stdbool.h>")?;
+    writeln!(f, "#include <stddef.h>")?;
+    writeln!(f, "#include <stdint.h>")?;
+    if !self.declarations.is_empty() {
+      writeln!(f)?;
+    }
+    for declaration in &self.declarations {
+      writeln!(f, "{declaration}")?;
+    }
+    for definition in &self.definitions {
+      writeln!(f)?;
+      f.write_str(definition)?;
+    }
+    Ok(())
+  }
+}
+
+/// Why a type couldn't be declared in a [`CHeader`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CHeaderError {
+  /// The type doesn't have a C-compatible layout
+  Type {
+    type_name: &'static str,
+    reason: &'static str,
+  },
+  /// A field of a struct or union has a type that doesn't have a
+  /// C-compatible layout
+  Field {
+    type_name: &'static str,
+    field_name: String,
+    field_type: &'static str,
+    reason: &'static str,
+  },
+  /// A type that a field of a struct or union refers to couldn't be
+  /// declared
+  Nested {
+    type_name: &'static str,
+    field_name: String,
+    error: Box<CHeaderError>,
+  },
+  /// The name of the type, or one of its fields, is a keyword in C
+  Keyword {
+    type_name: &'static str,
+    name: String,
+  },
+  /// Two different types would have the same name in C
+  NameClash {
+    name: String,
+    first: &'static str,
+    second: &'static str,
+  },
+}
+
+impl fmt::Display for CHeaderError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      CHeaderError::Type { type_name, reason } => {
+        write!(f, "`{type_name}` can't be declared in C: {reason}")
+      },
+      CHeaderError::Field {
+        type_name,
+        field_name,
+        field_type,
+        reason,
+      } => write!(
+        f,
+        "field `{field_name}` of `{type_name}` has type `{field_type}`, which \
+         can't be declared in C: {reason}"
+      ),
+      CHeaderError::Nested {
+        type_name,
+        field_name,
+        error,
+      } => write!(f, "field `{field_name}` of `{type_name}`: {error}"),
+      CHeaderError::Keyword { type_name, name } => {
+        write!(f, "`{name}`, in `{type_name}`, is a keyword in C")
+      },
+      CHeaderError::NameClash {
+        name,
+        first,
+        second,
+      } => write!(
+        f,
+        "`{first}` and `{second}` would both be called `{name}` in C"
+      ),
+    }
+  }
+}
+
+impl Error for CHeaderError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      CHeaderError::Nested { error, .. } => Some(&**error),
+      _ => None,
+    }
+  }
+}
+
+/// Why the type of a field couldn't be represented in C
+enum Problem {
+  /// The type itself isn't C-compatible
+  Reason(&'static str),
+  /// A struct, union or enum the type refers to couldn't be declared
+  Nested(CHeaderError),
+}
+
+impl Problem {
+  fn in_field(self, type_name: &'static str, field: &Field) -> CHeaderError {
+    match self {
+      Problem::Reason(reason) => CHeaderError::Field {
+        type_name,
+        field_name: field.label(),
+        field_type: field.type_info().type_name(),
+        reason,
+      },
+      Problem::Nested(err) => CHeaderError::Nested {
+        type_name,
+        field_name: field.label(),
+        error: Box::new(err),
+      },
+    }
+  }
+}
+
+/// A C type, which can be declared with a name
+enum CType {
+  Name(String),
+  Pointer { target: Box<CType>, is_const: bool },
+  Array { item: Box<CType>, length: usize },
+}
+
+impl CType {
+  /// Declare `declarator` as a value of the type, i.e. `int32_t *name[4]`
+  fn declare(&self, declarator: &str) -> String {
+    self.declare_qualified(declarator.to_string(), false)
+  }
+
+  fn declare_qualified(&self, declarator: String, is_const: bool) -> String {
+    match self {
+      CType::Name(name) if is_const => format!("const {name} {declarator}"),
+      CType::Name(name) => format!("{name} {declarator}"),
+      CType::Pointer {
+        target,
+        is_const: target_is_const,
+      } => {
+        let declarator = if is_const {
+          format!("*const {declarator}")
+        } else {
+          format!("*{declarator}")
+        };
+        let declarator = match **target {
+          CType::Array { .. } => format!("({declarator})"),
+          _ => declarator,
+        };
+        target.declare_qualified(declarator, *target_is_const)
+      },
+      CType::Array { item, length } => {
+        item.declare_qualified(format!("{declarator}[{length}]"), is_const)
+      },
+    }
+  }
+}
+
+/// The name of a field in C; the fields of tuple structs are called `_0`,
+/// `_1`, etc
+fn c_name(field: &Field) -> String {
+  match field.name {
+    Some(name) => name.to_string(),
+    None => format!("_{}", field.index),
+  }
+}
+
+/// Zero-sized markers, which have no equivalent in C, but take up no space
+/// either; so they're left out of structs
+fn is_zero_sized(type_info: &TypeInfo) -> bool {
+  matches!(
+    type_info,
+    TypeInfo::Primitive(Primitive::Unit)
+      | TypeInfo::Std(Std::PhantomData { .. })
+  )
+}
+
+fn primitive_c_type(
+  primitive: Primitive,
+) -> Result<&'static str, &'static str> {
+  Ok(match primitive {
+    Primitive::u8 => "uint8_t",
+    Primitive::u16 => "uint16_t",
+    Primitive::u32 => "uint32_t",
+    Primitive::u64 => "uint64_t",
+    Primitive::usize => "uintptr_t",
+    Primitive::i8 => "int8_t",
+    Primitive::i16 => "int16_t",
+    Primitive::i32 => "int32_t",
+    Primitive::i64 => "int64_t",
+    Primitive::isize => "intptr_t",
+    Primitive::f32 => "float",
+    Primitive::f64 => "double",
+    Primitive::bool => "bool",
+    // a unicode scalar value
+    Primitive::char => "uint32_t",
+    Primitive::u128 | Primitive::i128 => {
+      return Err("128 bit integers aren't part of standard C")
+    },
+    Primitive::Unit => return Err("C has no zero-sized types"),
+  })
+}
+
+fn is_keyword(name: &str) -> bool {
+  const KEYWORDS: [&str; 44] = [
+    "auto",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+    "_Alignas",
+    "_Alignof",
+    "_Atomic",
+    "_Bool",
+    "_Complex",
+    "_Generic",
+    "_Imaginary",
+    "_Noreturn",
+    "_Static_assert",
+    "_Thread_local",
+  ];
+  KEYWORDS.contains(&name)
+}
+
+fn size_asserts(type_info: &TypeInfo, name: &str) -> String {
+  format!(
+    "_Static_assert(sizeof({name}) == {}, \"size of {name}\");\n\
+     _Static_assert(_Alignof({name}) == {}, \"alignment of {name}\");\n",
+    type_info.size().unwrap(),
+    type_info.align().unwrap(),
+  )
+}
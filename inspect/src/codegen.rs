@@ -0,0 +1,73 @@
+//! Helpers shared by the generators of definitions in other languages
+
+use {
+  crate::type_info::TypeInfo,
+  ::core::{any::TypeId, hash::Hash},
+  ::std::collections::{HashMap, HashSet},
+};
+
+/// The names given to the types defined in a generated file, which are kept
+/// distinct from each other
+#[derive(Clone)]
+pub(crate) struct Names<K = TypeId> {
+  names: HashMap<K, String>,
+  taken: HashSet<String>,
+}
+
+impl<K> Default for Names<K> {
+  fn default() -> Self {
+    Names {
+      names: HashMap::new(),
+      taken: HashSet::new(),
+    }
+  }
+}
+
+impl<K> Names<K>
+where
+  K: Hash + Eq,
+{
+  /// The name `key` was given, if it's been named
+  pub(crate) fn get(&self, key: &K) -> Option<&String> {
+    self.names.get(key)
+  }
+
+  /// Give `key` the first of the `preferred` names that isn't taken, or else
+  /// the last of them followed by `_2`, `_3`, etc
+  pub(crate) fn insert(&mut self, key: K, preferred: &[String]) -> String {
+    let last = preferred.last().expect("a name to prefer");
+    let name = preferred
+      .iter()
+      .cloned()
+      .chain((2..).map(|n| format!("{last}_{n}")))
+      .find(|name| !self.taken.contains(name))
+      .unwrap();
+    self.taken.insert(name.clone());
+    self.names.insert(key, name.clone());
+    name
+  }
+}
+
+impl Names {
+  /// Name a struct or enum after its identifier, or its full path if another
+  /// type has that name already. Types are named before their fields are
+  /// defined, so that they can refer to themselves
+  pub(crate) fn insert_type(
+    &mut self,
+    type_info: &'static TypeInfo,
+  ) -> String {
+    self.insert(
+      type_info.type_id(),
+      &[type_info.identifier(), type_info.qualified_identifier()],
+    )
+  }
+}
+
+/// A doc comment as `//` comments, each line ending in a new line
+pub(crate) fn line_comment(doc: Option<&str>, indent: &str) -> String {
+  doc
+    .into_iter()
+    .flat_map(str::lines)
+    .map(|line| format!("{indent}// {line}").trim_end().to_string() + "\n")
+    .collect()
+}
@@ -0,0 +1,221 @@
+use {
+  crate::{
+    type_info::{
+      EnumVariantInfo, Field, Pointer, Primitive, Sequence, Std, Struct,
+      TypeInfo, TypeInfoProvider,
+    },
+    value::ValueRef,
+  },
+  ::core::fmt,
+};
+
+/// Format a value with [`fmt::Debug`], using only the [`TypeInfo`] of its
+/// type; so the type doesn't need to implement `Debug` itself.
+///
+/// Fields marked with `#[inspect(skip)]` are left out. References and boxes
+/// are followed, but raw pointers only show their address.
+///
+/// The precision controls how many levels deep the value is printed, in the
+/// same way as the `Display` implementation for [`TypeInfo`].
+///
+/// ```rust
+/// # use ::inspect::TypeInfo;
+/// #[derive(TypeInfo)]
+/// struct Point {
+///   x: i32,
+///   y: Option<Box<Point>>,
+/// }
+///
+/// let point = Point { x: 1, y: Some(Box::new(Point { x: 2, y: None })) };
+/// assert_eq!(
+///   format!("{:?}", inspect::debug(&point)),
+///   "Point { x: 1, y: Some(Point { x: 2, y: None }) }",
+/// );
+/// assert_eq!(
+///   format!("{:.2?}", inspect::debug(&point)),
+///   "Point { x: 1, y: Some(..) }",
+/// );
+/// ```
+pub fn debug<T>(value: &T) -> DebugValue<'_>
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  DebugValue {
+    value: ValueRef::new(value),
+    depth: None,
+  }
+}
+
+/// Formats a value with [`fmt::Debug`] using its [`TypeInfo`]. See [`debug`]
+#[derive(Clone, Copy)]
+pub struct DebugValue<'v> {
+  value: ValueRef<'v>,
+  /// The remaining depth, or `None` to take it from the formatter
+  depth: Option<usize>,
+}
+
+impl<'v> DebugValue<'v> {
+  /// Format a value behind a [`ValueRef`]
+  pub fn new(value: ValueRef<'v>) -> Self {
+    DebugValue { value, depth: None }
+  }
+}
+
+impl fmt::Debug for DebugValue<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let depth = self.depth.or(f.precision()).unwrap_or(10);
+    let value = self.value;
+    // nested values are formatted with the same formatter, so the remaining
+    // depth is passed along explicitly rather than through the precision
+    let child = |value| DebugValue {
+      value,
+      depth: Some(depth.saturating_sub(1)),
+    };
+    let type_info = value.type_info();
+    let name = type_info.bare_name();
+
+    match type_info {
+      TypeInfo::Primitive(primitive) => unsafe {
+        // the formatter's options are deliberately not forwarded, so that
+        // the precision doesn't affect floats
+        match primitive {
+          Primitive::u8 => write!(f, "{:?}", value.cast::<u8>()),
+          Primitive::u16 => write!(f, "{:?}", value.cast::<u16>()),
+          Primitive::u32 => write!(f, "{:?}", value.cast::<u32>()),
+          Primitive::u64 => write!(f, "{:?}", value.cast::<u64>()),
+          Primitive::u128 => write!(f, "{:?}", value.cast::<u128>()),
+
+          Primitive::i8 => write!(f, "{:?}", value.cast::<i8>()),
+          Primitive::i16 => write!(f, "{:?}", value.cast::<i16>()),
+          Primitive::i32 => write!(f, "{:?}", value.cast::<i32>()),
+          Primitive::i64 => write!(f, "{:?}", value.cast::<i64>()),
+          Primitive::i128 => write!(f, "{:?}", value.cast::<i128>()),
+
+          Primitive::f32 => write!(f, "{:?}", value.cast::<f32>()),
+          Primitive::f64 => write!(f, "{:?}", value.cast::<f64>()),
+
+          Primitive::usize => write!(f, "{:?}", value.cast::<usize>()),
+          Primitive::isize => write!(f, "{:?}", value.cast::<isize>()),
+          Primitive::Unit => f.write_str("()"),
+          Primitive::bool => write!(f, "{:?}", value.cast::<bool>()),
+          Primitive::char => write!(f, "{:?}", value.cast::<char>()),
+        }
+      },
+      TypeInfo::Sequence(Sequence::str) | TypeInfo::Std(Std::String) => {
+        write!(f, "{:?}", value.as_str().unwrap())
+      },
+      TypeInfo::Std(Std::PhantomData { .. }) => f.write_str(name),
+      _ if depth == 0 => f.write_str(".."),
+      TypeInfo::Sequence(Sequence::Slice { .. } | Sequence::Array { .. })
+      | TypeInfo::Std(Std::Vec { .. }) => f
+        .debug_list()
+        .entries(value.items().unwrap().map(child))
+        .finish(),
+      TypeInfo::Std(Std::HashMap { .. } | Std::BTreeMap { .. }) => f
+        .debug_map()
+        .entries(
+          value
+            .entries()
+            .unwrap()
+            .into_iter()
+            .map(|(key, value)| (child(key), child(value))),
+        )
+        .finish(),
+      TypeInfo::Std(Std::Option { .. }) => match value.option().unwrap() {
+        Some(some) => f.debug_tuple("Some").field(&child(some)).finish(),
+        None => f.write_str("None"),
+      },
+      TypeInfo::Std(Std::Result { .. }) => match value.result().unwrap() {
+        Ok(ok) => f.debug_tuple("Ok").field(&child(ok)).finish(),
+        Err(err) => f.debug_tuple("Err").field(&child(err)).finish(),
+      },
+      TypeInfo::Pointer(
+        Pointer::Ref { .. } | Pointer::RefMut { .. } | Pointer::Box { .. },
+      ) => fmt::Debug::fmt(&child(value.target().unwrap()), f),
+      TypeInfo::Pointer(Pointer::RawConst { .. } | Pointer::RawMut { .. }) => {
+        write!(f, "{:p}", value.address().unwrap())
+      },
+      TypeInfo::Tuple(..) => {
+        write_fields(f.debug_tuple(""), value, type_info.fields(), child)
+      },
+      TypeInfo::Struct(Struct::UnitStruct { .. }) => f.write_str(name),
+      TypeInfo::Struct(Struct::TupleStruct { .. }) => {
+        write_fields(f.debug_tuple(name), value, type_info.fields(), child)
+      },
+      TypeInfo::Struct(Struct::Struct { .. }) => {
+        write_fields(f.debug_struct(name), value, type_info.fields(), child)
+      },
+      // which field of a union is active isn't known, so none are read
+      TypeInfo::Union(..) => f.debug_struct(name).finish_non_exhaustive(),
+      TypeInfo::Enum(..) => {
+        let (_, variant) = value.variant().unwrap();
+        let fields = variant.fields();
+        match variant {
+          EnumVariantInfo::Unit { variant_name, .. } => {
+            f.write_str(variant_name)
+          },
+          EnumVariantInfo::Tuple { variant_name, .. } => {
+            write_fields(f.debug_tuple(variant_name), value, fields, child)
+          },
+          EnumVariantInfo::Struct { variant_name, .. } => {
+            write_fields(f.debug_struct(variant_name), value, fields, child)
+          },
+        }
+      },
+    }
+  }
+}
+
+/// The builders of `fmt::Debug` output that fields are written to
+trait FieldsBuilder {
+  fn field(&mut self, field: &Field, value: &dyn fmt::Debug);
+  fn finish(&mut self, skipped: bool) -> fmt::Result;
+}
+
+impl FieldsBuilder for fmt::DebugTuple<'_, '_> {
+  fn field(&mut self, _: &Field, value: &dyn fmt::Debug) {
+    fmt::DebugTuple::field(self, value);
+  }
+
+  fn finish(&mut self, skipped: bool) -> fmt::Result {
+    if skipped {
+      self.finish_non_exhaustive()
+    } else {
+      fmt::DebugTuple::finish(self)
+    }
+  }
+}
+
+impl FieldsBuilder for fmt::DebugStruct<'_, '_> {
+  fn field(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    fmt::DebugStruct::field(self, &field.label(), value);
+  }
+
+  fn finish(&mut self, skipped: bool) -> fmt::Result {
+    if skipped {
+      self.finish_non_exhaustive()
+    } else {
+      fmt::DebugStruct::finish(self)
+    }
+  }
+}
+
+/// Write the fields that aren't skipped, ending with `..` if any were
+fn write_fields<'v>(
+  mut builder: impl FieldsBuilder,
+  value: ValueRef<'v>,
+  fields: impl Iterator<Item = Field>,
+  child: impl Fn(ValueRef<'v>) -> DebugValue<'v>,
+) -> fmt::Result {
+  let mut skipped = false;
+  for field in fields {
+    if field.attributes.skip {
+      skipped = true;
+      continue;
+    }
+    let field_value =
+      unsafe { value.field_at(field.offset, field.type_info()) };
+    builder.field(&field, &child(field_value));
+  }
+  builder.finish(skipped)
+}
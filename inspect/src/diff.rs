@@ -0,0 +1,304 @@
+use {
+  crate::{
+    debug::DebugValue,
+    path::{FieldPath, Segment},
+    type_info::{Field, Pointer, Sequence, Std, TypeInfo, TypeInfoProvider},
+    value::ValueRef,
+  },
+  ::core::{cmp::Ordering, fmt},
+};
+
+/// Compare two values of the same type, using only the [`TypeInfo`] of their
+/// type, and list what changed
+///
+/// Structs, tuples, and enums that are the same variant are compared field by
+/// field; arrays, slices and `Vec`s item by item; maps entry by entry, by key;
+/// and the contents of `Option`s, `Result`s, `Box`es and references are
+/// compared if they're the same variant. Anything else, like numbers and
+/// strings, is compared by its [`debug`](crate::debug) representation, which
+/// is also how the values in each [`Change`] are formatted. Fields marked
+/// `#[inspect(skip)]` are left out.
+///
+/// Each change has the [path](FieldPath) to the part of the value that
+/// changed, with the fields of a variant written after its name, like
+/// `shape::Circle.radius`.
+///
+/// ```rust
+/// # use ::inspect::TypeInfo;
+/// #[derive(TypeInfo)]
+/// struct User {
+///   name: String,
+///   roles: Vec<&'static str>,
+///   manager: Option<u32>,
+/// }
+///
+/// let old = User {
+///   name: "ada".to_string(),
+///   roles: vec!["admin"],
+///   manager: Some(1),
+/// };
+/// let new = User {
+///   name: "ada".to_string(),
+///   roles: vec!["admin", "ops"],
+///   manager: Some(2),
+/// };
+/// assert_eq!(
+///   inspect::diff(&old, &new).to_string(),
+///   "roles[1]: added \"ops\"\n\
+///    manager::Some: 1 -> 2\n"
+/// );
+/// ```
+pub fn diff<T>(old: &T, new: &T) -> Diff
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  Diff::new(ValueRef::new(old), ValueRef::new(new))
+}
+
+/// The changes between two values. See [`diff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff {
+  /// The changes, in the order the parts of the value are declared
+  pub changes: Vec<Change>,
+}
+
+/// A part of a value that's different between two values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+  /// The path to the part, from the root of the value, which is empty for
+  /// the root itself
+  pub path: FieldPath,
+  /// The part of the old value, or `None` if it was added
+  pub old: Option<String>,
+  /// The part of the new value, or `None` if it was removed
+  pub new: Option<String>,
+}
+
+impl Diff {
+  /// Compare the values behind two [`ValueRef`]s. If they're of different
+  /// types, the whole value has changed
+  pub fn new(old: ValueRef<'_>, new: ValueRef<'_>) -> Self {
+    let changes = compare(old, new, true)
+      .into_iter()
+      .map(|change| Change {
+        path: change.path,
+        old: change.old.map(debug),
+        new: change.new.map(debug),
+      })
+      .collect();
+    Diff { changes }
+  }
+
+  /// Whether the values are the same
+  pub fn is_empty(&self) -> bool {
+    self.changes.is_empty()
+  }
+}
+
+/// A part of a value that's different between two values, which hasn't been
+/// formatted yet
+pub(crate) struct RawChange<'v> {
+  pub(crate) path: FieldPath,
+  pub(crate) old: Option<ValueRef<'v>>,
+  pub(crate) new: Option<ValueRef<'v>>,
+}
+
+/// List the parts of two values that are different. Maps are compared by
+/// entry when `entries` is true, or as a whole otherwise
+pub(crate) fn compare<'v>(
+  old: ValueRef<'v>,
+  new: ValueRef<'v>,
+  entries: bool,
+) -> Vec<RawChange<'v>> {
+  let mut comparison = Comparison {
+    entries,
+    path: FieldPath::new(),
+    changes: Vec::new(),
+  };
+  comparison.compare(old, new);
+  comparison.changes
+}
+
+struct Comparison<'v> {
+  entries: bool,
+  /// The path to the parts being compared
+  path: FieldPath,
+  changes: Vec<RawChange<'v>>,
+}
+
+impl<'v> Comparison<'v> {
+  fn compare(&mut self, old: ValueRef<'v>, new: ValueRef<'v>) {
+    let type_info = old.type_info();
+    if type_info.type_id() != new.type_info().type_id() {
+      return self.change(Some(old), Some(new));
+    }
+
+    match type_info {
+      TypeInfo::Sequence(Sequence::Slice { .. } | Sequence::Array { .. })
+      | TypeInfo::Std(Std::Vec { .. }) => {
+        let mut old_items = old.items().unwrap();
+        let mut new_items = new.items().unwrap();
+        for index in 0.. {
+          let (old, new) = (old_items.next(), new_items.next());
+          if old.is_none() && new.is_none() {
+            break;
+          }
+          self.at(Segment::Index(index), |this| match (old, new) {
+            (Some(old), Some(new)) => this.compare(old, new),
+            (old, new) => this.change(old, new),
+          });
+        }
+      },
+      TypeInfo::Std(Std::HashMap { .. } | Std::BTreeMap { .. }) => {
+        if self.entries {
+          self.compare_entries(old, new);
+        } else if !compare(old, new, true).is_empty() {
+          self.change(Some(old), Some(new));
+        }
+      },
+      TypeInfo::Std(Std::Option { .. }) => {
+        match (old.option().unwrap(), new.option().unwrap()) {
+          (Some(old), Some(new)) => self
+            .at(Segment::Variant("Some".to_string()), |this| {
+              this.compare(old, new)
+            }),
+          (None, None) => {},
+          _ => self.change(Some(old), Some(new)),
+        }
+      },
+      TypeInfo::Std(Std::Result { .. }) => {
+        match (old.result().unwrap(), new.result().unwrap()) {
+          (Ok(old), Ok(new)) => self
+            .at(Segment::Variant("Ok".to_string()), |this| {
+              this.compare(old, new)
+            }),
+          (Err(old), Err(new)) => self
+            .at(Segment::Variant("Err".to_string()), |this| {
+              this.compare(old, new)
+            }),
+          _ => self.change(Some(old), Some(new)),
+        }
+      },
+      TypeInfo::Pointer(
+        Pointer::Ref { .. } | Pointer::RefMut { .. } | Pointer::Box { .. },
+      ) => self.compare(old.target().unwrap(), new.target().unwrap()),
+      TypeInfo::Tuple(_) | TypeInfo::Struct(_) => {
+        self.compare_fields(old, new, type_info.fields())
+      },
+      TypeInfo::Enum(_) => {
+        let (old_index, variant) = old.variant().unwrap();
+        let (new_index, _) = new.variant().unwrap();
+        if old_index != new_index {
+          return self.change(Some(old), Some(new));
+        }
+        self.at(Segment::Variant(variant.name().to_string()), |this| {
+          this.compare_fields(old, new, variant.fields())
+        })
+      },
+      _ => {
+        if debug(old) != debug(new) {
+          self.change(Some(old), Some(new));
+        }
+      },
+    }
+  }
+
+  /// Compare the entries of two maps, in order of key
+  fn compare_entries(&mut self, old: ValueRef<'v>, new: ValueRef<'v>) {
+    let key = |(key, _): &(ValueRef, ValueRef)| debug(*key);
+    let mut old_entries = old.entries().unwrap();
+    let mut new_entries = new.entries().unwrap();
+    // `HashMap`s have no order, so entries are listed in order of key
+    old_entries.sort_by_cached_key(key);
+    new_entries.sort_by_cached_key(key);
+    let mut old_entries = old_entries.into_iter().peekable();
+    let mut new_entries = new_entries.into_iter().peekable();
+    loop {
+      let order = match (old_entries.peek(), new_entries.peek()) {
+        (Some(old), Some(new)) => key(old).cmp(&key(new)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => break,
+      };
+      let (key, old, new) = match order {
+        Ordering::Less => {
+          let (key, old) = old_entries.next().unwrap();
+          (key, Some(old), None)
+        },
+        Ordering::Greater => {
+          let (key, new) = new_entries.next().unwrap();
+          (key, None, Some(new))
+        },
+        Ordering::Equal => {
+          let (key, old) = old_entries.next().unwrap();
+          let (_, new) = new_entries.next().unwrap();
+          (key, Some(old), Some(new))
+        },
+      };
+      self.at(Segment::Key(debug(key)), |this| match (old, new) {
+        (Some(old), Some(new)) => this.compare(old, new),
+        (old, new) => this.change(old, new),
+      });
+    }
+  }
+
+  fn compare_fields(
+    &mut self,
+    old: ValueRef<'v>,
+    new: ValueRef<'v>,
+    fields: impl Iterator<Item = Field>,
+  ) {
+    for field in fields.filter(|field| !field.attributes.skip) {
+      let name = field.label();
+      let (old, new) = unsafe {
+        (
+          old.field_at(field.offset, field.type_info()),
+          new.field_at(field.offset, field.type_info()),
+        )
+      };
+      self.at(Segment::Field(name), |this| this.compare(old, new));
+    }
+  }
+
+  /// Compare the parts one step further along the path with `compare`
+  fn at(&mut self, segment: Segment, compare: impl FnOnce(&mut Self)) {
+    self.path.push(segment);
+    compare(self);
+    self.path.pop();
+  }
+
+  fn change(&mut self, old: Option<ValueRef<'v>>, new: Option<ValueRef<'v>>) {
+    self.changes.push(RawChange {
+      path: self.path.clone(),
+      old,
+      new,
+    });
+  }
+}
+
+fn debug(value: ValueRef<'_>) -> String {
+  format!("{:?}", DebugValue::new(value))
+}
+
+impl fmt::Display for Diff {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for change in &self.changes {
+      writeln!(f, "{change}")?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for Change {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if !self.path.is_empty() {
+      write!(f, "{}: ", self.path)?;
+    }
+    match (&self.old, &self.new) {
+      (Some(old), Some(new)) => write!(f, "{old} -> {new}"),
+      (None, Some(new)) => write!(f, "added {new}"),
+      (Some(old), None) => write!(f, "removed {old}"),
+      (None, None) => Ok(()),
+    }
+  }
+}
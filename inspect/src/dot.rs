@@ -0,0 +1,176 @@
+//! Render the graph of types reachable from a type in Graphviz's DOT language
+//!
+//! Every type is a node, labelled with its name, size and alignment, and has
+//! an edge to each type it refers to, labelled with the field, variant or
+//! item it refers to the type through. Each type appears once, so recursive
+//! types are cycles in the graph.
+//!
+//! ```rust
+//! # use ::inspect::{TypeInfo, dot};
+//! #[derive(TypeInfo)]
+//! struct List {
+//!   value: u8,
+//!   next: Option<Box<List>>,
+//! }
+//!
+//! let graph = dot::render::<List>();
+//! assert!(graph.starts_with("digraph {\n"));
+//! assert!(graph.contains(&format!(
+//!   "  t0 [label=\"List\\nsize {}, align {}\"];\n",
+//!   size_of::<List>(),
+//!   align_of::<List>(),
+//! )));
+//! assert!(graph.contains("  t0 -> t2 [label=\"next\"];\n"));
+//! // the `Box` points back to `List`
+//! assert!(graph.contains("  t3 -> t0 [label=\"target\"];\n"));
+//! ```
+
+use {
+  crate::type_info::{
+    Field, Pointer, Sequence, Std, TypeInfo, TypeInfoProvider,
+  },
+  ::core::any::TypeId,
+  ::disqualified::ShortName,
+  ::std::collections::{HashMap, VecDeque},
+};
+
+/// Render the graph of `T`, and every type reachable from it
+pub fn render<T>() -> String
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  render_type_info(TypeInfo::of::<T>())
+}
+
+/// Render the graph of the type described by `type_info`, and every type
+/// reachable from it
+pub fn render_type_info(type_info: &'static TypeInfo) -> String {
+  let mut renderer = Renderer::default();
+  renderer.node(type_info);
+  while let Some((node, type_info)) = renderer.queue.pop_front() {
+    renderer.render(node, type_info);
+  }
+
+  let mut graph = String::from("digraph {\n  node [shape=box];\n");
+  for line in renderer.lines {
+    graph += &format!("  {line};\n");
+  }
+  graph += "}\n";
+  graph
+}
+
+#[derive(Default)]
+struct Renderer {
+  nodes: HashMap<TypeId, usize>,
+  /// Types that have a node but haven't been rendered yet
+  queue: VecDeque<(usize, &'static TypeInfo)>,
+  lines: Vec<String>,
+}
+
+impl Renderer {
+  /// The node of a type, queueing it to be rendered the first time it's seen
+  fn node(&mut self, type_info: &'static TypeInfo) -> usize {
+    if let Some(&node) = self.nodes.get(&type_info.type_id()) {
+      return node;
+    }
+    let node = self.nodes.len();
+    self.nodes.insert(type_info.type_id(), node);
+    self.queue.push_back((node, type_info));
+    node
+  }
+
+  fn render(&mut self, node: usize, type_info: &'static TypeInfo) {
+    let layout = match type_info.layout() {
+      Some(layout) => {
+        format!("size {}, align {}", layout.size(), layout.align())
+      },
+      None => "unsized".to_string(),
+    };
+    let name = ShortName(type_info.type_name()).to_string();
+    self.lines.push(format!(
+      "t{node} [label={}]",
+      quote(&format!("{name}\n{layout}"))
+    ));
+
+    match type_info {
+      TypeInfo::Primitive(..) => {},
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::str => {},
+        Sequence::Slice { item, .. } | Sequence::Array { item, .. } => {
+          self.edge(node, "item", item.type_info_fn, false)
+        },
+      },
+      TypeInfo::Std(std) => match std {
+        Std::String => {},
+        Std::Vec { item, .. }
+        | Std::Option { item, .. }
+        | Std::PhantomData { item, .. } => {
+          self.edge(node, "item", item.type_info_fn, false)
+        },
+        Std::Result { info, .. } => {
+          self.edge(node, "ok", info.ok_type_info_fn, false);
+          self.edge(node, "err", info.err_type_info_fn, false);
+        },
+        Std::HashMap { info, .. } | Std::BTreeMap { info, .. } => {
+          self.edge(node, "key", info.key_type_info_fn, false);
+          self.edge(node, "value", info.value_type_info_fn, false);
+        },
+      },
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { item, .. }
+        | Pointer::RefMut { item, .. }
+        | Pointer::RawConst { item, .. }
+        | Pointer::RawMut { item, .. }
+        | Pointer::Box { item, .. } => {
+          self.edge(node, "target", item.type_info_fn, false)
+        },
+      },
+      TypeInfo::Tuple(..) | TypeInfo::Struct(..) | TypeInfo::Union(..) => {
+        self.fields(node, "", type_info.fields())
+      },
+      TypeInfo::Enum(..) => {
+        for variant in type_info.variants() {
+          let prefix = format!("{}::", variant.name());
+          self.fields(node, &prefix, variant.fields())
+        }
+      },
+    }
+  }
+
+  /// An edge to the type of a field, which is dashed if the field is
+  /// `#[inspect(skip)]`ped
+  fn edge(
+    &mut self,
+    from: usize,
+    label: &str,
+    type_info_fn: fn() -> &'static TypeInfo,
+    skip: bool,
+  ) {
+    let to = self.node(type_info_fn());
+    let style = if skip { ", style=dashed" } else { "" };
+    self
+      .lines
+      .push(format!("t{from} -> t{to} [label={}{style}]", quote(label)));
+  }
+
+  fn fields(
+    &mut self,
+    node: usize,
+    prefix: &str,
+    fields: impl Iterator<Item = Field>,
+  ) {
+    for field in fields {
+      let label = format!("{prefix}{}", field.label());
+      self.edge(node, &label, field.type_info_fn, field.attributes.skip);
+    }
+  }
+}
+
+/// A DOT string, where new lines are line breaks
+fn quote(text: &str) -> String {
+  let escaped = text
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n");
+  format!("\"{escaped}\"")
+}
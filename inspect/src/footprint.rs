@@ -0,0 +1,276 @@
+use {
+  crate::{
+    type_info::{Field, Pointer, Sequence, Std, TypeInfo, TypeInfoProvider},
+    value::ValueRef,
+  },
+  ::core::{any::TypeId, fmt},
+  ::disqualified::ShortName,
+  ::std::collections::HashSet,
+};
+
+/// Measure the memory taken up by a value, including what it owns on the
+/// heap, using only the [`TypeInfo`] of its type
+///
+/// Besides the size of the value itself, this counts the allocations owned by
+/// `String`s, `Vec`s (their whole capacity), `Box`es and maps, and everything
+/// in them, and breaks it down by field. The memory behind references isn't
+/// counted, since it belongs to something else, but it can be with
+/// [`FootprintOptions::follow_references`]. Raw pointers aren't followed, and
+/// the active field of a union isn't known, so only their own size counts.
+/// Fields marked `#[inspect(skip)]` are measured like any other, since the
+/// value still owns their memory.
+///
+/// The allocations of maps are estimated from the sizes of their entries, and
+/// no allocator's bookkeeping is counted.
+///
+/// `Rc` and `Arc` have no [`TypeInfo`] yet, so values that share memory
+/// through them can't be measured; the only sharing that's accounted for is
+/// between references, and between references and `Box`es.
+///
+/// ```rust
+/// # use ::inspect::TypeInfo;
+/// #[derive(TypeInfo)]
+/// struct Cache {
+///   hits: u64,
+///   entries: Vec<Box<[u8; 16]>>,
+/// }
+///
+/// let mut entries = Vec::with_capacity(4);
+/// entries.push(Box::new([0; 16]));
+/// let cache = Cache { hits: 0, entries };
+///
+/// let footprint = inspect::footprint(&cache);
+/// assert_eq!(footprint.total(), 32 + 4 * 8 + 16);
+/// assert_eq!(footprint.fields[1].footprint.heap, 4 * 8 + 16);
+/// ```
+pub fn footprint<T>(value: &T) -> Footprint
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  FootprintOptions::new().footprint(ValueRef::new(value))
+}
+
+/// Options for measuring the memory taken up by a value. See [`footprint`]
+#[derive(Debug, Clone, Copy)]
+pub struct FootprintOptions {
+  follow_references: bool,
+}
+
+impl FootprintOptions {
+  pub fn new() -> Self {
+    FootprintOptions {
+      follow_references: false,
+    }
+  }
+
+  /// Whether to count the targets of references as part of the value
+  ///
+  /// Each target is counted once, however many references there are to it,
+  /// by whichever reference or owning `Box` reaches it first.
+  pub fn follow_references(mut self, follow_references: bool) -> Self {
+    self.follow_references = follow_references;
+    self
+  }
+
+  /// Measure the memory taken up by the value behind a [`ValueRef`]
+  pub fn footprint(&self, value: ValueRef<'_>) -> Footprint {
+    Measure {
+      options: *self,
+      seen: HashSet::new(),
+    }
+    .measure(value)
+  }
+}
+
+impl Default for FootprintOptions {
+  fn default() -> Self {
+    FootprintOptions::new()
+  }
+}
+
+/// The memory taken up by a value, and by each of its fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Footprint {
+  pub type_name: &'static str,
+  /// The size of the value itself, as with [`size_of_val`]
+  pub inline: usize,
+  /// The size of everything the value owns through pointers, and of the
+  /// targets of references if they're followed
+  pub heap: usize,
+  /// The active variant, if the value is an enum, `Option` or `Result`
+  pub variant: Option<&'static str>,
+  /// The fields of a struct, tuple or the active variant; the contents of an
+  /// `Option` or `Result` are its field `0`
+  pub fields: Vec<FieldFootprint>,
+}
+
+/// The memory taken up by a field of a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldFootprint {
+  /// The name of the field, or its index if it doesn't have one
+  pub name: String,
+  pub footprint: Footprint,
+}
+
+impl Footprint {
+  /// The size of the value and everything it owns
+  pub fn total(&self) -> usize {
+    self.inline + self.heap
+  }
+
+  fn write(
+    &self,
+    f: &mut fmt::Formatter<'_>,
+    name: Option<&str>,
+    depth: usize,
+  ) -> fmt::Result {
+    write!(f, "{:indent$}", "", indent = depth * 2)?;
+    if let Some(name) = name {
+      write!(f, "{name}: ")?;
+    }
+    write!(f, "{}", ShortName(self.type_name))?;
+    if let Some(variant) = self.variant {
+      write!(f, "::{variant}")?;
+    }
+    writeln!(
+      f,
+      " = {} bytes ({} inline + {} heap)",
+      self.total(),
+      self.inline,
+      self.heap
+    )?;
+    for field in &self.fields {
+      field.footprint.write(f, Some(&field.name), depth + 1)?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for Footprint {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.write(f, None, 0)
+  }
+}
+
+struct Measure {
+  options: FootprintOptions,
+  /// The addresses, and types, of the targets of pointers that have been
+  /// counted already
+  seen: HashSet<(*const u8, TypeId)>,
+}
+
+impl Measure {
+  fn measure(&mut self, value: ValueRef<'_>) -> Footprint {
+    let type_info = value.type_info();
+    let mut footprint = Footprint {
+      type_name: type_info.type_name(),
+      inline: value.size().unwrap_or(0),
+      heap: 0,
+      variant: None,
+      fields: Vec::new(),
+    };
+
+    match type_info {
+      TypeInfo::Sequence(Sequence::Slice { .. } | Sequence::Array { .. }) => {
+        footprint.heap = self.heap_of(value.items().unwrap());
+      },
+      TypeInfo::Std(Std::String) => {
+        footprint.heap = unsafe { value.cast::<String>() }.capacity();
+      },
+      TypeInfo::Std(Std::Vec { info, .. }) => {
+        let capacity = unsafe { (info.capacity_fn)(value.as_ptr()) };
+        let item_size = type_info.item().unwrap().size().unwrap();
+        footprint.heap =
+          capacity * item_size + self.heap_of(value.items().unwrap());
+      },
+      TypeInfo::Std(Std::HashMap { .. } | Std::BTreeMap { .. }) => {
+        let entries = value.entries().unwrap();
+        let entry_size = type_info
+          .generic_args()
+          .iter()
+          .map(|type_info| type_info.size().unwrap())
+          .sum::<usize>();
+        footprint.heap = entries.len() * entry_size
+          + entries
+            .into_iter()
+            .map(|(key, value)| {
+              self.measure(key).heap + self.measure(value).heap
+            })
+            .sum::<usize>();
+      },
+      TypeInfo::Std(Std::Option { .. } | Std::Result { .. }) => {
+        let (variant, inner) = value.std_variant().unwrap();
+        footprint.variant = Some(variant);
+        if let Some(inner) = inner {
+          self.push(&mut footprint, "0".to_string(), inner);
+        }
+      },
+      TypeInfo::Pointer(Pointer::Box { .. }) => {
+        footprint.heap = self.behind(value.target().unwrap());
+      },
+      TypeInfo::Pointer(Pointer::Ref { .. } | Pointer::RefMut { .. })
+        if self.options.follow_references =>
+      {
+        footprint.heap = self.behind(value.target().unwrap());
+      },
+      TypeInfo::Tuple(_) | TypeInfo::Struct(_) => {
+        self.fields(&mut footprint, value, type_info.fields());
+      },
+      TypeInfo::Enum(_) => {
+        let (_, variant) = value.variant().unwrap();
+        footprint.variant = Some(variant.name());
+        self.fields(&mut footprint, value, variant.fields());
+      },
+      _ => {},
+    }
+    footprint
+  }
+
+  /// The size of the target of a pointer and everything it owns, or `0` if
+  /// it's been counted already, through a reference or a `Box`
+  fn behind(&mut self, target: ValueRef<'_>) -> usize {
+    let size = target.size().unwrap_or(0);
+    if size > 0 {
+      let key = (target.as_ptr(), target.type_info().type_id());
+      if !self.seen.insert(key) {
+        return 0;
+      }
+    }
+    size + self.measure(target).heap
+  }
+
+  fn heap_of<'v>(
+    &mut self,
+    values: impl Iterator<Item = ValueRef<'v>>,
+  ) -> usize {
+    values.map(|value| self.measure(value).heap).sum()
+  }
+
+  fn fields(
+    &mut self,
+    footprint: &mut Footprint,
+    value: ValueRef<'_>,
+    fields: impl Iterator<Item = Field>,
+  ) {
+    for field in fields {
+      let name = field.label();
+      let field_value =
+        unsafe { value.field_at(field.offset, field.type_info()) };
+      self.push(footprint, name, field_value);
+    }
+  }
+
+  fn push(
+    &mut self,
+    footprint: &mut Footprint,
+    name: String,
+    field: ValueRef,
+  ) {
+    let field = self.measure(field);
+    footprint.heap += field.heap;
+    footprint.fields.push(FieldFootprint {
+      name,
+      footprint: field,
+    });
+  }
+}
@@ -0,0 +1,275 @@
+use {
+  crate::{
+    debug::DebugValue,
+    type_info::{Sequence, Std, TypeInfo, TypeInfoProvider},
+    value::ValueRef,
+  },
+  ::core::fmt,
+  ::disqualified::ShortName,
+};
+
+/// Format the bytes of a value as a hex dump, using only the [`TypeInfo`] of
+/// its type, with each range of bytes labelled with the path to the field
+/// it belongs to and its value
+///
+/// Structs, tuples and arrays are broken down into their fields, and so are
+/// the active variants of enums, and the contents of `Option`s and
+/// `Result`s. Only the bytes of primitives and pointers are printed; others,
+/// like those of a `String`, are shown as `??` and labelled with the value,
+/// and padding is shown as `--`, since reading it isn't allowed. The bytes
+/// of an enum that aren't part of the active variant's fields hold its
+/// discriminant, if anything, but where isn't known, so they're `??` too.
+///
+/// ```rust
+/// # use ::inspect::TypeInfo;
+/// #[derive(TypeInfo, Default)]
+/// #[repr(u8)]
+/// enum Tag {
+///   #[default]
+///   Empty,
+///   Id(u16),
+/// }
+///
+/// #[derive(TypeInfo)]
+/// #[repr(C)]
+/// struct Header {
+///   flags: u16,
+///   length: u32,
+///   tag: Tag,
+/// }
+///
+/// let header = Header { flags: 0x8001, length: 260, tag: Tag::Id(42) };
+/// assert_eq!(
+///   inspect::hex_dump(&header).to_string(),
+///   "Header: 12 bytes
+///   0000  01 80                    flags: u16 = 32769
+///   0002  -- --                    <padding>
+///   0004  04 01 00 00              length: u32 = 260
+///   0008  ?? ??                    tag: <Id>
+///   000a  2a 00                    tag::Id.0: u16 = 42
+/// "
+/// );
+/// ```
+pub fn hex_dump<T>(value: &T) -> HexDump<'_>
+where
+  T: TypeInfoProvider,
+{
+  HexDump {
+    value: ValueRef::new(value),
+  }
+}
+
+/// Formats the bytes of a value as a hex dump using its [`TypeInfo`]. See
+/// [`hex_dump`]
+#[derive(Clone, Copy)]
+pub struct HexDump<'v> {
+  value: ValueRef<'v>,
+}
+
+impl<'v> HexDump<'v> {
+  /// Format the value behind a [`ValueRef`], which must be statically sized
+  pub fn new(value: ValueRef<'v>) -> Self {
+    HexDump { value }
+  }
+}
+
+/// A range of bytes of the value, relative to its start
+struct Row<'v> {
+  offset: usize,
+  len: usize,
+  bytes: Bytes<'v>,
+  label: String,
+}
+
+#[derive(Clone, Copy)]
+enum Bytes<'v> {
+  /// The bytes of a primitive or pointer, which are all initialised
+  Known(&'v [u8]),
+  /// The bytes of other types, which may not all be initialised
+  Unknown,
+  Padding,
+}
+
+/// The number of bytes on each line
+const WIDTH: usize = 8;
+
+impl fmt::Display for HexDump<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let type_info = self.value.type_info();
+    let size = type_info.size().unwrap_or(0);
+    writeln!(f, "{}: {size} bytes", ShortName(type_info.type_name()))?;
+
+    let mut rows = vec![];
+    collect(self.value, 0, String::new(), &mut rows);
+    fill(&mut rows, 0, 0, size, Bytes::Padding, "<padding>");
+
+    for row in rows {
+      for start in (0..row.len).step_by(WIDTH) {
+        let end = row.len.min(start + WIDTH);
+        let hex = (start..end)
+          .map(|index| match &row.bytes {
+            Bytes::Known(bytes) => format!("{:02x}", bytes[index]),
+            Bytes::Unknown => "??".to_string(),
+            Bytes::Padding => "--".to_string(),
+          })
+          .collect::<Vec<_>>()
+          .join(" ");
+        let offset = row.offset + start;
+        if start == 0 {
+          writeln!(f, "  {offset:04x}  {hex:<23}  {}", row.label)?;
+        } else {
+          writeln!(f, "  {offset:04x}  {hex}")?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Add the rows of the value, which is `offset` bytes into the outermost
+/// value, and reached through `path`
+fn collect<'v>(
+  value: ValueRef<'v>,
+  offset: usize,
+  path: String,
+  rows: &mut Vec<Row<'v>>,
+) {
+  let type_info = value.type_info();
+  let size = type_info.size().unwrap_or(0);
+  if size == 0 {
+    return;
+  }
+  let join = |separator: &str, segment: &str| match path.as_str() {
+    "" => segment.to_string(),
+    _ => format!("{path}{separator}{segment}"),
+  };
+  let first = rows.len();
+
+  match type_info {
+    TypeInfo::Primitive(_) | TypeInfo::Pointer(_) => {
+      let bytes =
+        unsafe { ::core::slice::from_raw_parts(value.as_ptr(), size) };
+      // pointers aren't followed, so that a dangling one can't be read
+      let decoded = match type_info {
+        TypeInfo::Pointer(_) => format!("{:p}", value.address().unwrap()),
+        _ => format!("{:?}", DebugValue::new(value)),
+      };
+      rows.push(Row {
+        offset,
+        len: size,
+        bytes: Bytes::Known(bytes),
+        label: label(&path, type_info, &decoded),
+      });
+    },
+    TypeInfo::Sequence(Sequence::Array { .. }) => {
+      let stride = type_info.item().unwrap().size().unwrap_or(0);
+      for (index, item) in value.items().unwrap().enumerate() {
+        let item_offset = offset + index * stride;
+        collect(item, item_offset, format!("{path}[{index}]"), rows);
+      }
+    },
+    TypeInfo::Struct(_) | TypeInfo::Tuple(_) => {
+      for field in type_info.fields() {
+        let field_value =
+          unsafe { value.field_at(field.offset, field.type_info()) };
+        let field_path = join(".", &field.label());
+        collect(field_value, offset + field.offset, field_path, rows);
+      }
+      fill(rows, first, offset, size, Bytes::Padding, "<padding>");
+    },
+    TypeInfo::Enum(_)
+    | TypeInfo::Std(Std::Option { .. } | Std::Result { .. }) => {
+      let (variant, fields) = match type_info {
+        TypeInfo::Enum(_) => {
+          let (_, variant) = value.variant().unwrap();
+          let fields = variant
+            .fields()
+            .map(|field| {
+              (Some(field.label()), field.offset, field.type_info())
+            })
+            .collect::<Vec<_>>();
+          (variant.name(), fields)
+        },
+        _ => {
+          let (variant, inner) = value.std_variant().unwrap();
+          let fields = inner
+            .map(|inner| {
+              let field_offset =
+                inner.as_ptr() as usize - value.as_ptr() as usize;
+              (None, field_offset, inner.type_info())
+            })
+            .into_iter()
+            .collect();
+          (variant, fields)
+        },
+      };
+      let variant_path = join("::", variant);
+      for (name, field_offset, field_type_info) in fields {
+        let field_value =
+          unsafe { value.field_at(field_offset, field_type_info) };
+        let field_path = match name {
+          Some(name) => format!("{variant_path}.{name}"),
+          None => variant_path.clone(),
+        };
+        collect(field_value, offset + field_offset, field_path, rows);
+      }
+      let gap_label = match path.as_str() {
+        "" => format!("<{variant}>"),
+        _ => format!("{path}: <{variant}>"),
+      };
+      fill(rows, first, offset, size, Bytes::Unknown, &gap_label);
+    },
+    _ => {
+      let decoded = format!("{:.3?}", DebugValue::new(value));
+      rows.push(Row {
+        offset,
+        len: size,
+        bytes: Bytes::Unknown,
+        label: label(&path, type_info, &decoded),
+      })
+    },
+  }
+}
+
+/// Sort the rows from `first` onwards, which are within the `size` bytes at
+/// `offset`, and fill the gaps between them with new rows
+fn fill<'v>(
+  rows: &mut Vec<Row<'v>>,
+  first: usize,
+  offset: usize,
+  size: usize,
+  bytes: Bytes<'v>,
+  label: &str,
+) {
+  let mut filled = rows.split_off(first);
+  filled.sort_by_key(|row| row.offset);
+  let mut covered = offset;
+  for row in filled {
+    if row.offset > covered {
+      rows.push(Row {
+        offset: covered,
+        len: row.offset - covered,
+        bytes,
+        label: label.to_string(),
+      });
+    }
+    covered = covered.max(row.offset + row.len);
+    rows.push(row);
+  }
+  if offset + size > covered {
+    rows.push(Row {
+      offset: covered,
+      len: offset + size - covered,
+      bytes,
+      label: label.to_string(),
+    });
+  }
+}
+
+fn label(path: &str, type_info: &TypeInfo, value: &str) -> String {
+  let type_name = ShortName(type_info.type_name());
+  match path {
+    "" => format!("{type_name} = {value}"),
+    _ => format!("{path}: {type_name} = {value}"),
+  }
+}
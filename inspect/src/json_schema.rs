@@ -0,0 +1,315 @@
+//! Generate [JSON Schema]s describing how types are serialized
+//!
+//! The schemas describe values as they're written by
+//! [`serde::Reflected`](crate::serde::Reflected), which matches the shape of
+//! `#[derive(Serialize)]`. Structs and enums are put in `$defs` and referred
+//! to with `$ref`, so recursive types are supported. Doc comments become
+//! `description`s.
+//!
+//! ```rust
+//! # use ::inspect::{TypeInfo, json_schema};
+//! /// The settings of the editor
+//! #[derive(TypeInfo)]
+//! struct Config {
+//!   /// Spaces per indent
+//!   tab_width: u8,
+//!   theme: Option<String>,
+//! }
+//!
+//! let schema = json_schema::generate::<Config>();
+//! assert_eq!(schema["description"], "The settings of the editor");
+//! assert_eq!(schema["properties"]["tab_width"]["maximum"], 255);
+//! assert_eq!(schema["required"], serde_json::json!(["tab_width"]));
+//! ```
+//!
+//! [JSON Schema]: https://json-schema.org
+
+use {
+  crate::{
+    codegen::Names,
+    schema::{Field, Kind, Schema, TypeDef, Variant},
+    type_info::{Primitive, TypeInfoProvider},
+  },
+  ::serde_json::{json, Map, Value},
+};
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Generate the JSON Schema of `T`
+pub fn generate<T>() -> Value
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  from_schema(&Schema::of::<T>())
+}
+
+/// Generate the JSON Schema of the root type of a [`Schema`]
+pub fn from_schema(schema: &Schema) -> Value {
+  let definitions = schema
+    .types
+    .iter()
+    .filter(|(name, type_def)| {
+      **name != schema.root && is_definition(type_def)
+    })
+    .collect::<Vec<_>>();
+  // names that only differ in characters `$ref`s can't hold are numbered
+  let mut def_names = Names::default();
+  for (name, _) in &definitions {
+    def_names.insert(name.as_str(), &[def_name(name)]);
+  }
+  let generator = Generator { schema, def_names };
+  let defs = definitions
+    .into_iter()
+    .map(|(name, type_def)| {
+      let def_name = generator.def_names.get(&name.as_str()).unwrap();
+      (def_name.clone(), generator.definition(type_def))
+    })
+    .collect::<Map<_, _>>();
+
+  let mut root = match schema.root_def() {
+    Some(root) => generator.definition(root),
+    None => Value::Bool(false),
+  };
+  if let Value::Object(root) = &mut root {
+    root.insert("$schema".to_string(), DIALECT.into());
+    root.insert("title".to_string(), short_title(&schema.root).into());
+    if !defs.is_empty() {
+      root.insert("$defs".to_string(), Value::Object(defs));
+    }
+  }
+  root
+}
+
+struct Generator<'s> {
+  schema: &'s Schema,
+  /// The keys of the types in `$defs`
+  def_names: Names<&'s str>,
+}
+
+impl Generator<'_> {
+  /// The schema of a type, where the type is used
+  fn reference(&self, name: &str) -> Value {
+    // no value matches a type that a malformed schema doesn't define
+    let Some(type_def) = self.schema.types.get(name) else {
+      return Value::Bool(false);
+    };
+    if name == self.schema.root {
+      json!({ "$ref": "#" })
+    } else if is_definition(type_def) {
+      json!({ "$ref": format!("#/$defs/{}", self.def_names.get(&name).unwrap()) })
+    } else {
+      self.definition(type_def)
+    }
+  }
+
+  /// The full schema of a type
+  fn definition(&self, type_def: &TypeDef) -> Value {
+    let mut schema = match &type_def.kind {
+      Kind::Primitive { primitive } => primitive_schema(*primitive),
+      Kind::Str | Kind::String => json!({ "type": "string" }),
+      Kind::Slice { item } | Kind::Vec { item } => {
+        json!({ "type": "array", "items": self.reference(item) })
+      },
+      Kind::Array { item, length } => json!({
+        "type": "array",
+        "items": self.reference(item),
+        "minItems": length,
+        "maxItems": length,
+      }),
+      // JSON objects can only have string keys, which serializers write
+      // other keys as
+      Kind::HashMap { value, .. } | Kind::BTreeMap { value, .. } => json!({
+        "type": "object",
+        "additionalProperties": self.reference(value),
+      }),
+      Kind::Option { item } => json!({
+        "anyOf": [self.reference(item), { "type": "null" }],
+      }),
+      Kind::Result { ok, err } => json!({
+        "oneOf": [
+          tagged("Ok", self.reference(ok), None),
+          tagged("Err", self.reference(err), None),
+        ],
+      }),
+      Kind::PhantomData { .. } | Kind::UnitStruct => json!({ "type": "null" }),
+      Kind::Ref { target }
+      | Kind::RefMut { target }
+      | Kind::Box { target } => self.reference(target),
+      // raw pointers and unions can't be serialized
+      Kind::RawConst { .. } | Kind::RawMut { .. } | Kind::Union { .. } => {
+        Value::Bool(false)
+      },
+      Kind::Tuple { fields } => self.tuple(fields),
+      Kind::TupleStruct { fields } => self.tuple_struct(fields),
+      Kind::Struct { fields } => self.object(fields),
+      Kind::Enum { variants } => json!({
+        "oneOf": variants
+          .iter()
+          .map(|variant| self.variant(variant))
+          .collect::<Vec<_>>(),
+      }),
+    };
+    if let (Some(doc), Value::Object(schema)) = (&type_def.doc, &mut schema) {
+      schema.insert("description".to_string(), doc.as_str().into());
+    }
+    schema
+  }
+
+  /// Tuple structs and variants with a single field are serialized as that
+  /// field, otherwise they're arrays like tuples
+  fn tuple_struct(&self, fields: &[Field]) -> Value {
+    match fields {
+      [field] if !field.skip => self.field(field),
+      fields => self.tuple(fields),
+    }
+  }
+
+  fn tuple(&self, fields: &[Field]) -> Value {
+    let items = fields
+      .iter()
+      .filter(|field| !field.skip)
+      .map(|field| self.field(field))
+      .collect::<Vec<_>>();
+    json!({
+      "type": "array",
+      "prefixItems": items,
+      "items": false,
+      "minItems": items.len(),
+      "maxItems": items.len(),
+    })
+  }
+
+  fn object(&self, fields: &[Field]) -> Value {
+    let fields = fields.iter().filter(|field| !field.skip);
+    let properties = fields
+      .clone()
+      .map(|field| {
+        let name = field.serialized_name().unwrap_or_default();
+        (name.to_string(), self.field(field))
+      })
+      .collect::<Map<_, _>>();
+    let required = fields
+      .filter(|field| {
+        !self
+          .schema
+          .types
+          .get(&field.ty)
+          .is_some_and(|def| matches!(def.kind, Kind::Option { .. }))
+      })
+      .map(|field| field.serialized_name().unwrap_or_default())
+      .collect::<Vec<_>>();
+    json!({
+      "type": "object",
+      "properties": properties,
+      "required": required,
+    })
+  }
+
+  fn field(&self, field: &Field) -> Value {
+    let mut schema = self.reference(&field.ty);
+    if let Some(doc) = &field.doc {
+      // `$ref` can have siblings since draft 2019-09
+      if let Value::Object(schema) = &mut schema {
+        schema.insert("description".to_string(), doc.as_str().into());
+      }
+    }
+    schema
+  }
+
+  /// Unit variants are serialized as their name, and other variants as an
+  /// object with the variant's name as its only key
+  fn variant(&self, variant: &Variant) -> Value {
+    let doc = variant.doc();
+    match variant {
+      Variant::Unit { name, .. } => {
+        let mut schema = json!({ "const": name });
+        if let Some(doc) = doc {
+          schema["description"] = doc.into();
+        }
+        schema
+      },
+      Variant::Tuple { name, fields, .. } => {
+        tagged(name, self.tuple_struct(fields), doc)
+      },
+      Variant::Struct { name, fields, .. } => {
+        tagged(name, self.object(fields), doc)
+      },
+    }
+  }
+}
+
+/// An object with a single key, `tag`, holding a value matching `schema`
+fn tagged(tag: &str, schema: Value, doc: Option<&str>) -> Value {
+  let mut tagged = json!({
+    "type": "object",
+    "properties": { tag: schema },
+    "required": [tag],
+    "additionalProperties": false,
+  });
+  if let Some(doc) = doc {
+    tagged["description"] = doc.into();
+  }
+  tagged
+}
+
+fn primitive_schema(primitive: Primitive) -> Value {
+  macro_rules! integer {
+    ($ty:ty) => {
+      json!({
+        "type": "integer",
+        "minimum": <$ty>::MIN,
+        "maximum": <$ty>::MAX,
+      })
+    };
+  }
+
+  match primitive {
+    Primitive::u8 => integer!(u8),
+    Primitive::u16 => integer!(u16),
+    Primitive::u32 => integer!(u32),
+    Primitive::u64 => integer!(u64),
+    Primitive::usize => integer!(usize),
+    Primitive::i8 => integer!(i8),
+    Primitive::i16 => integer!(i16),
+    Primitive::i32 => integer!(i32),
+    Primitive::i64 => integer!(i64),
+    Primitive::isize => integer!(isize),
+    // JSON numbers can't hold the bounds of 128 bit integers exactly
+    Primitive::u128 => json!({ "type": "integer", "minimum": 0 }),
+    Primitive::i128 => json!({ "type": "integer" }),
+    Primitive::f32 | Primitive::f64 => json!({ "type": "number" }),
+    Primitive::bool => json!({ "type": "boolean" }),
+    Primitive::char => {
+      json!({ "type": "string", "minLength": 1, "maxLength": 1 })
+    },
+    Primitive::Unit => json!({ "type": "null" }),
+  }
+}
+
+/// Structs and enums are defined once in `$defs`, other types are inlined
+fn is_definition(type_def: &TypeDef) -> bool {
+  matches!(
+    type_def.kind,
+    Kind::UnitStruct
+      | Kind::TupleStruct { .. }
+      | Kind::Struct { .. }
+      | Kind::Enum { .. }
+  )
+}
+
+/// A key for `$defs` that doesn't need escaping in a `$ref`
+fn def_name(name: &str) -> String {
+  let name = name.replace("::", ".");
+  let name = name
+    .chars()
+    .map(|c| match c {
+      'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '.' | '-' => c,
+      _ => '_',
+    })
+    .collect::<String>();
+  name.trim_end_matches('_').to_string()
+}
+
+fn short_title(name: &str) -> &str {
+  name.split('<').next().unwrap_or(name)
+}
@@ -0,0 +1,582 @@
+//! Find the padding in the layout of a type, and orders of its fields that
+//! would take less space
+//!
+//! The fields of structs, tuples, unions and enum variants are laid out by
+//! their offsets, and the bytes between and after them that no field covers
+//! are reported as holes. Every field, and the item type of every array, is
+//! analysed in turn, so the padding inside nested types is found too.
+//!
+//! For structs and tuples, the size the type would have with `#[repr(C)]`,
+//! which keeps the fields in the order they're declared, is compared with
+//! the size it'd have with its fields in order of decreasing alignment. A
+//! [`Reordering`] is suggested when declaring the fields in that order would
+//! make it smaller.
+//!
+//! A [`ByteMap`] goes down to the leaves instead: it maps every range of
+//! bytes of a type to the path of the field in it, like `header.flags` or
+//! `payload[3].x`, or to padding.
+//!
+//! ```rust
+//! # use ::inspect::{layout, TypeInfo};
+//! #[derive(TypeInfo)]
+//! #[repr(C)]
+//! struct Particle {
+//!   alive: bool,
+//!   position: [f32; 3],
+//!   id: u64,
+//!   generation: u8,
+//! }
+//!
+//! let analysis = layout::analyze::<Particle>();
+//! assert_eq!(analysis.size, 32);
+//! assert_eq!(analysis.padding(), 10);
+//! assert_eq!(analysis.holes[0], layout::Hole { offset: 1, len: 3 });
+//!
+//! let reordering = analysis.reordering.unwrap();
+//! assert_eq!(reordering.declared_size, 32);
+//! assert_eq!(reordering.order, ["id", "position", "alive", "generation"]);
+//! assert_eq!(reordering.size, 24);
+//! ```
+
+use {
+  crate::type_info::{Field, Sequence, TypeInfo, TypeInfoProvider},
+  ::disqualified::ShortName,
+  ::std::fmt,
+};
+
+/// Analyse the layout of `T`
+pub fn analyze<T>() -> Analysis
+where
+  T: TypeInfoProvider,
+{
+  analyze_type_info(TypeInfo::of::<T>())
+    .expect("sized types always have a layout")
+}
+
+/// Analyse the layout of the type described by `type_info`, or `None` if it
+/// isn't statically sized
+pub fn analyze_type_info(type_info: &'static TypeInfo) -> Option<Analysis> {
+  let layout = type_info.layout()?;
+  let mut analysis = Analysis {
+    type_name: type_info.type_name(),
+    size: layout.size(),
+    align: layout.align(),
+    fields: vec![],
+    holes: vec![],
+    variants: vec![],
+    items: None,
+    reordering: None,
+  };
+
+  if let TypeInfo::Sequence(Sequence::Array { info, .. }) = type_info {
+    analysis.items = Some(Items {
+      length: info.array_length,
+      analysis: Box::new(analyze_type_info(type_info.item().unwrap())?),
+    });
+    return Some(analysis);
+  }
+
+  if type_info.as_enum().is_some() {
+    for variant in type_info.variants() {
+      let fields = fields(variant.fields())?;
+      analysis.variants.push(VariantLayout {
+        name: variant.name(),
+        holes: holes(&fields, analysis.size),
+        fields,
+      });
+    }
+    return Some(analysis);
+  }
+  let has_fields = type_info.as_struct().is_some()
+    || type_info.as_tuple().is_some()
+    || type_info.as_union().is_some();
+  if !has_fields {
+    return Some(analysis);
+  }
+
+  analysis.fields = fields(type_info.fields())?;
+  analysis.holes = holes(&analysis.fields, analysis.size);
+  let packed = type_info.repr().is_some_and(|repr| repr.packed.is_some());
+  if !packed && type_info.as_union().is_none() {
+    analysis.reordering = reordering(type_info, analysis.align);
+  }
+  Some(analysis)
+}
+
+/// The layout of a type, with the padding in it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+  pub type_name: &'static str,
+  pub size: usize,
+  pub align: usize,
+  /// The fields of a struct, tuple or union, in order of their offsets
+  pub fields: Vec<FieldLayout>,
+  /// The bytes of a struct, tuple or union that aren't part of any field
+  pub holes: Vec<Hole>,
+  /// The variants of an enum, in the order they're declared
+  pub variants: Vec<VariantLayout>,
+  /// The items of an array
+  pub items: Option<Items>,
+  /// An order of the fields of a struct or tuple that would make it smaller
+  /// with `#[repr(C)]` than the order they're declared in
+  pub reordering: Option<Reordering>,
+}
+
+/// A field, at its offset in the type containing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldLayout {
+  /// The name of the field, or its index for the fields of tuples, tuple
+  /// structs, and tuple variants
+  pub name: String,
+  pub offset: usize,
+  /// The layout of the field's type
+  pub analysis: Analysis,
+}
+
+/// A variant of an enum
+///
+/// The bytes of the enum its fields don't cover are holes, which include the
+/// enum's discriminant, unless it's stored in a niche of one of the fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantLayout {
+  pub name: &'static str,
+  /// The fields of the variant, in order of their offsets in the enum
+  pub fields: Vec<FieldLayout>,
+  pub holes: Vec<Hole>,
+}
+
+/// The items of an array, which all have the layout of its item type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Items {
+  pub length: usize,
+  /// The layout of the item type
+  pub analysis: Box<Analysis>,
+}
+
+/// A range of bytes that isn't part of any field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hole {
+  pub offset: usize,
+  pub len: usize,
+}
+
+/// An order of the fields of a struct or tuple that would waste less space
+/// than the order they're declared in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reordering {
+  /// The size the type would have with `#[repr(C)]`, with its fields in the
+  /// order they're declared in
+  pub declared_size: usize,
+  /// The names of the fields, or their indices, in order of decreasing
+  /// alignment
+  pub order: Vec<String>,
+  /// The size the type would have with `#[repr(C)]`, with its fields in
+  /// that order
+  pub size: usize,
+}
+
+impl Analysis {
+  /// The number of bytes of the type that aren't part of any field
+  ///
+  /// For enums, it's the bytes that aren't part of the fields of the variant
+  /// with the fewest such bytes, including the discriminant.
+  pub fn padding(&self) -> usize {
+    if self.variants.is_empty() {
+      total(&self.holes)
+    } else {
+      self
+        .variants
+        .iter()
+        .map(|variant| total(&variant.holes))
+        .min()
+        .unwrap_or(0)
+    }
+  }
+
+  /// The number of bytes of the type that are padding, including the padding
+  /// inside its fields, recursively
+  ///
+  /// For enums, it's the fewest wasted bytes of any variant, and for arrays,
+  /// it's the bytes wasted in every item.
+  pub fn wasted(&self) -> usize {
+    let wasted = |fields: &[FieldLayout], holes: &[Hole]| {
+      total(holes)
+        + fields
+          .iter()
+          .map(|field| field.analysis.wasted())
+          .sum::<usize>()
+    };
+    if let Some(items) = &self.items {
+      items.length * items.analysis.wasted()
+    } else if self.variants.is_empty() {
+      wasted(&self.fields, &self.holes)
+    } else {
+      self
+        .variants
+        .iter()
+        .map(|variant| wasted(&variant.fields, &variant.holes))
+        .min()
+        .unwrap_or(0)
+    }
+  }
+
+  fn write_body(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    write_entries(f, depth, &self.fields, &self.holes, "padding")?;
+    for variant in &self.variants {
+      writeln!(f, "{indent}{}", variant.name)?;
+      write_entries(f, depth + 1, &variant.fields, &variant.holes, "unused")?;
+    }
+    if let Some(items) = &self.items {
+      writeln!(
+        f,
+        "{indent}[0..{}]: {}",
+        items.length,
+        ShortName(items.analysis.type_name)
+      )?;
+      items.analysis.write_body(f, depth + 1)?;
+    }
+    if let Some(reordering) = &self.reordering {
+      writeln!(
+        f,
+        "{indent}with `#[repr(C)]`, the declared order would take {} bytes, \
+         and `{}` would take {}",
+        reordering.declared_size,
+        reordering.order.join(", "),
+        reordering.size
+      )?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for Analysis {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(
+      f,
+      "{}: size {}, align {}, {} bytes of padding",
+      ShortName(self.type_name),
+      self.size,
+      self.align,
+      self.padding()
+    )?;
+    self.write_body(f, 1)
+  }
+}
+
+/// Map the bytes of `T` to the fields they belong to
+///
+/// ```rust
+/// # use ::inspect::{layout, TypeInfo};
+/// #[derive(TypeInfo)]
+/// #[repr(C)]
+/// struct Header {
+///   flags: u16,
+///   length: u32,
+/// }
+///
+/// #[derive(TypeInfo)]
+/// #[repr(C)]
+/// struct Packet {
+///   header: Header,
+///   payload: [u8; 2],
+/// }
+///
+/// assert_eq!(
+///   layout::byte_map::<Packet>().to_string(),
+///   "Packet: 12 bytes
+///   0..2   header.flags: u16
+///   2..4   <padding>
+///   4..8   header.length: u32
+///   8..9   payload[0]: u8
+///   9..10  payload[1]: u8
+///   10..12 <padding>
+/// "
+/// );
+/// ```
+pub fn byte_map<T>() -> ByteMap
+where
+  T: TypeInfoProvider,
+{
+  byte_map_type_info(TypeInfo::of::<T>())
+    .expect("sized types always have a layout")
+}
+
+/// Map the bytes of the type described by `type_info` to the fields they
+/// belong to, or `None` if it isn't statically sized
+pub fn byte_map_type_info(type_info: &'static TypeInfo) -> Option<ByteMap> {
+  let size = type_info.size()?;
+  let mut rows = vec![];
+  let mut covered = 0;
+  for leaf in leaves_type_info(type_info)? {
+    if leaf.offset > covered {
+      rows.push(Row::Padding(Hole {
+        offset: covered,
+        len: leaf.offset - covered,
+      }));
+    }
+    covered = covered.max(leaf.offset + leaf.size);
+    rows.push(Row::Field(leaf));
+  }
+  if size > covered {
+    rows.push(Row::Padding(Hole {
+      offset: covered,
+      len: size - covered,
+    }));
+  }
+  Some(ByteMap {
+    type_name: type_info.type_name(),
+    size,
+    rows,
+  })
+}
+
+/// Get the leaf fields of `T`, in order of their offsets
+///
+/// The fields of structs, tuples and arrays are broken down into their own
+/// fields, and everything else, including enums and unions, whose fields
+/// depend on the value, is a leaf. Zero-sized fields are left out.
+pub fn leaves<T>() -> impl Iterator<Item = Leaf>
+where
+  T: TypeInfoProvider,
+{
+  leaves_type_info(TypeInfo::of::<T>())
+    .expect("sized types always have a layout")
+}
+
+/// Get the leaf fields of the type described by `type_info`, in order of
+/// their offsets, or `None` if it isn't statically sized
+pub fn leaves_type_info(
+  type_info: &'static TypeInfo,
+) -> Option<impl Iterator<Item = Leaf>> {
+  type_info.size()?;
+  let mut leaves = vec![];
+  collect_leaves(type_info, 0, String::new(), &mut leaves);
+  // a stable sort, so that fields at the same offset stay in the order
+  // they're declared in
+  leaves.sort_by_key(|leaf| leaf.offset);
+  Some(leaves.into_iter())
+}
+
+/// The bytes of a type, mapped to the fields they belong to
+#[derive(Debug, Clone)]
+pub struct ByteMap {
+  pub type_name: &'static str,
+  pub size: usize,
+  /// Ranges of bytes, in order of their offsets, which cover the whole type
+  pub rows: Vec<Row>,
+}
+
+/// A range of bytes of a [`ByteMap`]
+#[derive(Debug, Clone)]
+pub enum Row {
+  Field(Leaf),
+  Padding(Hole),
+}
+
+/// A field that isn't broken down any further, at its offset from the start
+/// of the outermost type
+#[derive(Debug, Clone)]
+pub struct Leaf {
+  /// The path to the field from the outermost type, like `header.flags` or
+  /// `payload[3].x`, which is empty if the type itself is the leaf
+  pub path: String,
+  pub offset: usize,
+  pub size: usize,
+  pub type_info: &'static TypeInfo,
+}
+
+impl Row {
+  /// The offset of the first byte of the row
+  pub fn offset(&self) -> usize {
+    match self {
+      Row::Field(leaf) => leaf.offset,
+      Row::Padding(hole) => hole.offset,
+    }
+  }
+
+  /// The number of bytes in the row
+  pub fn len(&self) -> usize {
+    match self {
+      Row::Field(leaf) => leaf.size,
+      Row::Padding(hole) => hole.len,
+    }
+  }
+
+  /// Whether the row has no bytes, which is never the case for rows of a
+  /// [`ByteMap`]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
+
+impl fmt::Display for ByteMap {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "{}: {} bytes", ShortName(self.type_name), self.size)?;
+    let range =
+      |row: &Row| format!("{}..{}", row.offset(), row.offset() + row.len());
+    let width = self.rows.iter().map(|row| range(row).len()).max();
+    let width = width.unwrap_or(0);
+    for row in &self.rows {
+      let range = range(row);
+      match row {
+        Row::Field(leaf) => writeln!(
+          f,
+          "  {range:<width$} {}: {}",
+          leaf.path,
+          ShortName(leaf.type_info.type_name())
+        )?,
+        Row::Padding(_) => writeln!(f, "  {range:<width$} <padding>")?,
+      }
+    }
+    Ok(())
+  }
+}
+
+fn collect_leaves(
+  type_info: &'static TypeInfo,
+  offset: usize,
+  path: String,
+  leaves: &mut Vec<Leaf>,
+) {
+  let size = type_info.size().unwrap_or(0);
+  if size == 0 {
+    return;
+  }
+  if let TypeInfo::Sequence(Sequence::Array { info, .. }) = type_info {
+    let item = type_info.item().unwrap();
+    let item_size = item.size().unwrap_or(0);
+    for index in 0..info.array_length {
+      let path = format!("{path}[{index}]");
+      collect_leaves(item, offset + index * item_size, path, leaves);
+    }
+  } else if type_info.as_struct().is_some() || type_info.as_tuple().is_some() {
+    for field in type_info.fields() {
+      let name = field.label();
+      let path = match path.as_str() {
+        "" => name,
+        _ => format!("{path}.{name}"),
+      };
+      collect_leaves(field.type_info(), offset + field.offset, path, leaves);
+    }
+  } else {
+    leaves.push(Leaf {
+      path,
+      offset,
+      size,
+      type_info,
+    });
+  }
+}
+
+/// Write the fields and holes in order of their offsets, with the layouts
+/// of the fields' types below them
+fn write_entries(
+  f: &mut fmt::Formatter,
+  depth: usize,
+  fields: &[FieldLayout],
+  holes: &[Hole],
+  hole_label: &str,
+) -> fmt::Result {
+  let indent = "  ".repeat(depth);
+  let mut fields = fields.iter().peekable();
+  let mut holes = holes.iter().peekable();
+  loop {
+    let next_is_hole = match (fields.peek(), holes.peek()) {
+      (Some(field), Some(hole)) => hole.offset < field.offset,
+      (None, Some(_)) => true,
+      (Some(_), None) => false,
+      (None, None) => return Ok(()),
+    };
+    if next_is_hole {
+      let hole = holes.next().unwrap();
+      let end = hole.offset + hole.len;
+      writeln!(f, "{indent}{}..{end} ({hole_label})", hole.offset)?;
+    } else {
+      let field = fields.next().unwrap();
+      let analysis = &field.analysis;
+      writeln!(
+        f,
+        "{indent}{}..{} {}: {}",
+        field.offset,
+        field.offset + analysis.size,
+        field.name,
+        ShortName(analysis.type_name)
+      )?;
+      analysis.write_body(f, depth + 1)?;
+    }
+  }
+}
+
+/// The layouts of the fields, in order of their offsets
+fn fields(fields: impl Iterator<Item = Field>) -> Option<Vec<FieldLayout>> {
+  let mut fields = fields
+    .map(|field| {
+      Some(FieldLayout {
+        name: field.label(),
+        offset: field.offset,
+        analysis: analyze_type_info(field.type_info())?,
+      })
+    })
+    .collect::<Option<Vec<_>>>()?;
+  fields.sort_by_key(|field| field.offset);
+  Some(fields)
+}
+
+/// The bytes of a type of the given size that none of the fields cover
+fn holes(fields: &[FieldLayout], size: usize) -> Vec<Hole> {
+  let mut holes = vec![];
+  let mut covered = 0;
+  for field in fields {
+    if field.analysis.size == 0 {
+      continue;
+    }
+    if field.offset > covered {
+      holes.push(Hole {
+        offset: covered,
+        len: field.offset - covered,
+      });
+    }
+    covered = covered.max(field.offset + field.analysis.size);
+  }
+  if size > covered {
+    holes.push(Hole {
+      offset: covered,
+      len: size - covered,
+    });
+  }
+  holes
+}
+
+fn reordering(type_info: &TypeInfo, align: usize) -> Option<Reordering> {
+  let mut fields = type_info
+    .fields()
+    .map(|field| {
+      let layout = field.type_info().layout()?;
+      Some((field.label(), layout.size(), layout.align()))
+    })
+    .collect::<Option<Vec<_>>>()?;
+  let declared_size = repr_c_size(&fields, align);
+  // a stable sort, so that fields with the same alignment stay in the order
+  // they're declared in
+  fields.sort_by_key(|&(_, _, align)| ::core::cmp::Reverse(align));
+  let size = repr_c_size(&fields, align);
+  (size < declared_size).then(|| Reordering {
+    declared_size,
+    order: fields.into_iter().map(|(name, ..)| name).collect(),
+    size,
+  })
+}
+
+/// The size of a `#[repr(C)]` struct with fields of the given sizes and
+/// alignments, in order
+fn repr_c_size(fields: &[(String, usize, usize)], align: usize) -> usize {
+  let end = fields.iter().fold(0_usize, |end, &(_, size, align)| {
+    end.next_multiple_of(align) + size
+  });
+  end.next_multiple_of(align)
+}
+
+fn total(holes: &[Hole]) -> usize {
+  holes.iter().map(|hole| hole.len).sum()
+}
@@ -1,6 +1,34 @@
 #![doc = include_str!("../../README.md")]
 
+pub mod bytes;
+pub mod c_header;
+mod codegen;
+mod debug;
+mod diff;
+pub mod dot;
+mod footprint;
+mod hex_dump;
+#[cfg(feature = "json")]
+pub mod json_schema;
+pub mod layout;
+pub mod patch;
+pub mod path;
+pub mod proto;
+mod registry;
+pub mod schema;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod type_info;
-pub use self::type_info::TypeInfo;
+pub mod typescript;
+pub mod value;
+pub use self::{
+  debug::{debug, DebugValue},
+  diff::{diff, Change, Diff},
+  footprint::{footprint, FieldFootprint, Footprint, FootprintOptions},
+  hex_dump::{hex_dump, HexDump},
+  registry::Registry,
+  type_info::TypeInfo,
+  value::DynValue,
+};
 #[cfg(feature = "derive")]
 pub use ::inspect_derive::TypeInfo;
@@ -0,0 +1,400 @@
+//! Changes to values that can be applied, undone, and sent elsewhere, using
+//! only the [`TypeInfo`] of their type
+//!
+//! A [`Patch`] is a list of [`PatchOp`]s, each addressing a part of a value
+//! by its [path] from the root, and holding the values it puts in, and takes
+//! out, in the [packed] binary format; so a patch doesn't depend on the
+//! types it applies to, and can be serialized with the `serde` feature.
+//! Since each op holds what it replaces, every patch has an
+//! [inverse][Patch::inverse] that undoes it.
+//!
+//! Applying a patch builds values, and writes to fields, without going
+//! through the types' constructors, so the derived types it changes must opt
+//! in with `#[inspect(constructible)]`; see [`TypeInfo::is_constructible`].
+//!
+//! ```rust
+//! # use ::inspect::{TypeInfo, patch::Patch};
+//! #[derive(TypeInfo, Debug, Clone, PartialEq)]
+//! #[inspect(constructible)]
+//! struct Level {
+//!   name: String,
+//!   spawns: Vec<(i32, i32)>,
+//! }
+//!
+//! let before = Level { name: "cave".to_string(), spawns: vec![(0, 0)] };
+//! let mut level = before.clone();
+//! level.name = "deep cave".to_string();
+//! level.spawns.push((4, 2));
+//!
+//! let patch = Patch::diff(&before, &level).unwrap();
+//! let mut replica = before.clone();
+//! patch.apply(&mut replica).unwrap();
+//! assert_eq!(replica, level);
+//!
+//! patch.inverse().apply(&mut replica).unwrap();
+//! assert_eq!(replica, before);
+//! ```
+//!
+//! [`TypeInfo`]: enum@crate::TypeInfo
+//! [`TypeInfo::is_constructible`]: crate::TypeInfo::is_constructible
+//! [packed]: crate::bytes::pack
+//! [path]: crate::path::FieldPath
+
+use {
+  crate::{
+    bytes::{self, Endian, PackError},
+    diff,
+    path::{FieldPath, PathError, Segment},
+    type_info::{Std, TypeInfo, TypeInfoProvider},
+    value::{ValueMut, ValueRef},
+  },
+  ::core::{error::Error, fmt, ptr},
+};
+
+/// The byte order values are packed in
+const ENDIAN: Endian = Endian::Little;
+
+/// A list of changes to a value, applied in order
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize)
+)]
+pub struct Patch {
+  pub ops: Vec<PatchOp>,
+}
+
+/// A change to a part of a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize)
+)]
+#[non_exhaustive]
+pub enum PatchOp {
+  /// Replace the part at `path`, which was `old`, with `new`. This is also
+  /// how an enum, `Option` or `Result` is switched to another variant
+  Set {
+    path: FieldPath,
+    old: Vec<u8>,
+    new: Vec<u8>,
+  },
+  /// Insert `item` into the `Vec` at `path`, at `index`
+  Insert {
+    path: FieldPath,
+    index: usize,
+    item: Vec<u8>,
+  },
+  /// Remove `item` from the `Vec` at `path`, at `index`
+  Remove {
+    path: FieldPath,
+    index: usize,
+    item: Vec<u8>,
+  },
+}
+
+impl Patch {
+  pub fn new() -> Self {
+    Patch { ops: Vec::new() }
+  }
+
+  /// Make the patch that changes `old` into `new`
+  ///
+  /// The parts that differ are found as with [`diff`], and each
+  /// is set to its new value, except that items added to and removed from
+  /// the end of a `Vec` are inserted and removed, and maps whose entries
+  /// differ are set as a whole.
+  pub fn diff<T>(old: &T, new: &T) -> Result<Self, PatchError>
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    let mut ops = Vec::new();
+    for change in diff::compare(ValueRef::new(old), ValueRef::new(new), false)
+    {
+      let mut path = change.path;
+      ops.push(match (change.old, change.new) {
+        (Some(old), Some(new)) => PatchOp::Set {
+          path,
+          old: pack(old)?,
+          new: pack(new)?,
+        },
+        (old, new) => {
+          let Some(Segment::Index(index)) = path.pop() else {
+            unreachable!("only items are added or removed")
+          };
+          match (old, new) {
+            (None, Some(item)) => PatchOp::Insert {
+              path,
+              index,
+              item: pack(item)?,
+            },
+            (Some(item), _) => PatchOp::Remove {
+              path,
+              index,
+              item: pack(item)?,
+            },
+            (None, None) => unreachable!(),
+          }
+        },
+      });
+    }
+    // items are removed from the end first, so the indices stay valid
+    for run in ops.chunk_by_mut(|a, b| match (a, b) {
+      (PatchOp::Remove { path: a, .. }, PatchOp::Remove { path: b, .. }) => {
+        a == b
+      },
+      _ => false,
+    }) {
+      run.reverse();
+    }
+    Ok(Patch { ops })
+  }
+
+  /// Whether the patch doesn't change anything
+  pub fn is_empty(&self) -> bool {
+    self.ops.is_empty()
+  }
+
+  /// The patch that undoes this one
+  pub fn inverse(&self) -> Self {
+    Patch {
+      ops: self.ops.iter().rev().map(PatchOp::inverse).collect(),
+    }
+  }
+
+  /// Apply each op of the patch to a value, in order
+  ///
+  /// If an op can't be applied, the ops before it stay applied.
+  pub fn apply<T>(&self, value: &mut T) -> Result<(), PatchError>
+  where
+    T: TypeInfoProvider,
+  {
+    self.apply_to(ValueMut::new(value))
+  }
+
+  /// Apply each op of the patch to the value behind a [`ValueMut`], in order
+  pub fn apply_to(&self, mut value: ValueMut<'_>) -> Result<(), PatchError> {
+    for op in &self.ops {
+      op.apply_to(value.reborrow())?;
+    }
+    Ok(())
+  }
+}
+
+impl PatchOp {
+  /// Replace the part at `path`, which is `old`, with `new`
+  pub fn set<T>(path: FieldPath, old: &T, new: &T) -> Result<Self, PatchError>
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    Ok(PatchOp::Set {
+      path,
+      old: pack(ValueRef::new(old))?,
+      new: pack(ValueRef::new(new))?,
+    })
+  }
+
+  /// Insert `item` into the `Vec` at `path`, at `index`
+  pub fn insert<T>(
+    path: FieldPath,
+    index: usize,
+    item: &T,
+  ) -> Result<Self, PatchError>
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    Ok(PatchOp::Insert {
+      path,
+      index,
+      item: pack(ValueRef::new(item))?,
+    })
+  }
+
+  /// Remove `item` from the `Vec` at `path`, at `index`
+  pub fn remove<T>(
+    path: FieldPath,
+    index: usize,
+    item: &T,
+  ) -> Result<Self, PatchError>
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    Ok(PatchOp::Remove {
+      path,
+      index,
+      item: pack(ValueRef::new(item))?,
+    })
+  }
+
+  /// The path to the part of the value the op changes
+  pub fn path(&self) -> &FieldPath {
+    match self {
+      PatchOp::Set { path, .. }
+      | PatchOp::Insert { path, .. }
+      | PatchOp::Remove { path, .. } => path,
+    }
+  }
+
+  /// The op that undoes this one
+  pub fn inverse(&self) -> Self {
+    match self.clone() {
+      PatchOp::Set { path, old, new } => PatchOp::Set {
+        path,
+        old: new,
+        new: old,
+      },
+      PatchOp::Insert { path, index, item } => {
+        PatchOp::Remove { path, index, item }
+      },
+      PatchOp::Remove { path, index, item } => {
+        PatchOp::Insert { path, index, item }
+      },
+    }
+  }
+
+  /// Apply the op to the value behind a [`ValueMut`]
+  pub fn apply_to(&self, value: ValueMut<'_>) -> Result<(), PatchError> {
+    self.check_constructible(value.type_info())?;
+    let mut target = self.path().resolve_mut(value)?;
+    let type_info = target.type_info();
+    match self {
+      PatchOp::Set { new, .. } => {
+        let new = bytes::unpack(new, type_info, ENDIAN)?;
+        let ptr = target.as_mut_ptr();
+        unsafe {
+          type_info.drop_fn().unwrap()(ptr);
+          new.take_with(|new| {
+            ptr::copy_nonoverlapping(new, ptr, type_info.size().unwrap())
+          });
+        }
+      },
+      PatchOp::Insert { index, item, .. }
+      | PatchOp::Remove { index, item, .. } => {
+        let TypeInfo::Std(Std::Vec { info, .. }) = type_info else {
+          return Err(PatchError::NotAVec {
+            path: self.path().clone(),
+            type_name: type_info.type_name(),
+          });
+        };
+        let vec = target.as_mut_ptr();
+        let (_, len) = unsafe { (info.as_mut_slice_fn)(vec) };
+        let removing = matches!(self, PatchOp::Remove { .. });
+        if *index > len || (removing && *index == len) {
+          return Err(PatchError::IndexOutOfBounds {
+            path: self.path().clone(),
+            index: *index,
+            len,
+          });
+        }
+        if removing {
+          unsafe { (info.remove_fn)(vec, *index) };
+        } else {
+          let item = bytes::unpack(item, type_info.item().unwrap(), ENDIAN)?;
+          unsafe {
+            item.take_with(|item| (info.insert_fn)(vec, *index, item))
+          };
+        }
+      },
+    }
+    Ok(())
+  }
+
+  /// Check that every type the path passes through, on the way to the part
+  /// the op changes, may have its fields written to
+  fn check_constructible(
+    &self,
+    type_info: &'static TypeInfo,
+  ) -> Result<(), PatchError> {
+    let segments = self.path().segments();
+    for len in 0..segments.len() {
+      let path = FieldPath::from(segments[..len].to_vec());
+      let mut parent = path.resolve_type(type_info)?.type_info;
+      while let TypeInfo::Pointer(_) = parent {
+        parent = parent.item().unwrap();
+      }
+      if !parent.is_constructible() {
+        return Err(PatchError::NotConstructible {
+          path,
+          type_name: parent.type_name(),
+        });
+      }
+    }
+    Ok(())
+  }
+}
+
+fn pack(value: ValueRef<'_>) -> Result<Vec<u8>, PatchError> {
+  let mut packed = Vec::new();
+  bytes::pack_into(value, ENDIAN, &mut packed)?;
+  Ok(packed)
+}
+
+/// The reasons making or applying a [`Patch`] can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PatchError {
+  /// The path doesn't lead to a part of the value
+  Path(PathError),
+  /// Items can only be inserted into, and removed from, `Vec`s
+  NotAVec {
+    path: FieldPath,
+    type_name: &'static str,
+  },
+  /// The index to insert or remove an item at is past the end of the `Vec`
+  IndexOutOfBounds {
+    path: FieldPath,
+    index: usize,
+    len: usize,
+  },
+  /// A value couldn't be packed or unpacked
+  Pack(PackError),
+  /// The part at `path` would be changed field by field, but its type isn't
+  /// marked `#[inspect(constructible)]`
+  NotConstructible {
+    path: FieldPath,
+    type_name: &'static str,
+  },
+}
+
+impl From<PathError> for PatchError {
+  fn from(error: PathError) -> Self {
+    PatchError::Path(error)
+  }
+}
+
+impl From<PackError> for PatchError {
+  fn from(error: PackError) -> Self {
+    PatchError::Pack(error)
+  }
+}
+
+impl fmt::Display for PatchError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PatchError::Path(error) => write!(f, "{error}"),
+      PatchError::NotAVec { path, type_name } => {
+        write!(f, "`{path}` is a `{type_name}`, not a `Vec`")
+      },
+      PatchError::IndexOutOfBounds { path, index, len } => write!(
+        f,
+        "index {index} is out of bounds for `{path}`, of length {len}"
+      ),
+      PatchError::Pack(error) => write!(f, "{error}"),
+      PatchError::NotConstructible { path, type_name } => write!(
+        f,
+        "the fields of `{path}`, a `{type_name}`, can't be changed, since it \
+         isn't marked `#[inspect(constructible)]`"
+      ),
+    }
+  }
+}
+
+impl Error for PatchError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      PatchError::Path(error) => Some(error),
+      PatchError::Pack(error) => Some(error),
+      _ => None,
+    }
+  }
+}
@@ -0,0 +1,686 @@
+//! Paths from a value to one of its parts, like `config.servers[2].addr`
+//!
+//! A [`FieldPath`] is a list of [`Segment`]s, written like Rust expressions:
+//! fields after a `.`, items and map entries in `[]`, and variants after a
+//! `::`, or a `.` since a name that's a variant of an enum is taken as one.
+//! So `state::Running.since` and `state.Running.since` both lead to the
+//! `since` field of `state`, if it's the `Running` variant. `Box`es and
+//! references are stepped through without a segment of their own.
+//!
+//! A path can be [resolved][FieldPath::resolve_type] against a [`TypeInfo`],
+//! to find the type of the part it leads to, and its offset when the part is
+//! stored inline, or against a [value][FieldPath::resolve] to find the part
+//! itself. This is the notation [`diff`](crate::diff) and [`patch`] use for
+//! the parts of a value.
+//!
+//! ```rust
+//! # use ::inspect::{path::FieldPath, value::ValueRef, DebugValue, TypeInfo};
+//! #[derive(TypeInfo)]
+//! struct Server {
+//!   addr: &'static str,
+//!   weight: u8,
+//! }
+//!
+//! #[derive(TypeInfo)]
+//! struct Config {
+//!   servers: Vec<Server>,
+//!   primary: (u16, Server),
+//! }
+//!
+//! let config = Config {
+//!   servers: vec![Server { addr: "10.0.0.1", weight: 1 }],
+//!   primary: (7, Server { addr: "10.0.0.2", weight: 2 }),
+//! };
+//!
+//! let path = "servers[0].addr".parse::<FieldPath>().unwrap();
+//! let addr = path.resolve(ValueRef::new(&config)).unwrap();
+//! assert_eq!(format!("{:?}", DebugValue::new(addr)), "\"10.0.0.1\"");
+//!
+//! let path = "primary.1.weight".parse::<FieldPath>().unwrap();
+//! let weight = path.resolve_type(TypeInfo::of::<Config>()).unwrap();
+//! assert_eq!(weight.type_info.type_name(), "u8");
+//! assert!(weight.offset.is_some());
+//! ```
+//!
+//! [`patch`]: crate::patch
+
+use {
+  crate::{
+    debug::DebugValue,
+    type_info::{EnumVariantInfo, Field, Pointer, Sequence, Std, TypeInfo},
+    value::{ValueMut, ValueRef},
+  },
+  ::core::{error::Error, fmt, str::FromStr},
+};
+
+/// A path from a value to one of its parts. See the [module docs](self)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize),
+  serde(transparent)
+)]
+pub struct FieldPath {
+  segments: Vec<Segment>,
+}
+
+/// A step along a [`FieldPath`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize)
+)]
+pub enum Segment {
+  /// A field of a struct, tuple or the active variant of an enum. The fields
+  /// of tuples are named by their index, i.e. `"0"`. If the name is one of
+  /// the variants of an enum, it's taken as a [`Segment::Variant`]. The
+  /// fields of unions can't be reached, since which one is active isn't known
+  Field(String),
+  /// An item of an array, slice or `Vec`, or the entry of a map whose key is
+  /// written as this number
+  Index(usize),
+  /// The active variant of an enum, which must be the variant with this
+  /// name, or the contents of an `Option` or `Result`, which must be `Some`,
+  /// `Ok` or `Err`
+  Variant(String),
+  /// The entry of a map whose key has this `Debug` representation, like
+  /// `"name"` with its quotes
+  Key(String),
+}
+
+/// The part of a type a [`FieldPath`] leads to
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedType {
+  pub type_info: &'static TypeInfo,
+  /// The offset of the part from the start of the value, if it's stored in
+  /// the value itself rather than behind a pointer, and at an offset that
+  /// doesn't depend on the value. The contents of `Option`s and `Result`s
+  /// don't have a known offset
+  pub offset: Option<usize>,
+}
+
+impl FieldPath {
+  /// The path to the value itself
+  pub fn new() -> Self {
+    FieldPath {
+      segments: Vec::new(),
+    }
+  }
+
+  /// Extend the path with a field
+  pub fn field(mut self, name: impl Into<String>) -> Self {
+    self.push(Segment::Field(name.into()));
+    self
+  }
+
+  /// Extend the path with an item
+  pub fn index(mut self, index: usize) -> Self {
+    self.push(Segment::Index(index));
+    self
+  }
+
+  /// Extend the path with a variant
+  pub fn variant(mut self, name: impl Into<String>) -> Self {
+    self.push(Segment::Variant(name.into()));
+    self
+  }
+
+  /// Extend the path with the entry of a map, by the `Debug` representation
+  /// of its key
+  pub fn key(mut self, key: impl Into<String>) -> Self {
+    self.push(Segment::Key(key.into()));
+    self
+  }
+
+  pub fn push(&mut self, segment: Segment) {
+    self.segments.push(segment);
+  }
+
+  pub fn pop(&mut self) -> Option<Segment> {
+    self.segments.pop()
+  }
+
+  pub fn segments(&self) -> &[Segment] {
+    &self.segments
+  }
+
+  /// Whether this is the path to the value itself
+  pub fn is_empty(&self) -> bool {
+    self.segments.is_empty()
+  }
+
+  /// Follow the path through a type, to the type of the part it leads to
+  ///
+  /// An enum's fields can only be found after the variant they belong to.
+  /// Indices into arrays are checked against their length, but those into
+  /// slices and `Vec`s, and the keys of maps, aren't checked.
+  pub fn resolve_type(
+    &self,
+    type_info: &'static TypeInfo,
+  ) -> Result<ResolvedType, PathError> {
+    let mut resolved = ResolvedType {
+      type_info,
+      offset: Some(0),
+    };
+    let mut variant = None::<&'static EnumVariantInfo>;
+    for (position, segment) in self.segments.iter().enumerate() {
+      while let TypeInfo::Pointer(
+        Pointer::Ref { .. } | Pointer::RefMut { .. } | Pointer::Box { .. },
+      ) = resolved.type_info
+      {
+        resolved = ResolvedType {
+          type_info: resolved.type_info.item().unwrap(),
+          offset: None,
+        };
+      }
+      let type_info = resolved.type_info;
+      let no_match = || self.no_match(position, type_info);
+      let inner = |type_info: &'static TypeInfo| ResolvedType {
+        type_info,
+        offset: None,
+      };
+
+      resolved = match (segment, type_info) {
+        (Segment::Field(name) | Segment::Variant(name), TypeInfo::Enum(_))
+          if variant.is_none() =>
+        {
+          variant = Some(type_info.variant(name).ok_or_else(no_match)?);
+          continue;
+        },
+        (Segment::Field(name), TypeInfo::Enum(_)) => {
+          let field = Field::find(variant.take().unwrap().fields(), name)
+            .ok_or_else(no_match)?;
+          field_of(resolved, field)
+        },
+        (
+          Segment::Field(name) | Segment::Variant(name),
+          TypeInfo::Std(Std::Option { .. } | Std::Result { .. }),
+        ) => {
+          let args = type_info.generic_args();
+          match (name.as_str(), args.as_slice()) {
+            ("Some", [item]) => inner(item),
+            ("Ok", [ok, _]) => inner(ok),
+            ("Err", [_, err]) => inner(err),
+            _ => return Err(no_match()),
+          }
+        },
+        (Segment::Field(name), TypeInfo::Tuple(_) | TypeInfo::Struct(_)) => {
+          let field =
+            Field::find(type_info.fields(), name).ok_or_else(no_match)?;
+          field_of(resolved, field)
+        },
+        (
+          Segment::Index(index),
+          TypeInfo::Sequence(Sequence::Array { info, .. }),
+        ) => {
+          if *index >= info.array_length {
+            return Err(PathError::OutOfBounds {
+              path: self.prefix(position),
+              segment: segment.clone(),
+              len: info.array_length,
+            });
+          }
+          let item = type_info.item().unwrap();
+          ResolvedType {
+            type_info: item,
+            offset: resolved
+              .offset
+              .map(|offset| offset + index * item.size().unwrap()),
+          }
+        },
+        (
+          Segment::Index(_),
+          TypeInfo::Sequence(Sequence::Slice { .. })
+          | TypeInfo::Std(Std::Vec { .. }),
+        ) => inner(type_info.item().unwrap()),
+        (
+          Segment::Index(_) | Segment::Key(_),
+          TypeInfo::Std(Std::HashMap { .. } | Std::BTreeMap { .. }),
+        ) => inner(type_info.generic_args()[1]),
+        _ => return Err(no_match()),
+      };
+    }
+    Ok(resolved)
+  }
+
+  /// Follow the path through a value, to the part it leads to
+  pub fn resolve<'v>(
+    &self,
+    value: ValueRef<'v>,
+  ) -> Result<ValueRef<'v>, PathError> {
+    let place = Place {
+      ptr: value.as_ptr().cast_mut(),
+      len: value.len(),
+      type_info: value.type_info(),
+    };
+    let place = unsafe { self.resolve_place(place, false) }?;
+    Ok(unsafe {
+      ValueRef::from_raw_parts(place.ptr, place.len, place.type_info)
+    })
+  }
+
+  /// Follow the path through a value, to the part it leads to, which can
+  /// then be changed
+  ///
+  /// Only `Box`es and `&mut` references to sized types are stepped through,
+  /// and entries of maps can't be reached.
+  pub fn resolve_mut<'v>(
+    &self,
+    mut value: ValueMut<'v>,
+  ) -> Result<ValueMut<'v>, PathError> {
+    let place = Place {
+      ptr: value.as_mut_ptr(),
+      len: value.len(),
+      type_info: value.type_info(),
+    };
+    let place = unsafe { self.resolve_place(place, true) }?;
+    Ok(unsafe {
+      ValueMut::from_raw_parts(place.ptr, place.len, place.type_info)
+    })
+  }
+
+  /// Follow the path from the value at `place`
+  ///
+  /// # Safety
+  ///
+  /// `place` must hold a valid value, which may only be written through the
+  /// place that's returned if `mutable` is true
+  unsafe fn resolve_place(
+    &self,
+    mut place: Place,
+    mutable: bool,
+  ) -> Result<Place, PathError> {
+    for (position, segment) in self.segments.iter().enumerate() {
+      place = unsafe { place.deref(mutable) };
+      let type_info = place.type_info;
+      let value =
+        unsafe { ValueRef::from_raw_parts(place.ptr, place.len, type_info) };
+      let no_match = || self.no_match(position, type_info);
+      let inactive = |active| PathError::InactiveVariant {
+        path: self.prefix(position),
+        segment: segment.clone(),
+        active,
+      };
+
+      place = match (segment, type_info) {
+        (Segment::Field(name) | Segment::Variant(name), TypeInfo::Enum(_))
+          if type_info.variant(name).is_some() =>
+        {
+          let (_, active) = value.variant().unwrap();
+          if active.name() != name {
+            return Err(inactive(active.name()));
+          }
+          continue;
+        },
+        (
+          Segment::Field(name) | Segment::Variant(name),
+          TypeInfo::Std(Std::Option { .. }),
+        ) if name == "Some" => match value.option().unwrap() {
+          Some(some) => place.part(some, mutable),
+          None => return Err(inactive("None")),
+        },
+        (
+          Segment::Field(name) | Segment::Variant(name),
+          TypeInfo::Std(Std::Result { .. }),
+        ) if name == "Ok" || name == "Err" => {
+          match (name.as_str(), value.result().unwrap()) {
+            ("Ok", Ok(inner)) | ("Err", Err(inner)) => {
+              place.part(inner, mutable)
+            },
+            (_, Ok(_)) => return Err(inactive("Ok")),
+            (_, Err(_)) => return Err(inactive("Err")),
+          }
+        },
+        (Segment::Field(name), _) => {
+          let field = value.field(name).ok_or_else(no_match)?;
+          place.part(field, mutable)
+        },
+        (
+          Segment::Index(index),
+          TypeInfo::Sequence(Sequence::Array { .. } | Sequence::Slice { .. })
+          | TypeInfo::Std(Std::Vec { .. }),
+        ) => {
+          let (items, len) = match type_info {
+            TypeInfo::Std(Std::Vec { info, .. }) if mutable => unsafe {
+              (info.as_mut_slice_fn)(place.ptr)
+            },
+            TypeInfo::Std(Std::Vec { info, .. }) => {
+              let (items, len) = unsafe { (info.as_slice_fn)(place.ptr) };
+              (items.cast_mut(), len)
+            },
+            TypeInfo::Sequence(Sequence::Array { info, .. }) => {
+              (place.ptr, info.array_length)
+            },
+            _ => (place.ptr, place.len),
+          };
+          if *index >= len {
+            return Err(PathError::OutOfBounds {
+              path: self.prefix(position),
+              segment: segment.clone(),
+              len,
+            });
+          }
+          let item = type_info.item().unwrap();
+          Place {
+            ptr: unsafe { items.add(index * item.size().unwrap()) },
+            len: 0,
+            type_info: item,
+          }
+        },
+        (
+          Segment::Index(_) | Segment::Key(_),
+          TypeInfo::Std(Std::HashMap { .. } | Std::BTreeMap { .. }),
+        ) if !mutable => {
+          let key = match segment {
+            Segment::Index(index) => index.to_string(),
+            Segment::Key(key) => key.clone(),
+            _ => unreachable!(),
+          };
+          let (_, entry) = value
+            .entries()
+            .unwrap()
+            .into_iter()
+            .find(|(k, _)| format!("{:?}", DebugValue::new(*k)) == key)
+            .ok_or_else(no_match)?;
+          place.part(entry, mutable)
+        },
+        _ => return Err(no_match()),
+      };
+    }
+    Ok(place)
+  }
+
+  /// The path up to, and including, the segment at `position`
+  fn prefix(&self, position: usize) -> FieldPath {
+    FieldPath {
+      segments: self.segments[..=position].to_vec(),
+    }
+  }
+
+  fn no_match(&self, position: usize, type_info: &TypeInfo) -> PathError {
+    PathError::NoMatch {
+      path: self.prefix(position),
+      segment: self.segments[position].clone(),
+      type_name: type_info.type_name(),
+    }
+  }
+}
+
+fn field_of(resolved: ResolvedType, field: Field) -> ResolvedType {
+  ResolvedType {
+    type_info: field.type_info(),
+    offset: resolved.offset.map(|offset| offset + field.offset),
+  }
+}
+
+/// A value along a path, which may be written through if the path is being
+/// resolved mutably
+#[derive(Clone, Copy)]
+struct Place {
+  ptr: *mut u8,
+  /// The length of the value, if it is a `str` or slice
+  len: usize,
+  type_info: &'static TypeInfo,
+}
+
+impl Place {
+  /// Step through `Box`es and references to their targets
+  ///
+  /// When `mutable`, only `Box`es and `&mut` references to sized types are
+  /// stepped through, by reading the address they hold as it is, to keep
+  /// its permission to write.
+  unsafe fn deref(mut self, mutable: bool) -> Place {
+    loop {
+      let (TypeInfo::Pointer(Pointer::Box { item, .. })
+      | TypeInfo::Pointer(Pointer::RefMut { item, .. })
+      | TypeInfo::Pointer(Pointer::Ref { item, .. })) = self.type_info
+      else {
+        return self;
+      };
+      let target = (item.type_info_fn)();
+      self = if !mutable {
+        let value = unsafe {
+          ValueRef::from_raw_parts(self.ptr, self.len, self.type_info)
+        };
+        let target = value.target().unwrap();
+        Place {
+          ptr: target.as_ptr().cast_mut(),
+          len: target.len(),
+          type_info: target.type_info(),
+        }
+      } else if target.layout().is_some()
+        && !matches!(self.type_info, TypeInfo::Pointer(Pointer::Ref { .. }))
+      {
+        Place {
+          ptr: unsafe { self.ptr.cast::<*mut u8>().read() },
+          len: 0,
+          type_info: target,
+        }
+      } else {
+        return self;
+      };
+    }
+  }
+
+  /// The place of a part of the value that's stored in the value itself,
+  /// found through a shared reference. When `mutable`, only its offset is
+  /// used, to stay within the place's permission to write
+  fn part(&self, part: ValueRef<'_>, mutable: bool) -> Place {
+    let ptr = if mutable {
+      let offset = part.as_ptr() as usize - self.ptr as usize;
+      unsafe { self.ptr.add(offset) }
+    } else {
+      part.as_ptr().cast_mut()
+    };
+    Place {
+      ptr,
+      len: part.len(),
+      type_info: part.type_info(),
+    }
+  }
+}
+
+impl From<Vec<Segment>> for FieldPath {
+  fn from(segments: Vec<Segment>) -> Self {
+    FieldPath { segments }
+  }
+}
+
+impl FromIterator<Segment> for FieldPath {
+  fn from_iter<I: IntoIterator<Item = Segment>>(segments: I) -> Self {
+    FieldPath {
+      segments: segments.into_iter().collect(),
+    }
+  }
+}
+
+impl FromStr for FieldPath {
+  type Err = ParsePathError;
+
+  /// Parse a path written like `servers[2].addr` or `state::Running.since`.
+  /// The empty string is the path to the value itself
+  fn from_str(path: &str) -> Result<Self, Self::Err> {
+    let mut parser = Parser { path, rest: path };
+    let mut segments = Vec::new();
+    while !parser.rest.is_empty() {
+      let segment = if parser.eat("::") {
+        Segment::Variant(parser.name()?)
+      } else if parser.eat("[") {
+        parser.bracketed()?
+      } else if segments.is_empty() || parser.eat(".") {
+        Segment::Field(parser.name()?)
+      } else {
+        return Err(parser.expected("`.`, `::` or `[`"));
+      };
+      segments.push(segment);
+    }
+    Ok(FieldPath { segments })
+  }
+}
+
+struct Parser<'p> {
+  path: &'p str,
+  rest: &'p str,
+}
+
+impl Parser<'_> {
+  fn eat(&mut self, token: &str) -> bool {
+    match self.rest.strip_prefix(token) {
+      Some(rest) => {
+        self.rest = rest;
+        true
+      },
+      None => false,
+    }
+  }
+
+  fn name(&mut self) -> Result<String, ParsePathError> {
+    let end = self
+      .rest
+      .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+      .unwrap_or(self.rest.len());
+    if end == 0 {
+      return Err(self.expected("a name"));
+    }
+    let (name, rest) = self.rest.split_at(end);
+    self.rest = rest;
+    Ok(name.to_string())
+  }
+
+  /// An index or key, after its `[`
+  fn bracketed(&mut self) -> Result<Segment, ParsePathError> {
+    let end = if self.rest.starts_with('"') {
+      // the end of a quoted key, skipping over escaped characters
+      let mut chars = self.rest.char_indices().skip(1);
+      loop {
+        match chars.next() {
+          Some((_, '\\')) => {
+            chars.next();
+          },
+          Some((index, '"')) => break index + 1,
+          Some(_) => {},
+          None => {
+            self.rest = "";
+            return Err(self.expected("`\"`"));
+          },
+        }
+      }
+    } else {
+      self.rest.find(']').unwrap_or(self.rest.len())
+    };
+    let (key, rest) = self.rest.split_at(end);
+    if key.is_empty() {
+      return Err(self.expected("an index or key"));
+    }
+    self.rest = rest;
+    if !self.eat("]") {
+      return Err(self.expected("`]`"));
+    }
+    Ok(match key.parse() {
+      Ok(index) => Segment::Index(index),
+      Err(_) => Segment::Key(key.to_string()),
+    })
+  }
+
+  fn expected(&self, expected: &'static str) -> ParsePathError {
+    ParsePathError {
+      position: self.path.len() - self.rest.len(),
+      expected,
+    }
+  }
+}
+
+impl fmt::Display for FieldPath {
+  /// Write the path so it parses back to a path to the same part, like
+  /// `servers[2].addr` or `state::Running.since`
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (position, segment) in self.segments.iter().enumerate() {
+      match segment {
+        Segment::Field(_) if position > 0 => write!(f, ".")?,
+        Segment::Variant(_) if position > 0 => write!(f, "::")?,
+        _ => {},
+      }
+      write!(f, "{segment}")?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for Segment {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Segment::Field(name) | Segment::Variant(name) => write!(f, "{name}"),
+      Segment::Index(index) => write!(f, "[{index}]"),
+      Segment::Key(key) => write!(f, "[{key}]"),
+    }
+  }
+}
+
+/// A path that couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePathError {
+  /// The offset, in bytes, of the first character that couldn't be parsed
+  pub position: usize,
+  /// What was expected there
+  pub expected: &'static str,
+}
+
+impl fmt::Display for ParsePathError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "expected {} at byte {} of the path",
+      self.expected, self.position
+    )
+  }
+}
+
+impl Error for ParsePathError {}
+
+/// The reasons a [`FieldPath`] can't be followed. Each has the path up to,
+/// and including, the segment that couldn't be followed
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PathError {
+  /// The segment doesn't name a part of a `type_name`
+  NoMatch {
+    path: FieldPath,
+    segment: Segment,
+    type_name: &'static str,
+  },
+  /// The segment names a variant, but the value is the `active` one
+  InactiveVariant {
+    path: FieldPath,
+    segment: Segment,
+    active: &'static str,
+  },
+  /// The segment is an index past the end of an array, slice or `Vec`
+  OutOfBounds {
+    path: FieldPath,
+    segment: Segment,
+    len: usize,
+  },
+}
+
+impl fmt::Display for PathError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PathError::NoMatch {
+        path,
+        segment,
+        type_name,
+      } => write!(f, "`{segment}` doesn't match `{type_name}`, at `{path}`"),
+      PathError::InactiveVariant {
+        path,
+        segment,
+        active,
+      } => write!(f, "`{segment}` isn't the active `{active}`, at `{path}`"),
+      PathError::OutOfBounds { path, segment, len } => write!(
+        f,
+        "`{segment}` is out of bounds for length {len}, at `{path}`"
+      ),
+    }
+  }
+}
+
+impl Error for PathError {}
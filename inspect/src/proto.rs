@@ -0,0 +1,586 @@
+//! Generate Protobuf (`.proto`) message definitions for structs and enums
+//!
+//! Structs become messages, and their fields are numbered in the order
+//! they're declared, unless they're given a number with
+//! `#[inspect(proto = N)]`. `Vec`s become `repeated` fields, `Option`s become
+//! `optional`, and maps become `map`s. Enums without fields become Protobuf
+//! enums, and enums with fields become messages with a `oneof`, which has a
+//! field for each variant.
+//!
+//! ```rust
+//! # use ::inspect::{TypeInfo, proto};
+//! /// A reading from a sensor
+//! #[derive(TypeInfo)]
+//! struct Reading {
+//!   sensor: String,
+//!   #[inspect(proto = 5)]
+//!   value: Option<f64>,
+//!   samples: Vec<i32>,
+//! }
+//!
+//! assert_eq!(
+//!   proto::generate::<Reading>().unwrap(),
+//!   r#"syntax = "proto3";
+//!
+//! // A reading from a sensor
+//! message Reading {
+//!   string sensor = 1;
+//!   optional double value = 5;
+//!   repeated int32 samples = 3;
+//! }
+//! "#,
+//! );
+//! ```
+
+use {
+  crate::{
+    codegen::{line_comment, Names},
+    type_info::{
+      Enum, EnumVariantInfo, Field, Pointer, Primitive, Sequence, Std, Tuple,
+      TypeInfo, TypeInfoProvider, Union,
+    },
+  },
+  ::core::{error::Error, fmt},
+  ::std::collections::HashSet,
+};
+
+/// Generate the Protobuf definitions of `T`, and every struct and enum it
+/// refers to
+pub fn generate<T>() -> Result<String, ProtoError>
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  let mut definitions = Proto::new();
+  definitions.add::<T>()?;
+  Ok(definitions.to_string())
+}
+
+/// A `.proto` file that types can be added to one at a time. Formatting it
+/// with `Display` writes out the file
+#[derive(Clone, Default)]
+pub struct Proto {
+  /// The Protobuf names of the types that have been defined
+  names: Names,
+  /// Messages and enums, each after the messages and enums its fields use,
+  /// except where they use each other
+  definitions: Vec<String>,
+}
+
+impl Proto {
+  pub fn new() -> Self {
+    Proto::default()
+  }
+
+  /// Define `T`, and every struct and enum it refers to
+  pub fn add<T>(&mut self) -> Result<&mut Self, ProtoError>
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    self.add_type_info(TypeInfo::of::<T>())
+  }
+
+  /// Define the struct or enum described by `type_info`, and every struct
+  /// and enum it refers to. The file is left unchanged if it returns an
+  /// error
+  pub fn add_type_info(
+    &mut self,
+    type_info: &'static TypeInfo,
+  ) -> Result<&mut Self, ProtoError> {
+    if !matches!(type_info, TypeInfo::Struct(..) | TypeInfo::Enum(..)) {
+      return Err(ProtoError::Type {
+        type_name: type_info.type_name(),
+        reason: "only structs and enums can be defined",
+      });
+    }
+
+    let snapshot = self.clone();
+    match self.define(type_info) {
+      Ok(_) => Ok(self),
+      Err(err) => {
+        *self = snapshot;
+        Err(err)
+      },
+    }
+  }
+
+  /// Define a struct or enum, returning its name in Protobuf
+  fn define(
+    &mut self,
+    type_info: &'static TypeInfo,
+  ) -> Result<String, ProtoError> {
+    if let Some(name) = self.names.get(&type_info.type_id()) {
+      return Ok(name.clone());
+    }
+
+    let name = self.names.insert_type(type_info);
+
+    let type_name = type_info.type_name();
+    let mut definition = line_comment(type_info.doc(), "");
+    match type_info {
+      TypeInfo::Struct(..) => {
+        let body =
+          self.message_body(type_name, type_info.fields(), &[], "  ")?;
+        definition += &format!("message {name} {{{body}}}\n");
+      },
+      TypeInfo::Enum(Enum::Enum { variants, .. }) => {
+        let variants = variants.variant_infos;
+        if variants.is_empty() {
+          return Err(ProtoError::Type {
+            type_name,
+            reason: "Protobuf enums need at least one value",
+          });
+        }
+        if variants
+          .iter()
+          .all(|variant| matches!(variant, EnumVariantInfo::Unit { .. }))
+        {
+          definition += &format!("enum {name} {{\n");
+          let prefix = to_snake_case(&name).to_uppercase();
+          for (index, variant) in variants.iter().enumerate() {
+            definition += &line_comment(variant.doc(), "  ");
+            definition += &format!(
+              "  {prefix}_{} = {index};\n",
+              to_snake_case(variant.name()).to_uppercase()
+            );
+          }
+          definition += "}\n";
+        } else {
+          definition += &format!("message {name} {{\n");
+          definition += &self.oneof(type_name, variants)?;
+          definition += "}\n";
+        }
+      },
+      _ => unreachable!("only structs and enums are defined"),
+    }
+    self.definitions.push(definition);
+    Ok(name)
+  }
+
+  /// The body of a message enum, which is a `oneof` with a field for each
+  /// variant. Variants that don't have a single field are messages nested
+  /// in the enum's message
+  fn oneof(
+    &mut self,
+    type_name: &'static str,
+    variants: &'static [EnumVariantInfo],
+  ) -> Result<String, ProtoError> {
+    // a variant's message hides any type with the same name
+    let nested = variants
+      .iter()
+      .filter(|variant| single_field(variant).is_none())
+      .map(|variant| variant.name())
+      .collect::<Vec<_>>();
+
+    let mut messages = String::new();
+    let mut oneof = String::new();
+    for (index, variant) in variants.iter().enumerate() {
+      let name = variant.name();
+      let field_type = match single_field(variant) {
+        Some(field) => self
+          .field_type(field.type_info())
+          .map_err(|problem| {
+            problem.in_field(type_name, &format!("{name}.0"), &field)
+          })?
+          .qualify(&nested),
+        None => {
+          let fields = variant.fields();
+          let body = self.message_body(type_name, fields, &nested, "    ")?;
+          if body.is_empty() {
+            messages += &format!("  message {name} {{}}\n");
+          } else {
+            messages += &format!("  message {name} {{{body}  }}\n");
+          }
+          name.to_string()
+        },
+      };
+      oneof += &line_comment(variant.doc(), "    ");
+      oneof += &format!(
+        "    {field_type} {} = {};\n",
+        to_snake_case(name),
+        index + 1
+      );
+    }
+    Ok(format!("{messages}  oneof variant {{\n{oneof}  }}\n"))
+  }
+
+  /// The fields of a message, i.e. `optional string name = 1;`, each on
+  /// their own line, starting with a new line if there are any
+  fn message_body(
+    &mut self,
+    type_name: &'static str,
+    fields: impl Iterator<Item = Field>,
+    nested: &[&str],
+    indent: &str,
+  ) -> Result<String, ProtoError> {
+    let mut numbers = HashSet::new();
+    let mut body = String::new();
+    for field in fields {
+      if field.attributes.skip || is_zero_sized(field.type_info()) {
+        continue;
+      }
+      let field_name = proto_name(&field);
+      if !is_identifier(&field_name) {
+        return Err(ProtoError::Name {
+          type_name,
+          name: field_name,
+        });
+      }
+      // numbered by their position, unless given with `#[inspect(proto)]`
+      let number = field.attributes.proto.unwrap_or(field.index as u32 + 1);
+      let reason = match number {
+        0 | 536_870_912.. => Some("field numbers go from 1 to 536870911"),
+        19_000..=19_999 => {
+          Some("field numbers from 19000 to 19999 are reserved by Protobuf")
+        },
+        number if !numbers.insert(number) => {
+          Some("another field has the same number")
+        },
+        _ => None,
+      };
+      if let Some(reason) = reason {
+        return Err(ProtoError::FieldNumber {
+          type_name,
+          field_name: field.label(),
+          number,
+          reason,
+        });
+      }
+
+      let field_type = match self.field_type(field.type_info()) {
+        Ok(field_type) => field_type.qualify(nested),
+        Err(problem) => {
+          return Err(problem.in_field(type_name, &field.label(), &field))
+        },
+      };
+      if body.is_empty() {
+        body += "\n";
+      }
+      body += &line_comment(field.attributes.doc, indent);
+      body += &format!("{indent}{field_type} {field_name} = {number};\n");
+    }
+    Ok(body)
+  }
+
+  /// The Protobuf type of a field, defining any types it refers to
+  fn field_type(
+    &mut self,
+    type_info: &'static TypeInfo,
+  ) -> Result<FieldType, Problem> {
+    let scalar = |name: &str| Ok(FieldType::Singular(name.to_string()));
+    match type_info {
+      TypeInfo::Primitive(primitive) => match primitive {
+        Primitive::bool => scalar("bool"),
+        Primitive::u8 | Primitive::u16 | Primitive::u32 => scalar("uint32"),
+        Primitive::u64 | Primitive::usize => scalar("uint64"),
+        Primitive::i8 | Primitive::i16 | Primitive::i32 => scalar("int32"),
+        Primitive::i64 | Primitive::isize => scalar("int64"),
+        Primitive::f32 => scalar("float"),
+        Primitive::f64 => scalar("double"),
+        Primitive::char => scalar("string"),
+        Primitive::u128 | Primitive::i128 => {
+          Err(Problem::Reason("Protobuf has no 128 bit integers"))
+        },
+        Primitive::Unit => {
+          Err(Problem::Reason("`()` has no equivalent in Protobuf"))
+        },
+      },
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::str => scalar("string"),
+        Sequence::Slice { item, .. } | Sequence::Array { item, .. } => {
+          self.repeated((item.type_info_fn)())
+        },
+      },
+      TypeInfo::Std(std) => match std {
+        Std::String => scalar("string"),
+        Std::Vec { item, .. } => self.repeated((item.type_info_fn)()),
+        Std::Option { item, .. } => {
+          match self.field_type((item.type_info_fn)())? {
+            FieldType::Singular(name) => Ok(FieldType::Optional(name)),
+            _ => Err(Problem::Reason(
+              "`Option`s of `Vec`s, maps and `Option`s can't be optional \
+               fields",
+            )),
+          }
+        },
+        Std::HashMap { info, .. } | Std::BTreeMap { info, .. } => {
+          let key_type_info = (info.key_type_info_fn)();
+          let key = match self.field_type(key_type_info)? {
+            FieldType::Singular(key) if is_key(key_type_info) => key,
+            _ => {
+              return Err(Problem::Reason(
+                "map keys have to be integers, `bool`s or strings",
+              ))
+            },
+          };
+          match self.field_type((info.value_type_info_fn)())? {
+            FieldType::Singular(value) => Ok(FieldType::Map(key, value)),
+            _ => Err(Problem::Reason(
+              "map values can't be `Vec`s, maps or `Option`s",
+            )),
+          }
+        },
+        Std::Result { .. } => Err(Problem::Reason(
+          "`Result`s have no equivalent in Protobuf; an enum can be used \
+           instead",
+        )),
+        Std::PhantomData { .. } => Err(Problem::Reason(
+          "`PhantomData` has no equivalent in Protobuf",
+        )),
+      },
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { item, .. }
+        | Pointer::RefMut { item, .. }
+        | Pointer::Box { item, .. } => self.field_type((item.type_info_fn)()),
+        Pointer::RawConst { .. } | Pointer::RawMut { .. } => {
+          Err(Problem::Reason("raw pointers can't be serialized"))
+        },
+      },
+      TypeInfo::Tuple(Tuple::Tuple { .. }) => Err(Problem::Reason(
+        "tuples have no equivalent in Protobuf; a tuple struct can be used \
+         instead",
+      )),
+      TypeInfo::Union(Union::Union { .. }) => {
+        Err(Problem::Reason("unions can't be serialized"))
+      },
+      TypeInfo::Struct(..) | TypeInfo::Enum(..) => self
+        .define(type_info)
+        .map(FieldType::Singular)
+        .map_err(Problem::Nested),
+    }
+  }
+
+  /// Sequences of bytes are `bytes`, and other sequences are `repeated`
+  fn repeated(
+    &mut self,
+    item: &'static TypeInfo,
+  ) -> Result<FieldType, Problem> {
+    if let TypeInfo::Primitive(Primitive::u8) = item {
+      return Ok(FieldType::Singular("bytes".to_string()));
+    }
+    match self.field_type(item)? {
+      FieldType::Singular(name) => Ok(FieldType::Repeated(name)),
+      _ => Err(Problem::Reason(
+        "repeated fields can't hold `Vec`s, maps or `Option`s; a struct can \
+         be used to wrap them",
+      )),
+    }
+  }
+}
+
+impl fmt::Display for Proto {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "syntax = \"proto3\";")?;
+    for definition in &self.definitions {
+      writeln!(f)?;
+      f.write_str(definition)?;
+    }
+    Ok(())
+  }
+}
+
+/// Why a type couldn't be defined in a [`Proto`] file
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProtoError {
+  /// The type has no equivalent in Protobuf
+  Type {
+    type_name: &'static str,
+    reason: &'static str,
+  },
+  /// A field of a struct or enum variant has a type that has no equivalent
+  /// in Protobuf
+  Field {
+    type_name: &'static str,
+    field_name: String,
+    field_type: &'static str,
+    reason: &'static str,
+  },
+  /// A field has a number that can't be used
+  FieldNumber {
+    type_name: &'static str,
+    field_name: String,
+    number: u32,
+    reason: &'static str,
+  },
+  /// The name of a field isn't a Protobuf identifier
+  Name {
+    type_name: &'static str,
+    name: String,
+  },
+}
+
+impl fmt::Display for ProtoError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ProtoError::Type { type_name, reason } => {
+        write!(f, "`{type_name}` can't be defined in Protobuf: {reason}")
+      },
+      ProtoError::Field {
+        type_name,
+        field_name,
+        field_type,
+        reason,
+      } => write!(
+        f,
+        "field `{field_name}` of `{type_name}` has type `{field_type}`, which \
+         can't be defined in Protobuf: {reason}"
+      ),
+      ProtoError::FieldNumber {
+        type_name,
+        field_name,
+        number,
+        reason,
+      } => write!(
+        f,
+        "field `{field_name}` of `{type_name}` can't be number {number}: \
+         {reason}"
+      ),
+      ProtoError::Name { type_name, name } => write!(
+        f,
+        "`{name}`, in `{type_name}`, isn't a valid Protobuf field name"
+      ),
+    }
+  }
+}
+
+impl Error for ProtoError {}
+
+/// Why the type of a field couldn't be represented in Protobuf
+enum Problem {
+  /// The type itself has no equivalent in Protobuf
+  Reason(&'static str),
+  /// A struct or enum the type refers to couldn't be defined
+  Nested(ProtoError),
+}
+
+impl Problem {
+  fn in_field(
+    self,
+    type_name: &'static str,
+    field_name: &str,
+    field: &Field,
+  ) -> ProtoError {
+    match self {
+      Problem::Reason(reason) => ProtoError::Field {
+        type_name,
+        field_name: field_name.to_string(),
+        field_type: field.type_info().type_name(),
+        reason,
+      },
+      Problem::Nested(err) => err,
+    }
+  }
+}
+
+/// The type of a field, along with its label
+enum FieldType {
+  Singular(String),
+  Optional(String),
+  Repeated(String),
+  Map(String, String),
+}
+
+impl FieldType {
+  /// Write the type, refering to top-level types whose names are hidden by
+  /// nested messages by their full name
+  fn qualify(self, nested: &[&str]) -> String {
+    let qualify = |name: String| {
+      if nested.contains(&name.as_str()) {
+        format!(".{name}")
+      } else {
+        name
+      }
+    };
+    match self {
+      FieldType::Singular(name) => qualify(name),
+      FieldType::Optional(name) => format!("optional {}", qualify(name)),
+      FieldType::Repeated(name) => format!("repeated {}", qualify(name)),
+      FieldType::Map(key, value) => format!("map<{key}, {}>", qualify(value)),
+    }
+  }
+}
+
+/// The name of a field in Protobuf; the fields of tuple structs and variants
+/// are called `field_0`, `field_1`, etc
+fn proto_name(field: &Field) -> String {
+  match field.serialized_name() {
+    Some(name) => name.to_string(),
+    None => format!("field_{}", field.index),
+  }
+}
+
+/// The field of a tuple variant with one field, if it can be a field of a
+/// `oneof` by itself
+fn single_field(variant: &'static EnumVariantInfo) -> Option<Field> {
+  let mut fields = variant.fields();
+  match (variant, fields.next(), fields.next()) {
+    (EnumVariantInfo::Tuple { .. }, Some(field), None)
+      if !field.attributes.skip =>
+    {
+      // `oneof` fields can't be `repeated`, `optional` or maps
+      match field.type_info() {
+        TypeInfo::Std(
+          Std::Option { .. }
+          | Std::Vec { .. }
+          | Std::HashMap { .. }
+          | Std::BTreeMap { .. },
+        )
+        | TypeInfo::Sequence(
+          Sequence::Slice { .. } | Sequence::Array { .. },
+        ) => None,
+        _ => Some(field),
+      }
+    },
+    _ => None,
+  }
+}
+
+/// Zero-sized markers, which have nothing to encode, so they're left out of
+/// messages
+fn is_zero_sized(type_info: &TypeInfo) -> bool {
+  matches!(
+    type_info,
+    TypeInfo::Primitive(Primitive::Unit)
+      | TypeInfo::Std(Std::PhantomData { .. })
+  )
+}
+
+/// Whether a type can be the key of a map
+fn is_key(type_info: &TypeInfo) -> bool {
+  match type_info {
+    TypeInfo::Primitive(primitive) => {
+      !matches!(primitive, Primitive::f32 | Primitive::f64)
+    },
+    TypeInfo::Sequence(Sequence::str) | TypeInfo::Std(Std::String) => true,
+    TypeInfo::Pointer(
+      Pointer::Ref { item, .. }
+      | Pointer::RefMut { item, .. }
+      | Pointer::Box { item, .. },
+    ) => is_key((item.type_info_fn)()),
+    _ => false,
+  }
+}
+
+fn is_identifier(name: &str) -> bool {
+  name.chars().enumerate().all(|(index, c)| match c {
+    'a'..='z' | 'A'..='Z' => true,
+    '0'..='9' | '_' => index > 0,
+    _ => false,
+  }) && !name.is_empty()
+}
+
+/// `FileLink` as `file_link`
+fn to_snake_case(name: &str) -> String {
+  let mut snake = String::new();
+  let mut previous: Option<char> = None;
+  for c in name.chars() {
+    if c.is_ascii_uppercase()
+      && previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+    {
+      snake.push('_');
+    }
+    snake.push(c.to_ascii_lowercase());
+    previous = Some(c);
+  }
+  snake
+}
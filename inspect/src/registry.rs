@@ -0,0 +1,167 @@
+//! A registry of types that can be looked up by [`TypeId`] or by name
+//!
+//! Registering a type also registers every type reachable from it, through
+//! its fields, variants and items, so registering the root types of a
+//! program is enough to be able to look up any type they're made of.
+//!
+//! ```rust
+//! # use ::{core::any::TypeId, inspect::{Registry, TypeInfo}};
+//! #[derive(TypeInfo)]
+//! struct Message {
+//!   id: u32,
+//!   body: String,
+//! }
+//!
+//! Registry::global().register::<Message>();
+//!
+//! let type_info = Registry::global().get(TypeId::of::<Message>()).unwrap();
+//! assert_eq!(type_info.type_name(), TypeInfo::of::<Message>().type_name());
+//! let by_name = Registry::global()
+//!   .get_by_name(::core::any::type_name::<Message>())
+//!   .unwrap();
+//! assert_eq!(by_name.type_id(), TypeId::of::<Message>());
+//! assert!(Registry::global().get(TypeId::of::<String>()).is_some());
+//! ```
+
+use {
+  crate::type_info::{TypeInfo, TypeInfoProvider, TypeVisitor, Walker},
+  ::core::any::TypeId,
+  ::parking_lot::RwLock,
+  ::std::{
+    collections::{HashMap, HashSet},
+    sync::LazyLock,
+  },
+};
+
+/// A set of types that can be looked up by [`TypeId`] or by name
+///
+/// There is one [global][Registry::global] registry for the whole program,
+/// but separate registries can be made with [`Registry::new`].
+pub struct Registry {
+  inner: RwLock<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+  /// In the order they were registered
+  types: Vec<&'static TypeInfo>,
+  by_id: HashMap<TypeId, usize>,
+  by_name: HashMap<&'static str, usize>,
+}
+
+static GLOBAL: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+impl Registry {
+  /// Make an empty registry
+  pub fn new() -> Self {
+    Registry {
+      inner: RwLock::new(Inner::default()),
+    }
+  }
+
+  /// The registry shared by the whole program
+  pub fn global() -> &'static Registry {
+    &GLOBAL
+  }
+
+  /// Register `T`, and every type reachable from it, returning its
+  /// [`TypeInfo`]
+  pub fn register<T>(&self) -> &'static TypeInfo
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    let type_info = TypeInfo::of::<T>();
+    self.register_type_info(type_info);
+    type_info
+  }
+
+  /// Register the type described by `type_info`, and every type reachable
+  /// from it
+  pub fn register_type_info(&self, type_info: &'static TypeInfo) {
+    if self.contains(type_info.type_id()) {
+      return;
+    }
+
+    // the types are found before taking the lock, since getting their
+    // `TypeInfo`s may run arbitrary code
+    let mut reachable = Reachable::default();
+    Walker::new().walk(&mut reachable, type_info);
+
+    let mut inner = self.inner.write();
+    for type_info in reachable.found {
+      let Inner {
+        types,
+        by_id,
+        by_name,
+      } = &mut *inner;
+      if by_id.contains_key(&type_info.type_id()) {
+        continue;
+      }
+      let index = types.len();
+      types.push(type_info);
+      by_id.insert(type_info.type_id(), index);
+      // `type_name`s aren't guaranteed to be unique, so the first type
+      // registered with a name keeps it
+      by_name.entry(type_info.type_name()).or_insert(index);
+    }
+  }
+
+  /// Whether the type with the given [`TypeId`] has been registered
+  pub fn contains(&self, type_id: TypeId) -> bool {
+    self.inner.read().by_id.contains_key(&type_id)
+  }
+
+  /// Get the [`TypeInfo`] of the registered type with the given [`TypeId`]
+  pub fn get(&self, type_id: TypeId) -> Option<&'static TypeInfo> {
+    let inner = self.inner.read();
+    inner.by_id.get(&type_id).map(|&index| inner.types[index])
+  }
+
+  /// Get the [`TypeInfo`] of the registered type with the given name, as
+  /// given by [`type_name`][::core::any::type_name], e.g.
+  /// `"my_crate::Foo"`
+  pub fn get_by_name(&self, name: &str) -> Option<&'static TypeInfo> {
+    let inner = self.inner.read();
+    inner.by_name.get(name).map(|&index| inner.types[index])
+  }
+
+  /// The number of registered types
+  pub fn len(&self) -> usize {
+    self.inner.read().types.len()
+  }
+
+  /// Whether no types have been registered
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Iterate over the registered types, in the order they were registered
+  ///
+  /// The iterator is over a snapshot of the registry, so types registered
+  /// while iterating aren't included.
+  pub fn iter(&self) -> impl Iterator<Item = &'static TypeInfo> {
+    self.inner.read().types.clone().into_iter()
+  }
+}
+
+impl Default for Registry {
+  fn default() -> Self {
+    Registry::new()
+  }
+}
+
+/// Finds every type reachable from a type, once each
+#[derive(Default)]
+struct Reachable {
+  found: Vec<&'static TypeInfo>,
+  seen: HashSet<TypeId>,
+}
+
+impl TypeVisitor for Reachable {
+  fn visit_type(&mut self, walker: &mut Walker, type_info: &'static TypeInfo) {
+    if self.seen.insert(type_info.type_id()) {
+      self.found.push(type_info);
+      walker.walk_type(self, type_info);
+    }
+  }
+}
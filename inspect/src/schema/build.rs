@@ -0,0 +1,209 @@
+use {
+  super::{Field, Kind, Schema, TypeDef, Variant},
+  crate::type_info::{
+    AnonymousFieldInfo, Enum, EnumVariantInfo, NamedFieldInfo, Pointer,
+    Sequence, Std, Struct, Tuple, TypeInfo, Union,
+  },
+  ::core::any::TypeId,
+  ::disqualified::ShortName,
+  ::std::collections::{BTreeMap, HashMap, HashSet, VecDeque},
+};
+
+pub(super) fn build(type_info: &'static TypeInfo) -> Schema {
+  let mut builder = Builder::default();
+  let root = builder.name_of(type_info);
+  while let Some((name, type_info)) = builder.queue.pop_front() {
+    let type_def = builder.define(type_info);
+    builder.types.insert(name, type_def);
+  }
+  Schema {
+    root,
+    types: builder.types,
+  }
+}
+
+#[derive(Default)]
+struct Builder {
+  names: HashMap<TypeId, String>,
+  taken: HashSet<String>,
+  /// Types that have been named but not yet defined
+  queue: VecDeque<(String, &'static TypeInfo)>,
+  types: BTreeMap<String, TypeDef>,
+}
+
+impl Builder {
+  /// The name of the type in the schema, queueing it to be defined the first
+  /// time it's seen
+  fn name_of(&mut self, type_info: &'static TypeInfo) -> String {
+    if let Some(name) = self.names.get(&type_info.type_id()) {
+      return name.clone();
+    }
+
+    let type_name = type_info.type_name();
+    let short_name = ShortName(type_name).to_string();
+    let name = [short_name, type_name.to_string()]
+      .into_iter()
+      .chain((2..).map(|n| format!("{type_name}#{n}")))
+      .find(|name| !self.taken.contains(name))
+      .unwrap();
+
+    self.taken.insert(name.clone());
+    self.names.insert(type_info.type_id(), name.clone());
+    self.queue.push_back((name.clone(), type_info));
+    name
+  }
+
+  fn name_of_fn(&mut self, type_info_fn: fn() -> &'static TypeInfo) -> String {
+    self.name_of(type_info_fn())
+  }
+
+  fn define(&mut self, type_info: &'static TypeInfo) -> TypeDef {
+    let kind = match type_info {
+      TypeInfo::Primitive(primitive) => Kind::Primitive {
+        primitive: *primitive,
+      },
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::str => Kind::Str,
+        Sequence::Slice { item, .. } => Kind::Slice {
+          item: self.name_of_fn(item.type_info_fn),
+        },
+        Sequence::Array { item, info, .. } => Kind::Array {
+          item: self.name_of_fn(item.type_info_fn),
+          length: info.array_length,
+        },
+      },
+      TypeInfo::Std(std) => match std {
+        Std::String => Kind::String,
+        Std::Vec { item, .. } => Kind::Vec {
+          item: self.name_of_fn(item.type_info_fn),
+        },
+        Std::Option { item, .. } => Kind::Option {
+          item: self.name_of_fn(item.type_info_fn),
+        },
+        Std::Result { info, .. } => Kind::Result {
+          ok: self.name_of_fn(info.ok_type_info_fn),
+          err: self.name_of_fn(info.err_type_info_fn),
+        },
+        Std::PhantomData { item, .. } => Kind::PhantomData {
+          item: self.name_of_fn(item.type_info_fn),
+        },
+        Std::HashMap { info, .. } => Kind::HashMap {
+          key: self.name_of_fn(info.key_type_info_fn),
+          value: self.name_of_fn(info.value_type_info_fn),
+        },
+        Std::BTreeMap { info, .. } => Kind::BTreeMap {
+          key: self.name_of_fn(info.key_type_info_fn),
+          value: self.name_of_fn(info.value_type_info_fn),
+        },
+      },
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { item, .. } => Kind::Ref {
+          target: self.name_of_fn(item.type_info_fn),
+        },
+        Pointer::RefMut { item, .. } => Kind::RefMut {
+          target: self.name_of_fn(item.type_info_fn),
+        },
+        Pointer::RawConst { item, .. } => Kind::RawConst {
+          target: self.name_of_fn(item.type_info_fn),
+        },
+        Pointer::RawMut { item, .. } => Kind::RawMut {
+          target: self.name_of_fn(item.type_info_fn),
+        },
+        Pointer::Box { item, .. } => Kind::Box {
+          target: self.name_of_fn(item.type_info_fn),
+        },
+      },
+      TypeInfo::Tuple(Tuple::Tuple { info, .. }) => Kind::Tuple {
+        fields: self.anonymous_fields(info.field_infos),
+      },
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { .. } => Kind::UnitStruct,
+        Struct::TupleStruct { fields, .. } => Kind::TupleStruct {
+          fields: self.anonymous_fields(fields.field_infos),
+        },
+        Struct::Struct { fields, .. } => Kind::Struct {
+          fields: self.named_fields(fields.field_infos),
+        },
+      },
+      TypeInfo::Union(Union::Union { fields, .. }) => Kind::Union {
+        fields: self.named_fields(fields.field_infos),
+      },
+      TypeInfo::Enum(Enum::Enum { variants, .. }) => Kind::Enum {
+        variants: variants
+          .variant_infos
+          .iter()
+          .map(|variant| match variant {
+            EnumVariantInfo::Unit {
+              variant_name,
+              variant_doc,
+              ..
+            } => Variant::Unit {
+              name: variant_name.to_string(),
+              doc: variant_doc.map(str::to_string),
+            },
+            EnumVariantInfo::Tuple {
+              variant_name,
+              variant_doc,
+              field_infos,
+              ..
+            } => Variant::Tuple {
+              name: variant_name.to_string(),
+              fields: self.anonymous_fields(field_infos),
+              doc: variant_doc.map(str::to_string),
+            },
+            EnumVariantInfo::Struct {
+              variant_name,
+              variant_doc,
+              field_infos,
+              ..
+            } => Variant::Struct {
+              name: variant_name.to_string(),
+              fields: self.named_fields(field_infos),
+              doc: variant_doc.map(str::to_string),
+            },
+          })
+          .collect(),
+      },
+    };
+
+    TypeDef {
+      type_name: type_info.type_name().to_string(),
+      size: type_info.size(),
+      align: type_info.align(),
+      default: type_info.default_fn().is_some(),
+      doc: type_info.doc().map(str::to_string),
+      kind,
+    }
+  }
+
+  fn named_fields(&mut self, field_infos: &[NamedFieldInfo]) -> Vec<Field> {
+    field_infos
+      .iter()
+      .map(|field| Field {
+        name: Some(field.field_name.to_string()),
+        offset: field.field_offset,
+        ty: self.name_of_fn(field.type_info_fn),
+        rename: field.attributes.rename.map(str::to_string),
+        skip: field.attributes.skip,
+        doc: field.attributes.doc.map(str::to_string),
+      })
+      .collect()
+  }
+
+  fn anonymous_fields(
+    &mut self,
+    field_infos: &[AnonymousFieldInfo],
+  ) -> Vec<Field> {
+    field_infos
+      .iter()
+      .map(|field| Field {
+        name: None,
+        offset: field.field_offset,
+        ty: self.name_of_fn(field.type_info_fn),
+        rename: None,
+        skip: field.attributes.skip,
+        doc: field.attributes.doc.map(str::to_string),
+      })
+      .collect()
+  }
+}
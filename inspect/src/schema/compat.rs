@@ -0,0 +1,697 @@
+use {
+  super::{Field, Kind, Schema, Variant},
+  crate::type_info::Primitive,
+  ::core::fmt,
+  ::std::collections::HashSet,
+};
+
+pub(super) fn compare(
+  old: &Schema,
+  new: &Schema,
+  encoding: Encoding,
+) -> Report {
+  let mut comparer = Comparer {
+    old,
+    new,
+    encoding,
+    seen: HashSet::new(),
+    changes: Vec::new(),
+  };
+  comparer.types(&new.root, &old.root, &new.root);
+  Report {
+    changes: comparer.changes,
+  }
+}
+
+/// How values are stored, which decides which changes to their types can
+/// still be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Encoding {
+  /// Self-describing formats, where the fields of structs and the variants of
+  /// enums are identified by name, such as JSON written with
+  /// [`serde::Reflected`]. Unknown fields are ignored, and missing fields
+  /// are given their default value, if their type has one
+  ///
+  /// [`serde::Reflected`]: crate::serde::Reflected
+  Named,
+  /// Formats that write fields in order, and variants by their index,
+  /// without any names
+  Positional,
+  /// The bytes of values as they're laid out in memory
+  Memory,
+}
+
+/// The changes between two versions of a [`Schema`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+  pub changes: Vec<Change>,
+}
+
+impl Report {
+  /// Whether values written with the old schema can be read with the new one
+  pub fn is_backward_compatible(&self) -> bool {
+    self.changes.iter().all(|change| change.backward)
+  }
+
+  /// Whether values written with the new schema can be read with the old one
+  pub fn is_forward_compatible(&self) -> bool {
+    self.changes.iter().all(|change| change.forward)
+  }
+}
+
+impl fmt::Display for Report {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for change in &self.changes {
+      writeln!(f, "{change}")?;
+    }
+    Ok(())
+  }
+}
+
+/// A single change between two versions of a [`Schema`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+  /// Where the change is, starting from the root type, i.e.
+  /// `Message.tags[]` for the items of the `tags` field, or
+  /// `Shape::Circle.0` for the first field of a variant
+  pub path: String,
+  pub kind: ChangeKind,
+  /// Whether values written with the old schema can still be read with the
+  /// new one
+  pub backward: bool,
+  /// Whether values written with the new schema can be read with the old
+  /// one
+  pub forward: bool,
+}
+
+impl fmt::Display for Change {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let compatibility = match (self.backward, self.forward) {
+      (true, true) => "compatible",
+      (true, false) => "backward compatible",
+      (false, true) => "forward compatible",
+      (false, false) => "incompatible",
+    };
+    write!(f, "{}: {} ({compatibility})", self.path, self.kind)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChangeKind {
+  FieldAdded,
+  FieldRemoved,
+  /// The field has a new serialized name, but is in the same place with the
+  /// same type
+  FieldRenamed {
+    old: String,
+    new: String,
+  },
+  /// The field is in a different order relative to the other fields
+  FieldMoved {
+    old_index: usize,
+    new_index: usize,
+  },
+  OffsetChanged {
+    old: usize,
+    new: usize,
+  },
+  /// The type is different, and not just renamed. Variants that change
+  /// between unit, tuple and struct variants are retyped too
+  Retyped {
+    old: String,
+    new: String,
+  },
+  SizeChanged {
+    old: Option<usize>,
+    new: Option<usize>,
+  },
+  AlignChanged {
+    old: Option<usize>,
+    new: Option<usize>,
+  },
+  VariantAdded,
+  VariantRemoved,
+  /// The variant has a new name, but is in the same place with the same
+  /// fields
+  VariantRenamed {
+    old: String,
+    new: String,
+  },
+  /// The variant has a different index
+  VariantMoved {
+    old_index: usize,
+    new_index: usize,
+  },
+  /// The schema refers to a type that it doesn't define, so it's malformed,
+  /// and what's there can't be compared
+  MissingType {
+    name: String,
+  },
+}
+
+impl fmt::Display for ChangeKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let layout = |size: &Option<usize>| match size {
+      Some(size) => size.to_string(),
+      None => "unsized".to_string(),
+    };
+    match self {
+      ChangeKind::FieldAdded => write!(f, "field added"),
+      ChangeKind::FieldRemoved => write!(f, "field removed"),
+      ChangeKind::FieldRenamed { old, new } => {
+        write!(f, "field renamed from `{old}` to `{new}`")
+      },
+      ChangeKind::FieldMoved {
+        old_index,
+        new_index,
+      } => write!(f, "field moved from position {old_index} to {new_index}"),
+      ChangeKind::OffsetChanged { old, new } => {
+        write!(f, "offset changed from {old} to {new}")
+      },
+      ChangeKind::Retyped { old, new } => {
+        write!(f, "type changed from `{old}` to `{new}`")
+      },
+      ChangeKind::SizeChanged { old, new } => {
+        write!(f, "size changed from {} to {}", layout(old), layout(new))
+      },
+      ChangeKind::AlignChanged { old, new } => write!(
+        f,
+        "alignment changed from {} to {}",
+        layout(old),
+        layout(new)
+      ),
+      ChangeKind::VariantAdded => write!(f, "variant added"),
+      ChangeKind::VariantRemoved => write!(f, "variant removed"),
+      ChangeKind::VariantRenamed { old, new } => {
+        write!(f, "variant renamed from `{old}` to `{new}`")
+      },
+      ChangeKind::VariantMoved {
+        old_index,
+        new_index,
+      } => {
+        write!(f, "variant moved from index {old_index} to {new_index}")
+      },
+      ChangeKind::MissingType { name } => {
+        write!(f, "type `{name}` isn't defined in the schema")
+      },
+    }
+  }
+}
+
+/// Whether a change is backward and forward compatible, for each of
+/// [`Encoding::Named`], [`Encoding::Positional`] and [`Encoding::Memory`]
+type Compatibility = [(bool, bool); 3];
+
+const BOTH: (bool, bool) = (true, true);
+const BACKWARD: (bool, bool) = (true, false);
+const FORWARD: (bool, bool) = (false, true);
+const NEITHER: (bool, bool) = (false, false);
+
+struct Comparer<'a> {
+  old: &'a Schema,
+  new: &'a Schema,
+  encoding: Encoding,
+  /// Pairs of old and new types that have been compared, so that recursive
+  /// types are only compared once
+  seen: HashSet<(&'a str, &'a str)>,
+  changes: Vec<Change>,
+}
+
+impl<'a> Comparer<'a> {
+  fn push(
+    &mut self,
+    path: &str,
+    kind: ChangeKind,
+    compatibility: Compatibility,
+  ) {
+    let (backward, forward) = match self.encoding {
+      Encoding::Named => compatibility[0],
+      Encoding::Positional => compatibility[1],
+      Encoding::Memory => compatibility[2],
+    };
+    self.changes.push(Change {
+      path: path.to_string(),
+      kind,
+      backward,
+      forward,
+    });
+  }
+
+  /// Compare the old and new types at `path`
+  fn types(&mut self, path: &str, old: &'a str, new: &'a str) {
+    if !self.seen.insert((old, new)) {
+      return;
+    }
+    let (Some(old_def), Some(new_def)) =
+      (self.old.types.get(old), self.new.types.get(new))
+    else {
+      for (schema, name) in [(self.old, old), (self.new, new)] {
+        if !schema.types.contains_key(name) {
+          let kind = ChangeKind::MissingType {
+            name: name.to_string(),
+          };
+          self.push(path, kind, [NEITHER; 3]);
+        }
+      }
+      return;
+    };
+
+    if !same_kind(&old_def.kind, &new_def.kind) {
+      // a wider number can hold every value of a narrower one
+      let named = match (&old_def.kind, &new_def.kind) {
+        (
+          Kind::Primitive {
+            primitive: old_primitive,
+          },
+          Kind::Primitive {
+            primitive: new_primitive,
+          },
+        ) if is_widening(*old_primitive, *new_primitive) => BACKWARD,
+        _ => NEITHER,
+      };
+      let kind = ChangeKind::Retyped {
+        old: old.to_string(),
+        new: new.to_string(),
+      };
+      self.push(path, kind, [named, NEITHER, NEITHER]);
+      return;
+    }
+
+    // the layout only matters in memory
+    let layout = [BOTH, BOTH, NEITHER];
+    if old_def.size != new_def.size {
+      let kind = ChangeKind::SizeChanged {
+        old: old_def.size,
+        new: new_def.size,
+      };
+      self.push(path, kind, layout);
+    }
+    if old_def.align != new_def.align {
+      let kind = ChangeKind::AlignChanged {
+        old: old_def.align,
+        new: new_def.align,
+      };
+      self.push(path, kind, layout);
+    }
+
+    match (&old_def.kind, &new_def.kind) {
+      (Kind::Slice { item: old_item }, Kind::Slice { item: new_item })
+      | (
+        Kind::Array { item: old_item, .. },
+        Kind::Array { item: new_item, .. },
+      )
+      | (Kind::Vec { item: old_item }, Kind::Vec { item: new_item }) => {
+        self.types(&format!("{path}[]"), old_item, new_item)
+      },
+      (Kind::Option { item: old_item }, Kind::Option { item: new_item }) => {
+        self.types(&format!("{path}?"), old_item, new_item)
+      },
+      (
+        Kind::Result {
+          ok: old_ok,
+          err: old_err,
+        },
+        Kind::Result {
+          ok: new_ok,
+          err: new_err,
+        },
+      ) => {
+        self.types(&format!("{path}::Ok"), old_ok, new_ok);
+        self.types(&format!("{path}::Err"), old_err, new_err);
+      },
+      (
+        Kind::HashMap {
+          key: old_key,
+          value: old_value,
+        },
+        Kind::HashMap {
+          key: new_key,
+          value: new_value,
+        },
+      )
+      | (
+        Kind::BTreeMap {
+          key: old_key,
+          value: old_value,
+        },
+        Kind::BTreeMap {
+          key: new_key,
+          value: new_value,
+        },
+      ) => {
+        self.types(&format!("{path}[key]"), old_key, new_key);
+        self.types(&format!("{path}[]"), old_value, new_value);
+      },
+      (Kind::Ref { target: old_target }, Kind::Ref { target: new_target })
+      | (
+        Kind::RefMut { target: old_target },
+        Kind::RefMut { target: new_target },
+      )
+      | (
+        Kind::RawConst { target: old_target },
+        Kind::RawConst { target: new_target },
+      )
+      | (
+        Kind::RawMut { target: old_target },
+        Kind::RawMut { target: new_target },
+      )
+      | (Kind::Box { target: old_target }, Kind::Box { target: new_target }) => {
+        self.types(path, old_target, new_target)
+      },
+      (
+        Kind::Tuple { fields: old_fields },
+        Kind::Tuple { fields: new_fields },
+      )
+      | (
+        Kind::TupleStruct { fields: old_fields },
+        Kind::TupleStruct { fields: new_fields },
+      ) => self.anonymous_fields(path, old_fields, new_fields),
+      (
+        Kind::Struct { fields: old_fields },
+        Kind::Struct { fields: new_fields },
+      )
+      | (
+        Kind::Union { fields: old_fields },
+        Kind::Union { fields: new_fields },
+      ) => self.named_fields(path, old_fields, new_fields),
+      (
+        Kind::Enum {
+          variants: old_variants,
+        },
+        Kind::Enum {
+          variants: new_variants,
+        },
+      ) => self.variants(path, old_variants, new_variants),
+      // primitives, strings, unit structs and `PhantomData` have nothing
+      // else to compare
+      _ => {},
+    }
+  }
+
+  /// Fields are left out of every encoding but memory when they're skipped
+  fn stored(&self, fields: &'a [Field]) -> Vec<&'a Field> {
+    fields
+      .iter()
+      .filter(|field| self.encoding == Encoding::Memory || !field.skip)
+      .collect()
+  }
+
+  /// Compare the fields of tuples, tuple structs and tuple variants, which
+  /// are matched up by their position
+  fn anonymous_fields(
+    &mut self,
+    path: &str,
+    old: &'a [Field],
+    new: &'a [Field],
+  ) {
+    let (old, new) = (self.stored(old), self.stored(new));
+    for index in 0..old.len().max(new.len()) {
+      let field_path = format!("{path}.{index}");
+      match (old.get(index), new.get(index)) {
+        (Some(old_field), Some(new_field)) => {
+          self.field(&field_path, old_field, new_field)
+        },
+        (Some(_), None) => {
+          self.push(&field_path, ChangeKind::FieldRemoved, [NEITHER; 3])
+        },
+        (None, Some(_)) => {
+          self.push(&field_path, ChangeKind::FieldAdded, [NEITHER; 3])
+        },
+        (None, None) => unreachable!(),
+      }
+    }
+  }
+
+  /// Compare the fields of structs, unions and struct variants, which are
+  /// matched up by their serialized names. Fields with new names that take
+  /// the place of removed fields with the same type are renamed
+  fn named_fields(&mut self, path: &str, old: &'a [Field], new: &'a [Field]) {
+    let (old, new) = (self.stored(old), self.stored(new));
+    let name = |field: &'a Field| field.serialized_name().unwrap_or_default();
+    let has_default = |schema: &Schema, field: &Field| {
+      schema.types.get(&field.ty).is_some_and(|def| def.default)
+    };
+
+    let mut paired = vec![false; old.len()];
+    let mut old_indices = new
+      .iter()
+      .map(|new_field| {
+        let old_index = old
+          .iter()
+          .position(|old_field| name(old_field) == name(new_field))?;
+        paired[old_index] = true;
+        Some(old_index)
+      })
+      .collect::<Vec<_>>();
+    let mut renamed = vec![false; new.len()];
+    for (new_index, new_field) in new.iter().enumerate() {
+      if old_indices[new_index].is_some() {
+        continue;
+      }
+      let old_index = (0..old.len())
+        .find(|&index| !paired[index] && old[index].ty == new_field.ty);
+      if let Some(old_index) = old_index {
+        paired[old_index] = true;
+        old_indices[new_index] = Some(old_index);
+        renamed[new_index] = true;
+      }
+    }
+
+    for (new_index, new_field) in new.iter().enumerate() {
+      let field_path = format!("{path}.{}", name(new_field));
+      let Some(old_index) = old_indices[new_index] else {
+        let named = (has_default(self.new, new_field), true);
+        let kind = ChangeKind::FieldAdded;
+        self.push(&field_path, kind, [named, NEITHER, NEITHER]);
+        continue;
+      };
+      let old_field = old[old_index];
+      if renamed[new_index] {
+        let kind = ChangeKind::FieldRenamed {
+          old: name(old_field).to_string(),
+          new: name(new_field).to_string(),
+        };
+        let named = (
+          has_default(self.new, new_field),
+          has_default(self.old, old_field),
+        );
+        self.push(&field_path, kind, [named, BOTH, BOTH]);
+      }
+      self.field(&field_path, old_field, new_field);
+    }
+
+    for (old_index, old_field) in old.iter().enumerate() {
+      if !paired[old_index] {
+        let named = (true, has_default(self.old, old_field));
+        self.push(
+          &format!("{path}.{}", name(old_field)),
+          ChangeKind::FieldRemoved,
+          [named, NEITHER, NEITHER],
+        );
+      }
+    }
+
+    // fields that moved relative to each other, rather than because fields
+    // around them were added or removed
+    let matched = old_indices
+      .iter()
+      .enumerate()
+      .filter_map(|(new_index, old_index)| Some((new_index, (*old_index)?)))
+      .collect::<Vec<_>>();
+    let mut sorted = matched
+      .iter()
+      .map(|&(_, old_index)| old_index)
+      .collect::<Vec<_>>();
+    sorted.sort_unstable();
+    for (rank, &(new_index, old_index)) in matched.iter().enumerate() {
+      if sorted[rank] != old_index {
+        let kind = ChangeKind::FieldMoved {
+          old_index,
+          new_index,
+        };
+        let field_path = format!("{path}.{}", name(new[new_index]));
+        self.push(&field_path, kind, [BOTH, NEITHER, NEITHER]);
+      }
+    }
+  }
+
+  fn field(&mut self, path: &str, old: &'a Field, new: &'a Field) {
+    if old.offset != new.offset {
+      let kind = ChangeKind::OffsetChanged {
+        old: old.offset,
+        new: new.offset,
+      };
+      self.push(path, kind, [BOTH, BOTH, NEITHER]);
+    }
+    self.types(path, &old.ty, &new.ty);
+  }
+
+  /// Compare the variants of enums, which are matched up by their names.
+  /// Variants with new names that take the place of removed variants with
+  /// the same fields are renamed
+  fn variants(&mut self, path: &str, old: &'a [Variant], new: &'a [Variant]) {
+    let mut paired = vec![false; old.len()];
+    let mut old_indices = new
+      .iter()
+      .map(|new_variant| {
+        let old_index = old
+          .iter()
+          .position(|old_variant| old_variant.name() == new_variant.name())?;
+        paired[old_index] = true;
+        Some(old_index)
+      })
+      .collect::<Vec<_>>();
+    let mut renamed = vec![false; new.len()];
+    for (new_index, new_variant) in new.iter().enumerate() {
+      if old_indices[new_index].is_some() {
+        continue;
+      }
+      let old_index = (0..old.len())
+        .find(|&index| !paired[index] && same_shape(&old[index], new_variant));
+      if let Some(old_index) = old_index {
+        paired[old_index] = true;
+        old_indices[new_index] = Some(old_index);
+        renamed[new_index] = true;
+      }
+    }
+
+    for (new_index, new_variant) in new.iter().enumerate() {
+      let variant_path = format!("{path}::{}", new_variant.name());
+      let Some(old_index) = old_indices[new_index] else {
+        // old values never have the new variant, unless they had another
+        // variant with the same index
+        let by_index = match new_index < old.len() {
+          true => NEITHER,
+          false => BACKWARD,
+        };
+        let kind = ChangeKind::VariantAdded;
+        self.push(&variant_path, kind, [BACKWARD, by_index, by_index]);
+        continue;
+      };
+      let old_variant = &old[old_index];
+      if renamed[new_index] {
+        let kind = ChangeKind::VariantRenamed {
+          old: old_variant.name().to_string(),
+          new: new_variant.name().to_string(),
+        };
+        self.push(&variant_path, kind, [NEITHER, BOTH, BOTH]);
+      }
+      if old_index != new_index {
+        let kind = ChangeKind::VariantMoved {
+          old_index,
+          new_index,
+        };
+        self.push(&variant_path, kind, [BOTH, NEITHER, NEITHER]);
+      }
+
+      match (old_variant, new_variant) {
+        (Variant::Unit { .. }, Variant::Unit { .. }) => {},
+        (
+          Variant::Tuple {
+            fields: old_fields, ..
+          },
+          Variant::Tuple {
+            fields: new_fields, ..
+          },
+        ) => self.anonymous_fields(&variant_path, old_fields, new_fields),
+        (
+          Variant::Struct {
+            fields: old_fields, ..
+          },
+          Variant::Struct {
+            fields: new_fields, ..
+          },
+        ) => self.named_fields(&variant_path, old_fields, new_fields),
+        _ => {
+          let kind = ChangeKind::Retyped {
+            old: variant_kind(old_variant).to_string(),
+            new: variant_kind(new_variant).to_string(),
+          };
+          self.push(&variant_path, kind, [NEITHER; 3]);
+        },
+      }
+    }
+
+    for (old_index, old_variant) in old.iter().enumerate() {
+      if !paired[old_index] {
+        // new values never have the old variant, unless they have another
+        // variant with the same index
+        let by_index = match old_index < new.len() {
+          true => NEITHER,
+          false => FORWARD,
+        };
+        self.push(
+          &format!("{path}::{}", old_variant.name()),
+          ChangeKind::VariantRemoved,
+          [FORWARD, by_index, by_index],
+        );
+      }
+    }
+  }
+}
+
+/// Whether two types are the same sort of type, so that what's in them can
+/// be compared
+fn same_kind(old: &Kind, new: &Kind) -> bool {
+  match (old, new) {
+    (
+      Kind::Primitive { primitive: old },
+      Kind::Primitive { primitive: new },
+    ) => old == new,
+    (Kind::Array { length: old, .. }, Kind::Array { length: new, .. }) => {
+      old == new
+    },
+    _ => ::core::mem::discriminant(old) == ::core::mem::discriminant(new),
+  }
+}
+
+/// Whether two variants have the same fields
+fn same_shape(old: &Variant, new: &Variant) -> bool {
+  ::core::mem::discriminant(old) == ::core::mem::discriminant(new)
+    && old.fields().len() == new.fields().len()
+    && old
+      .fields()
+      .iter()
+      .zip(new.fields())
+      .all(|(old, new)| old.ty == new.ty && old.name == new.name)
+}
+
+fn variant_kind(variant: &Variant) -> &'static str {
+  match variant {
+    Variant::Unit { .. } => "unit variant",
+    Variant::Tuple { .. } => "tuple variant",
+    Variant::Struct { .. } => "struct variant",
+  }
+}
+
+/// Whether every value of `old` is also a value of `new`
+fn is_widening(old: Primitive, new: Primitive) -> bool {
+  let integer = |primitive| {
+    Some(match primitive {
+      Primitive::u8 => (false, 8),
+      Primitive::u16 => (false, 16),
+      Primitive::u32 => (false, 32),
+      Primitive::u64 => (false, 64),
+      Primitive::u128 => (false, 128),
+      Primitive::usize => (false, usize::BITS),
+      Primitive::i8 => (true, 8),
+      Primitive::i16 => (true, 16),
+      Primitive::i32 => (true, 32),
+      Primitive::i64 => (true, 64),
+      Primitive::i128 => (true, 128),
+      Primitive::isize => (true, isize::BITS),
+      _ => return None,
+    })
+  };
+  match (old, new) {
+    (Primitive::f32, Primitive::f64) => true,
+    _ => match (integer(old), integer(new)) {
+      (Some((old_signed, old_bits)), Some((new_signed, new_bits))) => {
+        new_bits > old_bits && (old_signed == new_signed || new_signed)
+      },
+      _ => false,
+    },
+  }
+}
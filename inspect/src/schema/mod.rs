@@ -0,0 +1,283 @@
+//! An owned description of a type, and every type it refers to, that can be
+//! written out and read back in
+//!
+//! A [`TypeInfo`] is made of `'static` references, function pointers and
+//! [`TypeId`]s, which are only meaningful inside the program that created
+//! them. A [`Schema`] instead holds a table of [`TypeDef`]s keyed by type
+//! name, which refer to each other by those names.
+//!
+//! With the `serde` feature the schema implements `Serialize` and
+//! `Deserialize`, and with the `json` feature it can be converted to and from
+//! JSON directly.
+//!
+//! A schema that was written out by an older build can be compared to the
+//! current one with [`Schema::compare`], to check that values stored by one
+//! can still be read by the other.
+//!
+//! ```rust
+//! # use ::inspect::{TypeInfo, schema::{Kind, Schema}};
+//! #[derive(TypeInfo)]
+//! struct Message {
+//!   id: u64,
+//!   tags: Vec<String>,
+//! }
+//!
+//! let schema = Schema::of::<Message>();
+//! assert_eq!(schema.root, "Message");
+//! assert!(matches!(
+//!   schema.types["Vec<String>"].kind,
+//!   Kind::Vec { ref item } if item == "String",
+//! ));
+//! ```
+//!
+//! [`TypeInfo`]: enum@crate::TypeInfo
+//! [`TypeId`]: ::core::any::TypeId
+
+mod build;
+mod compat;
+
+pub use self::compat::{Change, ChangeKind, Encoding, Report};
+use {
+  crate::type_info::{Primitive, TypeInfo, TypeInfoProvider},
+  ::std::collections::BTreeMap,
+};
+
+/// A table of type definitions, starting from a root type
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize)
+)]
+pub struct Schema {
+  /// The name of the type the schema was created from
+  pub root: String,
+  /// Every type reachable from the root type, keyed by name
+  pub types: BTreeMap<String, TypeDef>,
+}
+
+impl Schema {
+  /// Create the schema of `T`
+  pub fn of<T>() -> Self
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    Schema::from_type_info(TypeInfo::of::<T>())
+  }
+
+  /// Create the schema of the type described by `type_info`, by walking every
+  /// type it refers to
+  ///
+  /// Types are named by their [`type_name`] without module paths, unless
+  /// that would be ambiguous, in which case the full `type_name` is used.
+  ///
+  /// [`type_name`]: ::core::any::type_name
+  pub fn from_type_info(type_info: &'static TypeInfo) -> Self {
+    build::build(type_info)
+  }
+
+  /// The definition of the root type, which a malformed schema, such as one
+  /// read from a file that was edited by hand, may be missing
+  pub fn root_def(&self) -> Option<&TypeDef> {
+    self.types.get(&self.root)
+  }
+
+  /// Compare the schema to a newer version of it, reporting every change
+  /// between them, and whether values written with one version can still be
+  /// read with the other when they're stored with `encoding`
+  ///
+  /// Types are compared by their structure rather than their names, starting
+  /// from the two root types.
+  pub fn compare(&self, new: &Schema, encoding: Encoding) -> Report {
+    compat::compare(self, new, encoding)
+  }
+
+  /// Write the schema as JSON
+  #[cfg(feature = "json")]
+  pub fn to_json(&self) -> String {
+    ::serde_json::to_string_pretty(self).unwrap()
+  }
+
+  /// Read a schema from JSON, as written by [`Schema::to_json`]
+  #[cfg(feature = "json")]
+  pub fn from_json(json: &str) -> Result<Self, ::serde_json::Error> {
+    ::serde_json::from_str(json)
+  }
+}
+
+/// The definition of a single type in a [`Schema`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize)
+)]
+pub struct TypeDef {
+  /// The [`type_name`] of the type
+  ///
+  /// [`type_name`]: ::core::any::type_name
+  pub type_name: String,
+  /// The size of the type, if it's statically sized
+  pub size: Option<usize>,
+  /// The alignment of the type, if it's statically sized
+  pub align: Option<usize>,
+  /// Whether the type is known to implement `Default`; see
+  /// [`TypeInfo::default_fn`]
+  #[cfg_attr(
+    feature = "serde",
+    serde(default, skip_serializing_if = "::core::ops::Not::not")
+  )]
+  pub default: bool,
+  /// The doc comment on the type
+  #[cfg_attr(
+    feature = "serde",
+    serde(default, skip_serializing_if = "Option::is_none")
+  )]
+  pub doc: Option<String>,
+  /// What sort of type it is, and the types it refers to
+  #[cfg_attr(feature = "serde", serde(flatten))]
+  pub kind: Kind,
+}
+
+/// What sort of type a [`TypeDef`] describes. Other types are referred to by
+/// their name in the [`Schema`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize),
+  serde(tag = "kind", rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum Kind {
+  Primitive { primitive: Primitive },
+  Str,
+  String,
+  Slice { item: String },
+  Array { item: String, length: usize },
+  Vec { item: String },
+  Option { item: String },
+  Result { ok: String, err: String },
+  PhantomData { item: String },
+  HashMap { key: String, value: String },
+  BTreeMap { key: String, value: String },
+  Ref { target: String },
+  RefMut { target: String },
+  RawConst { target: String },
+  RawMut { target: String },
+  Box { target: String },
+  Tuple { fields: Vec<Field> },
+  UnitStruct,
+  TupleStruct { fields: Vec<Field> },
+  Struct { fields: Vec<Field> },
+  Union { fields: Vec<Field> },
+  Enum { variants: Vec<Variant> },
+}
+
+/// A field of a tuple, struct, or enum variant
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize)
+)]
+pub struct Field {
+  /// The name of the field, or `None` for the fields of tuples, tuple
+  /// structs, and tuple variants
+  #[cfg_attr(
+    feature = "serde",
+    serde(default, skip_serializing_if = "Option::is_none")
+  )]
+  pub name: Option<String>,
+  /// The offset of the field, in bytes
+  pub offset: usize,
+  /// The name of the field's type in the [`Schema`]
+  #[cfg_attr(feature = "serde", serde(rename = "type"))]
+  pub ty: String,
+  /// The name given with `#[inspect(rename = "name")]`
+  #[cfg_attr(
+    feature = "serde",
+    serde(default, skip_serializing_if = "Option::is_none")
+  )]
+  pub rename: Option<String>,
+  /// Whether the field is marked `#[inspect(skip)]`
+  #[cfg_attr(
+    feature = "serde",
+    serde(default, skip_serializing_if = "::core::ops::Not::not")
+  )]
+  pub skip: bool,
+  /// The doc comment on the field
+  #[cfg_attr(
+    feature = "serde",
+    serde(default, skip_serializing_if = "Option::is_none")
+  )]
+  pub doc: Option<String>,
+}
+
+impl Field {
+  /// The name of the field, or the name it was given with
+  /// `#[inspect(rename = "name")]`, or `None` if it doesn't have a name
+  pub fn serialized_name(&self) -> Option<&str> {
+    self.rename.as_deref().or(self.name.as_deref())
+  }
+}
+
+/// A variant of an enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize),
+  serde(tag = "kind", rename_all = "snake_case")
+)]
+pub enum Variant {
+  Unit {
+    name: String,
+    #[cfg_attr(
+      feature = "serde",
+      serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    doc: Option<String>,
+  },
+  Tuple {
+    name: String,
+    fields: Vec<Field>,
+    #[cfg_attr(
+      feature = "serde",
+      serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    doc: Option<String>,
+  },
+  Struct {
+    name: String,
+    fields: Vec<Field>,
+    #[cfg_attr(
+      feature = "serde",
+      serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    doc: Option<String>,
+  },
+}
+
+impl Variant {
+  /// The name of the variant
+  pub fn name(&self) -> &str {
+    match self {
+      Variant::Unit { name, .. }
+      | Variant::Tuple { name, .. }
+      | Variant::Struct { name, .. } => name,
+    }
+  }
+
+  /// The doc comment on the variant
+  pub fn doc(&self) -> Option<&str> {
+    match self {
+      Variant::Unit { doc, .. }
+      | Variant::Tuple { doc, .. }
+      | Variant::Struct { doc, .. } => doc.as_deref(),
+    }
+  }
+
+  /// The fields of the variant
+  pub fn fields(&self) -> &[Field] {
+    match self {
+      Variant::Unit { .. } => &[],
+      Variant::Tuple { fields, .. } | Variant::Struct { fields, .. } => fields,
+    }
+  }
+}
@@ -0,0 +1,828 @@
+use {
+  crate::{
+    type_info::{
+      internal::ConcurrentMap, AnonymousFieldInfo, Enum, EnumInfo,
+      EnumVariantInfo, NamedFieldInfo, Pointer, Primitive, Sequence, Std,
+      Struct, Tuple, TypeInfo, TypeInfoProvider,
+    },
+    value::{BuildError, DynValue, ValueBuilder},
+  },
+  ::core::{any::TypeId, fmt},
+  ::serde::de::{
+    Deserialize, DeserializeSeed, Deserializer, EnumAccess, Error, IgnoredAny,
+    MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
+  },
+};
+
+/// Deserializes a [`DynValue`] of the type described by a [`TypeInfo`]
+///
+/// The type doesn't need to implement `Deserialize`, only
+/// `#[derive(TypeInfo)]`, and derived types must opt in to being built from
+/// their fields with `#[inspect(constructible)]`; see
+/// [`TypeInfo::is_constructible`]. Fields that are missing, or marked
+/// `#[inspect(skip)]`, are given their type's [`Default`] value, when the type
+/// is known to implement it; see [`TypeInfo::default_fn`].
+///
+/// ```rust
+/// # use ::inspect::{TypeInfo, serde::TypeSeed};
+/// # use ::serde::de::DeserializeSeed;
+/// #[derive(TypeInfo, Debug, PartialEq)]
+/// #[inspect(constructible)]
+/// struct Config {
+///   name: String,
+///   retries: Option<u8>,
+/// }
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"{"name":"a"}"#);
+/// let value = TypeSeed::of::<Config>()
+///   .deserialize(&mut deserializer)
+///   .unwrap();
+/// assert_eq!(
+///   value.downcast::<Config>().ok(),
+///   Some(Config { name: "a".to_string(), retries: None }),
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct TypeSeed {
+  type_info: &'static TypeInfo,
+}
+
+impl TypeSeed {
+  /// Deserialize a value of the type described by `type_info`
+  pub fn new(type_info: &'static TypeInfo) -> Self {
+    TypeSeed { type_info }
+  }
+
+  /// Deserialize a value of type `T`
+  pub fn of<T>() -> Self
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    TypeSeed::new(TypeInfo::of::<T>())
+  }
+
+  /// The [`TypeInfo`] of the type being deserialized
+  pub fn type_info(&self) -> &'static TypeInfo {
+    self.type_info
+  }
+}
+
+/// Deserializes a value through its [`TypeInfo`], replacing the value it wraps
+///
+/// ```rust
+/// # use ::inspect::{TypeInfo, serde::ReflectedMut};
+/// # use ::serde::de::DeserializeSeed;
+/// #[derive(TypeInfo)]
+/// #[inspect(constructible)]
+/// enum Difficulty {
+///   Easy,
+///   Hard { lives: u8 },
+/// }
+///
+/// let mut difficulty = Difficulty::Easy;
+/// let mut deserializer =
+///   serde_json::Deserializer::from_str(r#"{"Hard":{"lives":3}}"#);
+/// ReflectedMut(&mut difficulty)
+///   .deserialize(&mut deserializer)
+///   .unwrap();
+/// assert!(matches!(difficulty, Difficulty::Hard { lives: 3 }));
+/// ```
+pub struct ReflectedMut<'a, T>(pub &'a mut T);
+
+impl<'de, T> DeserializeSeed<'de> for ReflectedMut<'_, T>
+where
+  T: TypeInfoProvider,
+{
+  type Value = ();
+
+  fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let value = TypeSeed::of::<T>().deserialize(deserializer)?;
+    unsafe { value.take_with(|value| *self.0 = value.cast::<T>().read()) };
+    Ok(())
+  }
+}
+
+impl<'de> DeserializeSeed<'de> for TypeSeed {
+  type Value = DynValue;
+
+  fn deserialize<D>(self, deserializer: D) -> Result<DynValue, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let type_info = self.type_info;
+    let name = type_info.bare_name();
+    if !type_info.is_constructible() {
+      return Err(D::Error::custom(BuildError::NotConstructible {
+        type_name: type_info.type_name(),
+      }));
+    }
+
+    match type_info {
+      TypeInfo::Primitive(primitive) => match primitive {
+        Primitive::u8 => u8::deserialize(deserializer).map(DynValue::new),
+        Primitive::u16 => u16::deserialize(deserializer).map(DynValue::new),
+        Primitive::u32 => u32::deserialize(deserializer).map(DynValue::new),
+        Primitive::u64 => u64::deserialize(deserializer).map(DynValue::new),
+        Primitive::u128 => u128::deserialize(deserializer).map(DynValue::new),
+
+        Primitive::i8 => i8::deserialize(deserializer).map(DynValue::new),
+        Primitive::i16 => i16::deserialize(deserializer).map(DynValue::new),
+        Primitive::i32 => i32::deserialize(deserializer).map(DynValue::new),
+        Primitive::i64 => i64::deserialize(deserializer).map(DynValue::new),
+        Primitive::i128 => i128::deserialize(deserializer).map(DynValue::new),
+
+        Primitive::f32 => f32::deserialize(deserializer).map(DynValue::new),
+        Primitive::f64 => f64::deserialize(deserializer).map(DynValue::new),
+
+        Primitive::usize => {
+          usize::deserialize(deserializer).map(DynValue::new)
+        },
+        Primitive::isize => {
+          isize::deserialize(deserializer).map(DynValue::new)
+        },
+        Primitive::Unit => <()>::deserialize(deserializer).map(DynValue::new),
+        Primitive::bool => bool::deserialize(deserializer).map(DynValue::new),
+        Primitive::char => char::deserialize(deserializer).map(DynValue::new),
+      },
+      TypeInfo::Std(Std::String) => {
+        String::deserialize(deserializer).map(DynValue::new)
+      },
+      TypeInfo::Sequence(Sequence::Array { item, info, .. }) => deserializer
+        .deserialize_tuple(
+          info.array_length,
+          ArrayVisitor {
+            type_info,
+            item_type_info: (item.type_info_fn)(),
+            length: info.array_length,
+          },
+        ),
+      TypeInfo::Std(Std::Vec { item, info, .. }) => deserializer
+        .deserialize_seq(VecVisitor {
+          type_info,
+          item_type_info: (item.type_info_fn)(),
+          push_fn: info.push_fn,
+        }),
+      TypeInfo::Std(
+        Std::HashMap { info, .. } | Std::BTreeMap { info, .. },
+      ) => deserializer.deserialize_map(MapVisitor {
+        type_info,
+        key_type_info: (info.key_type_info_fn)(),
+        value_type_info: (info.value_type_info_fn)(),
+        insert_fn: info.insert_fn,
+      }),
+      TypeInfo::Std(Std::Option { item, info, .. }) => deserializer
+        .deserialize_option(OptionVisitor {
+          type_info,
+          some_type_info: (item.type_info_fn)(),
+          some_fn: info.some_fn,
+        }),
+      TypeInfo::Std(Std::Result { info, .. }) => deserializer
+        .deserialize_enum(
+          "Result",
+          RESULT_VARIANTS,
+          ResultVisitor {
+            type_info,
+            ok_type_info: (info.ok_type_info_fn)(),
+            err_type_info: (info.err_type_info_fn)(),
+            ok_fn: info.ok_fn,
+            err_fn: info.err_fn,
+          },
+        ),
+      TypeInfo::Std(Std::PhantomData { .. }) => {
+        deserializer.deserialize_unit_struct("PhantomData", UnitVisitor)?;
+        Ok(DynValue::new_default(type_info).unwrap())
+      },
+      TypeInfo::Pointer(Pointer::Box { item, .. })
+        if (item.type_info_fn)().layout().is_some() =>
+      {
+        // a `Box` of a sized type is a pointer to an allocation with the
+        // type's layout, made by the global allocator, just like a `DynValue`
+        let target = TypeSeed::new((item.type_info_fn)())
+          .deserialize(deserializer)?
+          .into_raw()
+          .0;
+        Ok(unsafe {
+          DynValue::new_with(type_info, |ptr| {
+            ptr.cast::<*mut u8>().write(target.as_ptr())
+          })
+        })
+      },
+      TypeInfo::Tuple(Tuple::Tuple { info, .. }) => {
+        let fields = FieldsVisitor::anonymous(
+          ValueBuilder::new(type_info).unwrap(),
+          info.field_infos,
+        );
+        deserializer.deserialize_tuple(fields.len(), fields)
+      },
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { .. } => {
+          deserializer.deserialize_unit_struct(name, UnitVisitor)?;
+          ValueBuilder::new(type_info)
+            .and_then(ValueBuilder::build)
+            .map_err(D::Error::custom)
+        },
+        Struct::TupleStruct { fields, .. } => {
+          let newtype = is_newtype(fields.field_infos);
+          let fields = FieldsVisitor::anonymous(
+            ValueBuilder::new(type_info).unwrap(),
+            fields.field_infos,
+          );
+          if newtype {
+            deserializer.deserialize_newtype_struct(name, fields)
+          } else {
+            deserializer.deserialize_tuple_struct(name, fields.len(), fields)
+          }
+        },
+        Struct::Struct { fields, .. } => {
+          let fields = FieldsVisitor::named(
+            ValueBuilder::new(type_info).unwrap(),
+            fields.field_infos,
+            (type_info.type_id(), None),
+          );
+          deserializer.deserialize_struct(name, fields.names, fields)
+        },
+      },
+      TypeInfo::Enum(Enum::Enum { variants, .. }) => {
+        let names = static_names((type_info.type_id(), None), || {
+          variants
+            .variant_infos
+            .iter()
+            .map(|variant| match variant {
+              EnumVariantInfo::Unit { variant_name, .. }
+              | EnumVariantInfo::Tuple { variant_name, .. }
+              | EnumVariantInfo::Struct { variant_name, .. } => *variant_name,
+            })
+            .collect()
+        });
+        deserializer.deserialize_enum(
+          name,
+          names,
+          EnumVisitor {
+            type_info,
+            variants,
+            names,
+          },
+        )
+      },
+      _ => Err(D::Error::custom(format_args!(
+        "can't deserialize a value of type `{}`",
+        type_info.type_name(),
+      ))),
+    }
+  }
+}
+
+const RESULT_VARIANTS: &[&str] = &["Ok", "Err"];
+
+/// Leak a list of names once per key, since serde needs `'static` lists of
+/// field and variant names
+fn static_names(
+  key: (TypeId, Option<usize>),
+  names: impl FnOnce() -> Vec<&'static str>,
+) -> &'static [&'static str] {
+  static NAMES: ConcurrentMap<
+    (TypeId, Option<usize>),
+    &'static [&'static str],
+  > = ConcurrentMap::new();
+
+  NAMES.get_or_insert_with(key, || Box::leak(names().into_boxed_slice()))
+}
+
+/// Whether a tuple struct or variant is serialized as its only field
+fn is_newtype(field_infos: &[AnonymousFieldInfo]) -> bool {
+  matches!(field_infos, [field] if !field.attributes.skip)
+}
+
+struct UnitVisitor;
+
+impl Visitor<'_> for UnitVisitor {
+  type Value = ();
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("unit")
+  }
+
+  fn visit_unit<E>(self) -> Result<(), E> {
+    Ok(())
+  }
+}
+
+struct VecVisitor {
+  type_info: &'static TypeInfo,
+  item_type_info: &'static TypeInfo,
+  push_fn: unsafe fn(*mut u8, *mut u8),
+}
+
+impl<'de> Visitor<'de> for VecVisitor {
+  type Value = DynValue;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a sequence of `{}`", self.item_type_info.type_name())
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> Result<DynValue, A::Error>
+  where
+    A: SeqAccess<'de>,
+  {
+    let mut vec = DynValue::new_default(self.type_info).unwrap();
+    let vec_ptr = vec.as_value_mut().as_mut_ptr();
+    while let Some(item) =
+      seq.next_element_seed(TypeSeed::new(self.item_type_info))?
+    {
+      unsafe { item.take_with(|item| (self.push_fn)(vec_ptr, item)) };
+    }
+    Ok(vec)
+  }
+}
+
+struct ArrayVisitor {
+  type_info: &'static TypeInfo,
+  item_type_info: &'static TypeInfo,
+  length: usize,
+}
+
+impl<'de> Visitor<'de> for ArrayVisitor {
+  type Value = DynValue;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "an array of {} `{}`",
+      self.length,
+      self.item_type_info.type_name()
+    )
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> Result<DynValue, A::Error>
+  where
+    A: SeqAccess<'de>,
+  {
+    let mut items = Vec::with_capacity(self.length);
+    while items.len() < self.length {
+      match seq.next_element_seed(TypeSeed::new(self.item_type_info))? {
+        Some(item) => items.push(item),
+        None => return Err(A::Error::invalid_length(items.len(), &self)),
+      }
+    }
+    if seq.next_element::<IgnoredAny>()?.is_some() {
+      return Err(A::Error::invalid_length(self.length + 1, &self));
+    }
+    let stride = self.item_type_info.size().unwrap();
+    Ok(unsafe {
+      DynValue::new_with(self.type_info, |ptr| {
+        for (index, item) in items.into_iter().enumerate() {
+          item.take_with(|item| {
+            ::core::ptr::copy_nonoverlapping(
+              item,
+              ptr.add(index * stride),
+              stride,
+            )
+          });
+        }
+      })
+    })
+  }
+}
+
+struct MapVisitor {
+  type_info: &'static TypeInfo,
+  key_type_info: &'static TypeInfo,
+  value_type_info: &'static TypeInfo,
+  insert_fn: unsafe fn(*mut u8, *mut u8, *mut u8),
+}
+
+impl<'de> Visitor<'de> for MapVisitor {
+  type Value = DynValue;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "a map from `{}` to `{}`",
+      self.key_type_info.type_name(),
+      self.value_type_info.type_name(),
+    )
+  }
+
+  fn visit_map<A>(self, mut map: A) -> Result<DynValue, A::Error>
+  where
+    A: MapAccess<'de>,
+  {
+    let mut dyn_map = DynValue::new_default(self.type_info).unwrap();
+    let map_ptr = dyn_map.as_value_mut().as_mut_ptr();
+    while let Some((key, value)) = map.next_entry_seed(
+      TypeSeed::new(self.key_type_info),
+      TypeSeed::new(self.value_type_info),
+    )? {
+      unsafe {
+        key.take_with(|key| {
+          value.take_with(|value| (self.insert_fn)(map_ptr, key, value))
+        })
+      };
+    }
+    Ok(dyn_map)
+  }
+}
+
+struct OptionVisitor {
+  type_info: &'static TypeInfo,
+  some_type_info: &'static TypeInfo,
+  some_fn: unsafe fn(*mut u8, *mut u8),
+}
+
+impl<'de> Visitor<'de> for OptionVisitor {
+  type Value = DynValue;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "an optional `{}`", self.some_type_info.type_name())
+  }
+
+  fn visit_none<E>(self) -> Result<DynValue, E>
+  where
+    E: Error,
+  {
+    Ok(DynValue::new_default(self.type_info).unwrap())
+  }
+
+  fn visit_unit<E>(self) -> Result<DynValue, E>
+  where
+    E: Error,
+  {
+    self.visit_none()
+  }
+
+  fn visit_some<D>(self, deserializer: D) -> Result<DynValue, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let some = TypeSeed::new(self.some_type_info).deserialize(deserializer)?;
+    Ok(unsafe { some.wrap(self.type_info, self.some_fn) })
+  }
+}
+
+struct ResultVisitor {
+  type_info: &'static TypeInfo,
+  ok_type_info: &'static TypeInfo,
+  err_type_info: &'static TypeInfo,
+  ok_fn: unsafe fn(*mut u8, *mut u8),
+  err_fn: unsafe fn(*mut u8, *mut u8),
+}
+
+impl<'de> Visitor<'de> for ResultVisitor {
+  type Value = DynValue;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a `{}`", self.type_info.type_name())
+  }
+
+  fn visit_enum<A>(self, data: A) -> Result<DynValue, A::Error>
+  where
+    A: EnumAccess<'de>,
+  {
+    let (index, variant) = data.variant_seed(VariantSeed {
+      names: RESULT_VARIANTS,
+    })?;
+    let (type_info, wrap_fn) = match index {
+      0 => (self.ok_type_info, self.ok_fn),
+      _ => (self.err_type_info, self.err_fn),
+    };
+    let value = variant.newtype_variant_seed(TypeSeed::new(type_info))?;
+    Ok(unsafe { value.wrap(self.type_info, wrap_fn) })
+  }
+}
+
+struct EnumVisitor {
+  type_info: &'static TypeInfo,
+  variants: &'static EnumInfo,
+  names: &'static [&'static str],
+}
+
+impl<'de> Visitor<'de> for EnumVisitor {
+  type Value = DynValue;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "enum `{}`", self.type_info.type_name())
+  }
+
+  fn visit_enum<A>(self, data: A) -> Result<DynValue, A::Error>
+  where
+    A: EnumAccess<'de>,
+  {
+    let (index, variant) =
+      data.variant_seed(VariantSeed { names: self.names })?;
+    let builder = ValueBuilder::variant(self.type_info, self.names[index])
+      .map_err(A::Error::custom)?;
+
+    match &self.variants.variant_infos[index] {
+      EnumVariantInfo::Unit { .. } => {
+        variant.unit_variant()?;
+        builder.build().map_err(A::Error::custom)
+      },
+      EnumVariantInfo::Tuple { field_infos, .. } => {
+        let fields = FieldsVisitor::anonymous(builder, field_infos);
+        if is_newtype(field_infos) {
+          let value =
+            variant.newtype_variant_seed(TypeSeed::new((field_infos[0]
+              .type_info_fn)(
+            )))?;
+          fields.finish(vec![Some(value)])
+        } else {
+          variant.tuple_variant(fields.len(), fields)
+        }
+      },
+      EnumVariantInfo::Struct { field_infos, .. } => {
+        let fields = FieldsVisitor::named(
+          builder,
+          field_infos,
+          (self.type_info.type_id(), Some(index)),
+        );
+        variant.struct_variant(fields.names, fields)
+      },
+    }
+  }
+}
+
+/// Deserializes the name, or index, of a variant into its index
+struct VariantSeed {
+  names: &'static [&'static str],
+}
+
+impl<'de> DeserializeSeed<'de> for VariantSeed {
+  type Value = usize;
+
+  fn deserialize<D>(self, deserializer: D) -> Result<usize, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    deserializer.deserialize_identifier(self)
+  }
+}
+
+impl Visitor<'_> for VariantSeed {
+  type Value = usize;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "one of the variants `{}`", self.names.join("`, `"))
+  }
+
+  fn visit_u64<E>(self, index: u64) -> Result<usize, E>
+  where
+    E: Error,
+  {
+    usize::try_from(index)
+      .ok()
+      .filter(|&index| index < self.names.len())
+      .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(index), &self))
+  }
+
+  fn visit_str<E>(self, name: &str) -> Result<usize, E>
+  where
+    E: Error,
+  {
+    self
+      .names
+      .iter()
+      .position(|variant| *variant == name)
+      .ok_or_else(|| E::unknown_variant(name, self.names))
+  }
+
+  fn visit_bytes<E>(self, name: &[u8]) -> Result<usize, E>
+  where
+    E: Error,
+  {
+    match ::core::str::from_utf8(name) {
+      Ok(name) => self.visit_str(name),
+      Err(_) => Err(E::invalid_value(Unexpected::Bytes(name), &self)),
+    }
+  }
+}
+
+/// Deserializes the fields of a struct, tuple, or enum variant, and then
+/// builds it
+struct FieldsVisitor {
+  builder: ValueBuilder,
+  fields: Vec<Field>,
+  /// The serialized names of the fields that aren't skipped, or nothing for
+  /// anonymous fields
+  names: &'static [&'static str],
+}
+
+struct Field {
+  /// The serialized name of a named field
+  name: Option<&'static str>,
+  type_info: &'static TypeInfo,
+  skip: bool,
+}
+
+impl FieldsVisitor {
+  fn named(
+    builder: ValueBuilder,
+    field_infos: &'static [NamedFieldInfo],
+    key: (TypeId, Option<usize>),
+  ) -> Self {
+    let names = static_names(key, || {
+      field_infos
+        .iter()
+        .filter(|field| !field.attributes.skip)
+        .map(NamedFieldInfo::serialized_name)
+        .collect()
+    });
+    let fields = field_infos
+      .iter()
+      .map(|field| Field {
+        name: Some(field.serialized_name()),
+        type_info: (field.type_info_fn)(),
+        skip: field.attributes.skip,
+      })
+      .collect();
+    FieldsVisitor {
+      builder,
+      fields,
+      names,
+    }
+  }
+
+  fn anonymous(
+    builder: ValueBuilder,
+    field_infos: &'static [AnonymousFieldInfo],
+  ) -> Self {
+    let fields = field_infos
+      .iter()
+      .map(|field| Field {
+        name: None,
+        type_info: (field.type_info_fn)(),
+        skip: field.attributes.skip,
+      })
+      .collect();
+    FieldsVisitor {
+      builder,
+      fields,
+      names: &[],
+    }
+  }
+
+  /// The number of fields that aren't skipped
+  fn len(&self) -> usize {
+    self.unskipped().count()
+  }
+
+  /// The fields that aren't skipped, in the order they're serialized
+  fn unskipped(&self) -> impl Iterator<Item = (usize, &Field)> {
+    self
+      .fields
+      .iter()
+      .enumerate()
+      .filter(|(_, field)| !field.skip)
+  }
+
+  /// Build the value from the values deserialized for each field that isn't
+  /// skipped, filling in the rest with their default values
+  fn finish<E>(mut self, values: Vec<Option<DynValue>>) -> Result<DynValue, E>
+  where
+    E: Error,
+  {
+    let mut values = values.into_iter();
+    for field in &self.fields {
+      let value = match field.skip {
+        false => values.next().flatten(),
+        true => None,
+      };
+      let value = match value {
+        Some(value) => value,
+        None => match DynValue::new_default(field.type_info) {
+          Some(value) => value,
+          None if field.skip => {
+            return Err(E::custom(format_args!(
+              "skipped field of type `{}` has no default value",
+              field.type_info.type_name(),
+            )))
+          },
+          None => match field.name {
+            Some(name) => return Err(E::missing_field(name)),
+            None => {
+              return Err(E::custom(format_args!(
+                "missing field of type `{}`",
+                field.type_info.type_name(),
+              )))
+            },
+          },
+        },
+      };
+      self.builder.push(value).map_err(E::custom)?;
+    }
+    self.builder.build().map_err(E::custom)
+  }
+}
+
+impl<'de> Visitor<'de> for FieldsVisitor {
+  type Value = DynValue;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "`{}`", self.builder.type_info().type_name())
+  }
+
+  fn visit_newtype_struct<D>(
+    self,
+    deserializer: D,
+  ) -> Result<DynValue, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    // only types with exactly one field that isn't skipped are newtypes
+    let only = {
+      let mut unskipped = self.unskipped().map(|(_, field)| field.type_info);
+      (unskipped.next(), unskipped.next())
+    };
+    let (Some(type_info), None) = only else {
+      return Err(D::Error::invalid_type(Unexpected::NewtypeStruct, &self));
+    };
+    let value = TypeSeed::new(type_info).deserialize(deserializer)?;
+    self.finish(vec![Some(value)])
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> Result<DynValue, A::Error>
+  where
+    A: SeqAccess<'de>,
+  {
+    let mut values = Vec::new();
+    for (_, field) in self.unskipped() {
+      match seq.next_element_seed(TypeSeed::new(field.type_info))? {
+        Some(value) => values.push(Some(value)),
+        None => return Err(A::Error::invalid_length(values.len(), &self)),
+      }
+    }
+    self.finish(values)
+  }
+
+  fn visit_map<A>(self, mut map: A) -> Result<DynValue, A::Error>
+  where
+    A: MapAccess<'de>,
+  {
+    let unskipped = self
+      .unskipped()
+      .map(|(_, field)| field.type_info)
+      .collect::<Vec<_>>();
+    let mut values = unskipped.iter().map(|_| None).collect::<Vec<_>>();
+    while let Some(index) =
+      map.next_key_seed(FieldSeed { names: self.names })?
+    {
+      let Some(index) = index else {
+        map.next_value::<IgnoredAny>()?;
+        continue;
+      };
+      if values[index].is_some() {
+        return Err(A::Error::duplicate_field(self.names[index]));
+      }
+      values[index] =
+        Some(map.next_value_seed(TypeSeed::new(unskipped[index]))?);
+    }
+    self.finish(values)
+  }
+}
+
+/// Deserializes the name, or index, of a field into its index among the
+/// fields that aren't skipped. Unknown fields are ignored
+struct FieldSeed {
+  names: &'static [&'static str],
+}
+
+impl<'de> DeserializeSeed<'de> for FieldSeed {
+  type Value = Option<usize>;
+
+  fn deserialize<D>(self, deserializer: D) -> Result<Option<usize>, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    deserializer.deserialize_identifier(self)
+  }
+}
+
+impl Visitor<'_> for FieldSeed {
+  type Value = Option<usize>;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("a field name")
+  }
+
+  fn visit_u64<E>(self, index: u64) -> Result<Option<usize>, E> {
+    Ok(
+      usize::try_from(index)
+        .ok()
+        .filter(|&index| index < self.names.len()),
+    )
+  }
+
+  fn visit_str<E>(self, name: &str) -> Result<Option<usize>, E> {
+    Ok(self.names.iter().position(|field| *field == name))
+  }
+
+  fn visit_bytes<E>(self, name: &[u8]) -> Result<Option<usize>, E> {
+    Ok(
+      ::core::str::from_utf8(name)
+        .ok()
+        .and_then(|name| self.names.iter().position(|field| *field == name)),
+    )
+  }
+}
@@ -0,0 +1,18 @@
+//! Serialize and deserialize values through their [`TypeInfo`], with
+//! [`serde`]
+//!
+//! The value's type doesn't need to implement `Serialize` or `Deserialize`,
+//! only `#[derive(TypeInfo)]`. Values are serialized in the same shape as
+//! `#[derive(Serialize)]` would; fields marked `#[inspect(skip)]` are left
+//! out, and fields can be renamed with `#[inspect(rename = "name")]`.
+//!
+//! [`TypeInfo`]: enum@crate::TypeInfo
+//! [`serde`]: ::serde
+
+mod de;
+mod ser;
+
+pub use self::{
+  de::{ReflectedMut, TypeSeed},
+  ser::Reflected,
+};
@@ -0,0 +1,264 @@
+use {
+  crate::{
+    type_info::{
+      AnonymousFieldInfo, EnumVariantInfo, NamedFieldInfo, Pointer, Primitive,
+      Sequence, Std, Struct, Tuple, TypeInfo, TypeInfoProvider,
+    },
+    value::{DynValue, ValueRef},
+  },
+  ::serde::{
+    ser::{
+      Error as _, SerializeStruct, SerializeStructVariant, SerializeTuple,
+      SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Serialize, Serializer,
+  },
+};
+
+/// Serializes the value it wraps through its [`TypeInfo`]
+///
+/// ```rust
+/// # use ::inspect::{TypeInfo, serde::Reflected};
+/// #[derive(TypeInfo)]
+/// struct Login {
+///   user: String,
+///   #[inspect(skip)]
+///   password: String,
+///   #[inspect(rename = "remember-me")]
+///   remember_me: bool,
+/// }
+///
+/// let login = Login {
+///   user: "ickk".to_string(),
+///   password: "hunter2".to_string(),
+///   remember_me: true,
+/// };
+/// assert_eq!(
+///   serde_json::to_string(&Reflected(&login)).unwrap(),
+///   r#"{"user":"ickk","remember-me":true}"#,
+/// );
+/// ```
+pub struct Reflected<'a, T: ?Sized>(pub &'a T);
+
+impl<T> Serialize for Reflected<'_, T>
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    ValueRef::new(self.0).serialize(serializer)
+  }
+}
+
+impl Serialize for DynValue {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    self.as_value_ref().serialize(serializer)
+  }
+}
+
+impl Serialize for ValueRef<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let value = *self;
+    let name = value.type_info().bare_name();
+
+    match value.type_info() {
+      TypeInfo::Primitive(primitive) => unsafe {
+        match primitive {
+          Primitive::u8 => serializer.serialize_u8(*value.cast()),
+          Primitive::u16 => serializer.serialize_u16(*value.cast()),
+          Primitive::u32 => serializer.serialize_u32(*value.cast()),
+          Primitive::u64 => serializer.serialize_u64(*value.cast()),
+          Primitive::u128 => serializer.serialize_u128(*value.cast()),
+
+          Primitive::i8 => serializer.serialize_i8(*value.cast()),
+          Primitive::i16 => serializer.serialize_i16(*value.cast()),
+          Primitive::i32 => serializer.serialize_i32(*value.cast()),
+          Primitive::i64 => serializer.serialize_i64(*value.cast()),
+          Primitive::i128 => serializer.serialize_i128(*value.cast()),
+
+          Primitive::f32 => serializer.serialize_f32(*value.cast()),
+          Primitive::f64 => serializer.serialize_f64(*value.cast()),
+
+          Primitive::usize => {
+            serializer.serialize_u64(*value.cast::<usize>() as u64)
+          },
+          Primitive::isize => {
+            serializer.serialize_i64(*value.cast::<isize>() as i64)
+          },
+          Primitive::Unit => serializer.serialize_unit(),
+          Primitive::bool => serializer.serialize_bool(*value.cast()),
+          Primitive::char => serializer.serialize_char(*value.cast()),
+        }
+      },
+      TypeInfo::Sequence(Sequence::str) | TypeInfo::Std(Std::String) => {
+        serializer.serialize_str(value.as_str().unwrap())
+      },
+      TypeInfo::Sequence(Sequence::Slice { .. })
+      | TypeInfo::Std(Std::Vec { .. }) => {
+        serializer.collect_seq(value.items().unwrap())
+      },
+      TypeInfo::Sequence(Sequence::Array { .. }) => {
+        let items = value.items().unwrap();
+        let mut tuple = serializer.serialize_tuple(items.len())?;
+        for item in items {
+          tuple.serialize_element(&item)?;
+        }
+        tuple.end()
+      },
+      TypeInfo::Std(Std::HashMap { .. } | Std::BTreeMap { .. }) => {
+        serializer.collect_map(value.entries().unwrap())
+      },
+      TypeInfo::Std(Std::Option { .. }) => match value.option().unwrap() {
+        Some(some) => serializer.serialize_some(&some),
+        None => serializer.serialize_none(),
+      },
+      TypeInfo::Std(Std::Result { .. }) => match value.result().unwrap() {
+        Ok(ok) => serializer.serialize_newtype_variant("Result", 0, "Ok", &ok),
+        Err(err) => {
+          serializer.serialize_newtype_variant("Result", 1, "Err", &err)
+        },
+      },
+      TypeInfo::Std(Std::PhantomData { .. }) => {
+        serializer.serialize_unit_struct("PhantomData")
+      },
+      TypeInfo::Pointer(
+        Pointer::Ref { .. } | Pointer::RefMut { .. } | Pointer::Box { .. },
+      ) => value.target().unwrap().serialize(serializer),
+      TypeInfo::Pointer(Pointer::RawConst { .. } | Pointer::RawMut { .. }) => {
+        Err(S::Error::custom(format_args!(
+          "can't serialize the raw pointer `{}`",
+          value.type_info().type_name(),
+        )))
+      },
+      TypeInfo::Tuple(Tuple::Tuple { info, .. }) => {
+        let mut tuple = serializer.serialize_tuple(info.field_infos.len())?;
+        for field in info.field_infos {
+          tuple.serialize_element(&anonymous_field(value, field))?;
+        }
+        tuple.end()
+      },
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { .. } => serializer.serialize_unit_struct(name),
+        Struct::TupleStruct { fields, .. } => match fields.field_infos {
+          [field] if !field.attributes.skip => serializer
+            .serialize_newtype_struct(name, &anonymous_field(value, field)),
+          field_infos => {
+            let mut tuple = serializer
+              .serialize_tuple_struct(name, unskipped_len(field_infos))?;
+            for field in field_infos.iter().filter(|f| !f.attributes.skip) {
+              tuple.serialize_field(&anonymous_field(value, field))?;
+            }
+            tuple.end()
+          },
+        },
+        Struct::Struct { fields, .. } => {
+          let field_infos = fields.field_infos;
+          let mut structure = serializer
+            .serialize_struct(name, unskipped_named_len(field_infos))?;
+          for field in field_infos {
+            if field.attributes.skip {
+              structure.skip_field(field.serialized_name())?;
+            } else {
+              structure.serialize_field(
+                field.serialized_name(),
+                &named_field(value, field),
+              )?;
+            }
+          }
+          structure.end()
+        },
+      },
+      TypeInfo::Union(..) => Err(S::Error::custom(format_args!(
+        "can't serialize the union `{}`, as its active field isn't known",
+        value.type_info().type_name(),
+      ))),
+      TypeInfo::Enum(..) => {
+        let (index, variant) = value.variant().unwrap();
+        let index = index as u32;
+        match variant {
+          EnumVariantInfo::Unit { variant_name, .. } => {
+            serializer.serialize_unit_variant(name, index, variant_name)
+          },
+          EnumVariantInfo::Tuple {
+            variant_name,
+            field_infos,
+            ..
+          } => match field_infos {
+            [field] if !field.attributes.skip => serializer
+              .serialize_newtype_variant(
+                name,
+                index,
+                variant_name,
+                &anonymous_field(value, field),
+              ),
+            field_infos => {
+              let mut tuple = serializer.serialize_tuple_variant(
+                name,
+                index,
+                variant_name,
+                unskipped_len(field_infos),
+              )?;
+              for field in field_infos.iter().filter(|f| !f.attributes.skip) {
+                tuple.serialize_field(&anonymous_field(value, field))?;
+              }
+              tuple.end()
+            },
+          },
+          EnumVariantInfo::Struct {
+            variant_name,
+            field_infos,
+            ..
+          } => {
+            let mut structure = serializer.serialize_struct_variant(
+              name,
+              index,
+              variant_name,
+              unskipped_named_len(field_infos),
+            )?;
+            for field in field_infos.iter() {
+              if field.attributes.skip {
+                structure.skip_field(field.serialized_name())?;
+              } else {
+                structure.serialize_field(
+                  field.serialized_name(),
+                  &named_field(value, field),
+                )?;
+              }
+            }
+            structure.end()
+          },
+        }
+      },
+    }
+  }
+}
+
+fn named_field<'v>(
+  value: ValueRef<'v>,
+  field: &NamedFieldInfo,
+) -> ValueRef<'v> {
+  unsafe { value.field_at(field.field_offset, (field.type_info_fn)()) }
+}
+
+fn anonymous_field<'v>(
+  value: ValueRef<'v>,
+  field: &AnonymousFieldInfo,
+) -> ValueRef<'v> {
+  unsafe { value.field_at(field.field_offset, (field.type_info_fn)()) }
+}
+
+fn unskipped_len(field_infos: &[AnonymousFieldInfo]) -> usize {
+  field_infos.iter().filter(|f| !f.attributes.skip).count()
+}
+
+fn unskipped_named_len(field_infos: &[NamedFieldInfo]) -> usize {
+  field_infos.iter().filter(|f| !f.attributes.skip).count()
+}
@@ -0,0 +1,319 @@
+use super::{
+  AnonymousFieldInfo, Enum, EnumVariantInfo, FieldAttributes, NamedFieldInfo,
+  Pointer, Primitive, Sequence, Std, Struct, Tuple, TypeInfo, Union,
+};
+
+/// The kind of type a [`TypeInfo`] describes, without any of its details
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Kind {
+  Primitive,
+  Str,
+  String,
+  Slice,
+  Array,
+  Vec,
+  Option,
+  Result,
+  PhantomData,
+  HashMap,
+  BTreeMap,
+  Ref,
+  RefMut,
+  RawConst,
+  RawMut,
+  Box,
+  Tuple,
+  UnitStruct,
+  TupleStruct,
+  Struct,
+  Union,
+  Enum,
+}
+
+/// A field of a tuple, struct, union or enum variant, whether it has a name
+/// or not
+#[derive(Debug, Clone, Copy)]
+pub struct Field {
+  /// The position of the field in its declaration
+  pub index: usize,
+  /// The name of the field, or `None` for the fields of tuples, tuple
+  /// structs, and tuple variants
+  pub name: Option<&'static str>,
+  /// The offset of the field, in bytes
+  pub offset: usize,
+  pub type_info_fn: fn() -> &'static TypeInfo,
+  pub attributes: &'static FieldAttributes,
+}
+
+impl Field {
+  /// The [`TypeInfo`] of the field's type
+  pub fn type_info(&self) -> &'static TypeInfo {
+    (self.type_info_fn)()
+  }
+
+  /// The name of the field, or the name it was given with
+  /// `#[inspect(rename = "name")]`, or `None` if it doesn't have a name
+  pub fn serialized_name(&self) -> Option<&'static str> {
+    self.attributes.rename.or(self.name)
+  }
+
+  /// The name of the field, or its index if it doesn't have one
+  pub fn label(&self) -> String {
+    match self.name {
+      Some(name) => name.to_string(),
+      None => self.index.to_string(),
+    }
+  }
+
+  /// Find the field called `name`, or whose index it is if the fields don't
+  /// have names
+  pub(crate) fn find(
+    mut fields: impl Iterator<Item = Field>,
+    name: &str,
+  ) -> Option<Field> {
+    fields.find(|field| match field.name {
+      Some(field_name) => field_name == name,
+      None => name.parse() == Ok(field.index),
+    })
+  }
+
+  fn named(
+    field_infos: &'static [NamedFieldInfo],
+  ) -> impl Iterator<Item = Field> {
+    field_infos.iter().enumerate().map(|(index, field)| Field {
+      index,
+      name: Some(field.field_name),
+      offset: field.field_offset,
+      type_info_fn: field.type_info_fn,
+      attributes: &field.attributes,
+    })
+  }
+
+  fn anonymous(
+    field_infos: &'static [AnonymousFieldInfo],
+  ) -> impl Iterator<Item = Field> {
+    field_infos.iter().map(|field| Field {
+      index: field.field_index,
+      name: None,
+      offset: field.field_offset,
+      type_info_fn: field.type_info_fn,
+      attributes: &field.attributes,
+    })
+  }
+
+  fn of(
+    named: &'static [NamedFieldInfo],
+    anonymous: &'static [AnonymousFieldInfo],
+  ) -> impl Iterator<Item = Field> {
+    Field::named(named).chain(Field::anonymous(anonymous))
+  }
+}
+
+impl TypeInfo {
+  /// Get the [`Kind`] of the type
+  pub fn kind(&self) -> Kind {
+    match self {
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { .. } => Kind::Ref,
+        Pointer::RefMut { .. } => Kind::RefMut,
+        Pointer::RawConst { .. } => Kind::RawConst,
+        Pointer::RawMut { .. } => Kind::RawMut,
+        Pointer::Box { .. } => Kind::Box,
+      },
+      TypeInfo::Primitive(_) => Kind::Primitive,
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::str => Kind::Str,
+        Sequence::Slice { .. } => Kind::Slice,
+        Sequence::Array { .. } => Kind::Array,
+      },
+      TypeInfo::Std(std) => match std {
+        Std::String => Kind::String,
+        Std::Vec { .. } => Kind::Vec,
+        Std::Option { .. } => Kind::Option,
+        Std::Result { .. } => Kind::Result,
+        Std::PhantomData { .. } => Kind::PhantomData,
+        Std::HashMap { .. } => Kind::HashMap,
+        Std::BTreeMap { .. } => Kind::BTreeMap,
+      },
+      TypeInfo::Tuple(Tuple::Tuple { .. }) => Kind::Tuple,
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { .. } => Kind::UnitStruct,
+        Struct::TupleStruct { .. } => Kind::TupleStruct,
+        Struct::Struct { .. } => Kind::Struct,
+      },
+      TypeInfo::Union(Union::Union { .. }) => Kind::Union,
+      TypeInfo::Enum(Enum::Enum { .. }) => Kind::Enum,
+    }
+  }
+
+  /// Get the fields of the type, if it's a tuple, struct or union
+  ///
+  /// The fields of an enum belong to its [variants][TypeInfo::variants].
+  pub fn fields(&self) -> impl Iterator<Item = Field> {
+    let (named, anonymous): (&'static [_], &'static [_]) = match self {
+      TypeInfo::Tuple(Tuple::Tuple { info, .. }) => (&[], info.field_infos),
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { .. } => (&[], &[]),
+        Struct::TupleStruct { fields, .. } => (&[], fields.field_infos),
+        Struct::Struct { fields, .. } => (fields.field_infos, &[]),
+      },
+      TypeInfo::Union(Union::Union { fields, .. }) => {
+        (fields.field_infos, &[])
+      },
+      _ => (&[], &[]),
+    };
+    Field::of(named, anonymous)
+  }
+
+  /// Get the field with the given name, if the type is a struct or union
+  /// that has one
+  pub fn field(&self, name: &str) -> Option<Field> {
+    self.fields().find(|field| field.name == Some(name))
+  }
+
+  /// Get the variants of the type, if it's an enum
+  pub fn variants(&self) -> &'static [EnumVariantInfo] {
+    match self {
+      TypeInfo::Enum(Enum::Enum { variants, .. }) => variants.variant_infos,
+      _ => &[],
+    }
+  }
+
+  /// Get the variant with the given name, if the type is an enum that has
+  /// one
+  pub fn variant(&self, name: &str) -> Option<&'static EnumVariantInfo> {
+    self
+      .variants()
+      .iter()
+      .find(|variant| variant.name() == name)
+  }
+
+  /// Get the type of the items of a slice, array, `Vec`, `Option` or
+  /// `PhantomData`, or the type a pointer points to
+  pub fn item(&self) -> Option<&'static TypeInfo> {
+    let item = match self {
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { item, .. }
+        | Pointer::RefMut { item, .. }
+        | Pointer::RawConst { item, .. }
+        | Pointer::RawMut { item, .. }
+        | Pointer::Box { item, .. } => item,
+      },
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::str => return None,
+        Sequence::Slice { item, .. } | Sequence::Array { item, .. } => item,
+      },
+      TypeInfo::Std(std) => match std {
+        Std::Vec { item, .. }
+        | Std::Option { item, .. }
+        | Std::PhantomData { item, .. } => item,
+        Std::String
+        | Std::Result { .. }
+        | Std::HashMap { .. }
+        | Std::BTreeMap { .. } => return None,
+      },
+      _ => return None,
+    };
+    Some((item.type_info_fn)())
+  }
+
+  /// Get the types a built-in generic type was instantiated with: the item
+  /// of a pointer, slice, array, `Vec`, `Option` or `PhantomData`, the `Ok`
+  /// and `Err` of a `Result`, the key and value of a map, or the fields of a
+  /// tuple
+  ///
+  /// Derived types don't record their generic arguments, so they have none.
+  pub fn generic_args(&self) -> Vec<&'static TypeInfo> {
+    match self {
+      TypeInfo::Std(Std::Result { info, .. }) => {
+        vec![(info.ok_type_info_fn)(), (info.err_type_info_fn)()]
+      },
+      TypeInfo::Std(
+        Std::HashMap { info, .. } | Std::BTreeMap { info, .. },
+      ) => {
+        vec![(info.key_type_info_fn)(), (info.value_type_info_fn)()]
+      },
+      TypeInfo::Tuple(_) => {
+        self.fields().map(|field| field.type_info()).collect()
+      },
+      _ => self.item().into_iter().collect(),
+    }
+  }
+
+  /// Get the [`Primitive`], if the type is one
+  pub fn as_primitive(&self) -> Option<&Primitive> {
+    match self {
+      TypeInfo::Primitive(primitive) => Some(primitive),
+      _ => None,
+    }
+  }
+
+  /// Get the [`Pointer`], if the type is one
+  pub fn as_pointer(&self) -> Option<&Pointer> {
+    match self {
+      TypeInfo::Pointer(pointer) => Some(pointer),
+      _ => None,
+    }
+  }
+
+  /// Get the [`Sequence`], if the type is one
+  pub fn as_sequence(&self) -> Option<&Sequence> {
+    match self {
+      TypeInfo::Sequence(sequence) => Some(sequence),
+      _ => None,
+    }
+  }
+
+  /// Get the [`Std`] type, if the type is one
+  pub fn as_std(&self) -> Option<&Std> {
+    match self {
+      TypeInfo::Std(std) => Some(std),
+      _ => None,
+    }
+  }
+
+  /// Get the [`Tuple`], if the type is one
+  pub fn as_tuple(&self) -> Option<&Tuple> {
+    match self {
+      TypeInfo::Tuple(tuple) => Some(tuple),
+      _ => None,
+    }
+  }
+
+  /// Get the [`Struct`], if the type is one
+  pub fn as_struct(&self) -> Option<&Struct> {
+    match self {
+      TypeInfo::Struct(structure) => Some(structure),
+      _ => None,
+    }
+  }
+
+  /// Get the [`Union`], if the type is one
+  pub fn as_union(&self) -> Option<&Union> {
+    match self {
+      TypeInfo::Union(union) => Some(union),
+      _ => None,
+    }
+  }
+
+  /// Get the [`Enum`], if the type is one
+  pub fn as_enum(&self) -> Option<&Enum> {
+    match self {
+      TypeInfo::Enum(enumeration) => Some(enumeration),
+      _ => None,
+    }
+  }
+}
+
+impl EnumVariantInfo {
+  /// Get the fields of the variant
+  pub fn fields(&self) -> impl Iterator<Item = Field> {
+    let (named, anonymous): (&'static [_], &'static [_]) = match self {
+      EnumVariantInfo::Unit { .. } => (&[], &[]),
+      EnumVariantInfo::Tuple { field_infos, .. } => (&[], field_infos),
+      EnumVariantInfo::Struct { field_infos, .. } => (field_infos, &[]),
+    };
+    Field::of(named, anonymous)
+  }
+}
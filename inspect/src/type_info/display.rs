@@ -1,7 +1,7 @@
 use {
   super::{
     Enum, EnumInfo, EnumVariantInfo, Pointer, Sequence, Std, Struct, Tuple,
-    TypeInfo,
+    TypeInfo, Union,
   },
   ::core::fmt,
 };
@@ -169,6 +169,27 @@ impl fmt::Display for TypeInfo {
           }
         },
         Std::PhantomData { .. } => f.write_fmt(format_args!("{short_name}")),
+        Std::HashMap { info, .. } | Std::BTreeMap { info, .. } => {
+          let name = short_name.to_string();
+          let name = name.split('<').next().unwrap_or(&name);
+          if alternate {
+            f.write_fmt(format_args!(
+              "{name}<{:#.*}, {:#.*}>",
+              precision - 1,
+              (info.key_type_info_fn)(),
+              precision - 1,
+              (info.value_type_info_fn)(),
+            ))
+          } else {
+            f.write_fmt(format_args!(
+              "{name}<{:.*}, {:.*}>",
+              precision - 1,
+              (info.key_type_info_fn)(),
+              precision - 1,
+              (info.value_type_info_fn)(),
+            ))
+          }
+        },
         Std::Result { info, .. } => {
           if alternate {
             f.write_fmt(format_args!(
@@ -251,8 +272,26 @@ impl fmt::Display for TypeInfo {
           set.finish()
         },
       },
+      TypeInfo::Union(Union::Union { fields, .. }) => {
+        f.write_fmt(format_args!("union {short_name}"))?;
+        let mut set = f.debug_struct("");
+        for field in fields.field_infos {
+          if alternate {
+            set.field(
+              field.field_name,
+              &format_args!("{:#.*}", precision, (field.type_info_fn)()),
+            );
+          } else {
+            set.field(
+              field.field_name,
+              &format_args!("{:.*}", precision, (field.type_info_fn)()),
+            );
+          }
+        }
+        set.finish()
+      },
       TypeInfo::Enum(Enum::Enum {
-        variants: EnumInfo { variant_infos },
+        variants: EnumInfo { variant_infos, .. },
         ..
       }) => {
         struct DisplayVariant<'v>(&'v EnumVariantInfo);
@@ -0,0 +1,251 @@
+use {
+  super::{
+    AnonymousFieldInfo, Enum, EnumVariantInfo, NamedFieldInfo, Pointer,
+    Sequence, Std, Struct, Tuple, TypeInfo, Union,
+  },
+  ::core::any::TypeId,
+  ::std::collections::HashMap,
+};
+
+impl TypeInfo {
+  /// Get a 128-bit hash of the structure of the type: the names of structs,
+  /// unions and enums, their fields and variants, the types of the fields,
+  /// and the layout of every type, recursively
+  ///
+  /// Unlike [`TypeId`], it's the same in every build of a program, as long
+  /// as the types and the target don't change, so it can be stored with
+  /// data to check that it's read back as the same type. Paths aren't part
+  /// of it, so moving a type to another module doesn't change it.
+  ///
+  /// ```rust
+  /// # use ::inspect::TypeInfo;
+  /// #[derive(TypeInfo)]
+  /// struct Point {
+  ///   x: i32,
+  ///   y: i32,
+  /// }
+  ///
+  /// mod other {
+  ///   #[derive(::inspect::TypeInfo)]
+  ///   pub struct Point {
+  ///     pub x: i32,
+  ///     pub z: i32,
+  ///   }
+  /// }
+  ///
+  /// assert_eq!(
+  ///   TypeInfo::of::<Point>().fingerprint(),
+  ///   TypeInfo::of::<Point>().fingerprint(),
+  /// );
+  /// assert_ne!(
+  ///   TypeInfo::of::<Point>().fingerprint(),
+  ///   TypeInfo::of::<other::Point>().fingerprint(),
+  /// );
+  /// ```
+  pub fn fingerprint(&self) -> u128 {
+    let mut hasher = Fingerprinter::default();
+    hasher.type_info(self);
+    hasher.state
+  }
+}
+
+/// A 128-bit FNV-1a hash of the types it's given, which is defined
+/// independently of the standard library's hashers so that it never changes
+struct Fingerprinter {
+  state: u128,
+  /// The order types were first seen in, to refer back to them when they're
+  /// seen again
+  seen: HashMap<TypeId, usize>,
+}
+
+impl Default for Fingerprinter {
+  fn default() -> Self {
+    Fingerprinter {
+      state: 0x6c62272e07bb014262b821756295c58d,
+      seen: HashMap::new(),
+    }
+  }
+}
+
+impl Fingerprinter {
+  const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+  fn bytes(&mut self, bytes: &[u8]) {
+    for &byte in bytes {
+      self.state ^= byte as u128;
+      self.state = self.state.wrapping_mul(Fingerprinter::PRIME);
+    }
+  }
+
+  fn usize(&mut self, value: usize) {
+    self.bytes(&(value as u64).to_le_bytes());
+  }
+
+  /// Strings are prefixed with their length, so that they can't run into
+  /// each other
+  fn str(&mut self, value: &str) {
+    self.usize(value.len());
+    self.bytes(value.as_bytes());
+  }
+
+  fn type_info(&mut self, type_info: &TypeInfo) {
+    let next = self.seen.len();
+    if let Some(&index) = self.seen.get(&type_info.type_id()) {
+      // recursive types refer back to themselves
+      self.str("seen");
+      self.usize(index);
+      return;
+    }
+    self.seen.insert(type_info.type_id(), next);
+
+    match type_info.layout() {
+      Some(layout) => {
+        self.str("sized");
+        self.usize(layout.size());
+        self.usize(layout.align());
+      },
+      None => self.str("unsized"),
+    }
+
+    let item =
+      |hasher: &mut Self, tag, type_info_fn: fn() -> &'static TypeInfo| {
+        hasher.str(tag);
+        hasher.type_info(type_info_fn());
+      };
+    match type_info {
+      TypeInfo::Primitive(primitive) => self.str(&format!("{primitive:?}")),
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::str => self.str("str"),
+        Sequence::Slice { item: info, .. } => {
+          item(self, "slice", info.type_info_fn)
+        },
+        Sequence::Array {
+          item: info,
+          info: array,
+          ..
+        } => {
+          item(self, "array", info.type_info_fn);
+          self.usize(array.array_length);
+        },
+      },
+      TypeInfo::Std(std) => match std {
+        Std::String => self.str("String"),
+        Std::Vec { item: info, .. } => item(self, "Vec", info.type_info_fn),
+        Std::Option { item: info, .. } => {
+          item(self, "Option", info.type_info_fn)
+        },
+        Std::Result { info, .. } => {
+          item(self, "Result", info.ok_type_info_fn);
+          self.type_info((info.err_type_info_fn)());
+        },
+        Std::PhantomData { item: info, .. } => {
+          item(self, "PhantomData", info.type_info_fn)
+        },
+        Std::HashMap { info, .. } => {
+          item(self, "HashMap", info.key_type_info_fn);
+          self.type_info((info.value_type_info_fn)());
+        },
+        Std::BTreeMap { info, .. } => {
+          item(self, "BTreeMap", info.key_type_info_fn);
+          self.type_info((info.value_type_info_fn)());
+        },
+      },
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { item: info, .. } => item(self, "&", info.type_info_fn),
+        Pointer::RefMut { item: info, .. } => {
+          item(self, "&mut", info.type_info_fn)
+        },
+        Pointer::RawConst { item: info, .. } => {
+          item(self, "*const", info.type_info_fn)
+        },
+        Pointer::RawMut { item: info, .. } => {
+          item(self, "*mut", info.type_info_fn)
+        },
+        Pointer::Box { item: info, .. } => {
+          item(self, "Box", info.type_info_fn)
+        },
+      },
+      TypeInfo::Tuple(Tuple::Tuple { info, .. }) => {
+        self.str("tuple");
+        self.anonymous_fields(info.field_infos);
+      },
+      TypeInfo::Struct(structure) => {
+        self.str("struct");
+        self.str(type_info.bare_name());
+        match structure {
+          Struct::UnitStruct { .. } => self.str("unit"),
+          Struct::TupleStruct { fields, .. } => {
+            self.str("tuple");
+            self.anonymous_fields(fields.field_infos);
+          },
+          Struct::Struct { fields, .. } => {
+            self.str("named");
+            self.named_fields(fields.field_infos);
+          },
+        }
+      },
+      TypeInfo::Union(Union::Union { fields, .. }) => {
+        self.str("union");
+        self.str(type_info.bare_name());
+        self.named_fields(fields.field_infos);
+      },
+      TypeInfo::Enum(Enum::Enum { variants, .. }) => {
+        self.str("enum");
+        self.str(type_info.bare_name());
+        self.usize(variants.variant_infos.len());
+        for variant in variants.variant_infos {
+          self.str(variant.name());
+          let value = match variant {
+            EnumVariantInfo::Unit {
+              variant_discriminant_value,
+              ..
+            }
+            | EnumVariantInfo::Tuple {
+              variant_discriminant_value,
+              ..
+            }
+            | EnumVariantInfo::Struct {
+              variant_discriminant_value,
+              ..
+            } => variant_discriminant_value,
+          };
+          if let Some(value) = value {
+            self.str("discriminant");
+            self.usize(*value);
+          }
+          match variant {
+            EnumVariantInfo::Unit { .. } => self.str("unit"),
+            EnumVariantInfo::Tuple { field_infos, .. } => {
+              self.str("tuple");
+              self.anonymous_fields(field_infos);
+            },
+            EnumVariantInfo::Struct { field_infos, .. } => {
+              self.str("named");
+              self.named_fields(field_infos);
+            },
+          }
+        }
+      },
+    }
+  }
+
+  fn named_fields(&mut self, field_infos: &'static [NamedFieldInfo]) {
+    self.usize(field_infos.len());
+    for field in field_infos {
+      self.str(field.field_name);
+      self.str(field.serialized_name());
+      self.usize(field.field_offset);
+      self.usize(field.attributes.skip as usize);
+      self.type_info((field.type_info_fn)());
+    }
+  }
+
+  fn anonymous_fields(&mut self, field_infos: &'static [AnonymousFieldInfo]) {
+    self.usize(field_infos.len());
+    for field in field_infos {
+      self.usize(field.field_offset);
+      self.usize(field.attributes.skip as usize);
+      self.type_info((field.type_info_fn)());
+    }
+  }
+}
@@ -1,16 +1,24 @@
 use {
   crate::type_info::{
-    internal::{ConcurrentMap, Provider, ProviderOfTypeInfo},
-    AnonymousFieldInfo, IdInfo, ItemInfo, Pointer, Primitive, ResultInfo,
-    Sequence, SizedInfo, Std, Tuple, TupleInfo, TypeInfo,
+    internal::{
+      default_erased, drop_erased, ConcurrentMap, Provider, ProviderOfTypeInfo,
+    },
+    AnonymousFieldInfo, ArrayInfo, FieldAttributes, IdInfo, ItemInfo, MapInfo,
+    OptionInfo, Pointer, PointerInfo, Primitive, ResultInfo, Sequence,
+    SizedInfo, Std, Tuple, TupleInfo, TypeInfo, VecInfo,
   },
   ::core::{
     any::{type_name, TypeId},
+    hash::Hash,
     marker::PhantomData,
     mem::{align_of, offset_of, size_of},
     option::Option,
+    ptr,
+  },
+  ::std::{
+    collections::{BTreeMap, HashMap},
+    vec::Vec,
   },
-  ::std::vec::Vec,
 };
 
 macro_rules! impl_type_info_trivial {
@@ -19,16 +27,6 @@ macro_rules! impl_type_info_trivial {
       type StaticTy = $ty;
       type StaticTySized = $ty;
 
-      fn type_info() -> &'static TypeInfo {
-        &$variant
-      }
-    }
-  };
-  (unsized $ty:ty as $variant:expr) => {
-    unsafe impl ProviderOfTypeInfo<$ty> for Provider<$ty> {
-      type StaticTy = $ty;
-      type StaticTySized = ();
-
       fn type_info() -> &'static TypeInfo {
         &$variant
       }
@@ -57,7 +55,18 @@ impl_type_info_trivial! { () as TypeInfo::Primitive(Primitive::Unit) }
 impl_type_info_trivial! { bool as TypeInfo::Primitive(Primitive::bool) }
 impl_type_info_trivial! { char as TypeInfo::Primitive(Primitive::char) }
 
-impl_type_info_trivial! { unsized str as TypeInfo::Sequence(Sequence::str) }
+unsafe impl ProviderOfTypeInfo<str> for Provider<str> {
+  type StaticTy = str;
+  type StaticTySized = ();
+
+  fn type_info() -> &'static TypeInfo {
+    &TypeInfo::Sequence(Sequence::str)
+  }
+
+  fn len_of(ptr: *const str) -> usize {
+    (ptr as *const [u8]).len()
+  }
+}
 impl_type_info_trivial! { String as TypeInfo::Std(Std::String) }
 
 macro_rules! impl_type_info_sized_with_item {
@@ -65,6 +74,8 @@ macro_rules! impl_type_info_sized_with_item {
     <$($lifetime:lifetime,)? $item:ident> $ty:ty as
     $outer:ident::$outer_variant:ident($inner:ident::$inner_variant:ident);
     type StaticTy = $static_ty:ty;
+    default_fn = $default_fn:expr;
+    $(info = $info:expr;)?
   ) => {
     unsafe impl<$($lifetime,)? $item> ProviderOfTypeInfo<$ty> for Provider<$ty>
     where
@@ -88,10 +99,13 @@ macro_rules! impl_type_info_sized_with_item {
             sized: SizedInfo {
               size: size_of::<$ty>(),
               align: align_of::<$ty>(),
+              drop_fn: drop_erased::<$ty>,
+              default_fn: $default_fn,
             },
             item: ItemInfo {
               type_info_fn: Provider::<$item>::type_info,
             },
+            $(info: $info,)?
           });
           Box::leak(Box::new(info))
         })
@@ -103,10 +117,46 @@ macro_rules! impl_type_info_sized_with_item {
 impl_type_info_sized_with_item! {
   <T> Vec<T> as TypeInfo::Std(Std::Vec);
   type StaticTy = Vec<<Provider<T> as ProviderOfTypeInfo<T>>::StaticTySized>;
+  default_fn = Some(default_erased::<Vec<T>>);
+  info = VecInfo {
+    as_slice_fn: |vec| {
+      let vec = unsafe { &*vec.cast::<Vec<T>>() };
+      (vec.as_ptr().cast(), vec.len())
+    },
+    as_mut_slice_fn: |vec| {
+      let vec = unsafe { &mut *vec.cast::<Vec<T>>() };
+      (vec.as_mut_ptr().cast(), vec.len())
+    },
+    capacity_fn: |vec| unsafe { &*vec.cast::<Vec<T>>() }.capacity(),
+    push_fn: |vec, item| {
+      let vec = unsafe { &mut *vec.cast::<Vec<T>>() };
+      vec.push(unsafe { item.cast::<T>().read() });
+    },
+    insert_fn: |vec, index, item| {
+      let vec = unsafe { &mut *vec.cast::<Vec<T>>() };
+      vec.insert(index, unsafe { item.cast::<T>().read() });
+    },
+    remove_fn: |vec, index| {
+      let vec = unsafe { &mut *vec.cast::<Vec<T>>() };
+      drop(vec.remove(index));
+    },
+  };
 }
 impl_type_info_sized_with_item! {
   <T> Option<T> as TypeInfo::Std(Std::Option);
   type StaticTy = Option<<Provider<T> as ProviderOfTypeInfo<T>>::StaticTySized>;
+  default_fn = Some(default_erased::<Option<T>>);
+  info = OptionInfo {
+    as_option_fn: |option| {
+      let option = unsafe { &*option.cast::<Option<T>>() };
+      option.as_ref().map(|some| ptr::from_ref(some).cast())
+    },
+    some_fn: |option, some| unsafe {
+      option
+        .cast::<Option<T>>()
+        .write(Some(some.cast::<T>().read()));
+    },
+  };
 }
 
 macro_rules! impl_type_info_sized_with_unsized_item {
@@ -114,6 +164,8 @@ macro_rules! impl_type_info_sized_with_unsized_item {
     <$($lifetime:lifetime,)? $item:ident> $ty:ty as
     $outer:ident::$outer_variant:ident($inner:ident::$inner_variant:ident);
     type StaticTy = $static_ty:ty;
+    default_fn = $default_fn:expr;
+    $(info = $info:expr;)?
   ) => {
     unsafe impl<$($lifetime,)? $item> ProviderOfTypeInfo<$ty> for Provider<$ty>
     where
@@ -137,10 +189,13 @@ macro_rules! impl_type_info_sized_with_unsized_item {
             sized: SizedInfo {
               size: size_of::<$ty>(),
               align: align_of::<$ty>(),
+              drop_fn: drop_erased::<$ty>,
+              default_fn: $default_fn,
             },
             item: ItemInfo {
               type_info_fn: Provider::<$item>::type_info,
             },
+            $(info: $info,)?
           });
           Box::leak(Box::new(info))
         })
@@ -152,27 +207,65 @@ macro_rules! impl_type_info_sized_with_unsized_item {
 impl_type_info_sized_with_unsized_item! {
   <T> *const T as TypeInfo::Pointer(Pointer::RawConst);
   type StaticTy = *const <Provider<T> as ProviderOfTypeInfo<T>>::StaticTy;
+  default_fn = None;
+  info = PointerInfo {
+    target_fn: |pointer| target_parts(unsafe { *pointer.cast::<*const T>() }),
+  };
 }
 impl_type_info_sized_with_unsized_item! {
   <T> *mut T as TypeInfo::Pointer(Pointer::RawMut);
   type StaticTy = *mut <Provider<T> as ProviderOfTypeInfo<T>>::StaticTy;
+  default_fn = None;
+  info = PointerInfo {
+    target_fn: |pointer| {
+      target_parts(unsafe { *pointer.cast::<*mut T>() }.cast_const())
+    },
+  };
 }
 impl_type_info_sized_with_unsized_item! {
   <T> Box<T> as TypeInfo::Pointer(Pointer::Box);
   type StaticTy = Box<<Provider<T> as ProviderOfTypeInfo<T>>::StaticTy>;
+  default_fn = None;
+  info = PointerInfo {
+    target_fn: |pointer| {
+      target_parts(ptr::from_ref::<T>(unsafe { &**pointer.cast::<Box<T>>() }))
+    },
+  };
 }
 impl_type_info_sized_with_unsized_item! {
   <'s, T> &'s T as TypeInfo::Pointer(Pointer::Ref);
   type StaticTy = &'static <Provider<T> as ProviderOfTypeInfo<T>>::StaticTy;
+  default_fn = None;
+  info = PointerInfo {
+    target_fn: |pointer| {
+      target_parts(ptr::from_ref::<T>(unsafe { *pointer.cast::<&T>() }))
+    },
+  };
 }
 impl_type_info_sized_with_unsized_item! {
   <'s, T> &'s mut T as TypeInfo::Pointer(Pointer::RefMut);
   type StaticTy = &'static mut <Provider<T> as ProviderOfTypeInfo<T>>::StaticTy;
+  default_fn = None;
+  info = PointerInfo {
+    target_fn: |pointer| {
+      target_parts(ptr::from_ref::<T>(unsafe { &**pointer.cast::<&mut T>() }))
+    },
+  };
+}
+
+/// Splits a pointer into its address and the length held in its metadata
+fn target_parts<T>(target: *const T) -> (*const u8, usize)
+where
+  T: ?Sized,
+  Provider<T>: ProviderOfTypeInfo<T>,
+{
+  (target.cast(), Provider::<T>::len_of(target))
 }
 
 impl_type_info_sized_with_unsized_item! {
   <T> PhantomData<T> as TypeInfo::Std(Std::PhantomData);
   type StaticTy = PhantomData<<Provider<T> as ProviderOfTypeInfo<T>>::StaticTy>;
+  default_fn = Some(default_erased::<PhantomData<T>>);
 }
 
 macro_rules! impl_type_info_unsized_with_item {
@@ -188,6 +281,10 @@ macro_rules! impl_type_info_unsized_with_item {
       type StaticTy = $static_ty;
       type StaticTySized = ();
 
+      fn len_of(ptr: *const $ty) -> usize {
+        ptr.len()
+      }
+
       fn type_info() -> &'static TypeInfo {
         static DICTIONARY: ConcurrentMap<TypeId, &'static TypeInfo> =
           ConcurrentMap::new();
@@ -215,6 +312,41 @@ impl_type_info_unsized_with_item! {
   type StaticTy = [<Provider<T> as ProviderOfTypeInfo<T>>::StaticTySized];
 }
 
+unsafe impl<T, const N: usize> ProviderOfTypeInfo<[T; N]> for Provider<[T; N]>
+where
+  T: Sized,
+  Provider<T>: ProviderOfTypeInfo<T>,
+{
+  type StaticTy = [<Provider<T> as ProviderOfTypeInfo<T>>::StaticTySized; N];
+  type StaticTySized = Self::StaticTy;
+
+  fn type_info() -> &'static TypeInfo {
+    static DICTIONARY: ConcurrentMap<TypeId, &'static TypeInfo> =
+      ConcurrentMap::new();
+
+    let type_id = TypeId::of::<Self::StaticTy>();
+    DICTIONARY.get_or_insert_with(type_id, || {
+      let info = TypeInfo::Sequence(Sequence::Array {
+        id: IdInfo {
+          type_id,
+          type_name: type_name::<[T; N]>(),
+        },
+        sized: SizedInfo {
+          size: size_of::<[T; N]>(),
+          align: align_of::<[T; N]>(),
+          drop_fn: drop_erased::<[T; N]>,
+          default_fn: None,
+        },
+        item: ItemInfo {
+          type_info_fn: Provider::<T>::type_info,
+        },
+        info: ArrayInfo { array_length: N },
+      });
+      Box::leak(Box::new(info))
+    })
+  }
+}
+
 unsafe impl<O, E> ProviderOfTypeInfo<Result<O, E>> for Provider<Result<O, E>>
 where
   O: Sized,
@@ -245,10 +377,28 @@ where
         sized: SizedInfo {
           size: size_of::<Result<O, E>>(),
           align: align_of::<Result<O, E>>(),
+          drop_fn: drop_erased::<Result<O, E>>,
+          default_fn: None,
         },
         info: ResultInfo {
           ok_type_info_fn: Provider::<O>::type_info,
           err_type_info_fn: Provider::<E>::type_info,
+          as_result_fn: |result| match unsafe {
+            &*result.cast::<Result<O, E>>()
+          } {
+            Ok(ok) => Ok(ptr::from_ref(ok).cast()),
+            Err(err) => Err(ptr::from_ref(err).cast()),
+          },
+          ok_fn: |result, ok| unsafe {
+            result
+              .cast::<Result<O, E>>()
+              .write(Ok(ok.cast::<O>().read()));
+          },
+          err_fn: |result, err| unsafe {
+            result
+              .cast::<Result<O, E>>()
+              .write(Err(err.cast::<E>().read()));
+          },
         },
       });
       Box::leak(Box::new(info))
@@ -256,6 +406,65 @@ where
   }
 }
 
+macro_rules! impl_type_info_map {
+  ($map:ident as Std::$variant:ident where K: $($bound:path),+) => {
+    unsafe impl<K, V> ProviderOfTypeInfo<$map<K, V>> for Provider<$map<K, V>>
+    where
+      K: $($bound +)+ Sized,
+      V: Sized,
+      Provider<K>: ProviderOfTypeInfo<K>,
+      Provider<V>: ProviderOfTypeInfo<V>,
+    {
+      type StaticTy = $map<
+        <Provider<K> as ProviderOfTypeInfo<K>>::StaticTySized,
+        <Provider<V> as ProviderOfTypeInfo<V>>::StaticTySized,
+      >;
+      type StaticTySized = Self::StaticTy;
+
+      fn type_info() -> &'static TypeInfo {
+        static DICTIONARY: ConcurrentMap<TypeId, &'static TypeInfo> =
+          ConcurrentMap::new();
+
+        let type_id = TypeId::of::<Self::StaticTy>();
+        DICTIONARY.get_or_insert_with(type_id, || {
+          let info = TypeInfo::Std(Std::$variant {
+            id: IdInfo {
+              type_id,
+              type_name: type_name::<$map<K, V>>(),
+            },
+            sized: SizedInfo {
+              size: size_of::<$map<K, V>>(),
+              align: align_of::<$map<K, V>>(),
+              drop_fn: drop_erased::<$map<K, V>>,
+              default_fn: Some(default_erased::<$map<K, V>>),
+            },
+            info: MapInfo {
+              key_type_info_fn: Provider::<K>::type_info,
+              value_type_info_fn: Provider::<V>::type_info,
+              len_fn: |map| unsafe { &*map.cast::<$map<K, V>>() }.len(),
+              for_each_fn: |map, f| {
+                for (key, value) in unsafe { &*map.cast::<$map<K, V>>() } {
+                  f(ptr::from_ref(key).cast(), ptr::from_ref(value).cast());
+                }
+              },
+              insert_fn: |map, key, value| {
+                let map = unsafe { &mut *map.cast::<$map<K, V>>() };
+                let (key, value) =
+                  unsafe { (key.cast::<K>().read(), value.cast::<V>().read()) };
+                map.insert(key, value);
+              },
+            },
+          });
+          Box::leak(Box::new(info))
+        })
+      }
+    }
+  };
+}
+
+impl_type_info_map! { HashMap as Std::HashMap where K: Eq, Hash }
+impl_type_info_map! { BTreeMap as Std::BTreeMap where K: Ord }
+
 // does not support tuples with unsized last element - this is probably not
 // possible without specialisation, or giving up `SizedInfo`. Maybe it's
 // possible to abuse existing specialisation such as array-specialisation to
@@ -295,6 +504,7 @@ macro_rules! impl_type_info_tuple {
                 field_index: $index,
                 field_offset: offset_of!(Self::StaticTy, $index),
                 type_info_fn: Provider::<$generic>::type_info,
+                attributes: FieldAttributes::NONE,
               },
             )+]
             .into_boxed_slice(),
@@ -308,6 +518,8 @@ macro_rules! impl_type_info_tuple {
             sized: SizedInfo {
               size: size_of::<($($generic,)+)>(),
               align: align_of::<($($generic,)+)>(),
+              drop_fn: drop_erased::<($($generic,)+)>,
+              default_fn: None,
             },
             info: TupleInfo { field_infos },
           });
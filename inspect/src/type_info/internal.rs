@@ -1,6 +1,6 @@
 use {
   super::{discriminant_erased::DiscriminantErased, TypeInfo},
-  ::core::{hash::Hash, marker::PhantomData, mem::Discriminant},
+  ::core::{hash::Hash, marker::PhantomData, mem::Discriminant, ptr},
   ::parking_lot::RwLock,
   ::std::{collections::HashMap, sync::LazyLock},
 };
@@ -60,6 +60,12 @@ pub unsafe trait ProviderOfTypeInfo<Ty: ?Sized> {
   type StaticTySized: Sized + 'static;
 
   fn type_info() -> &'static TypeInfo;
+
+  /// The length held in the metadata of a pointer to `Ty`, if `Ty` is a
+  /// `str` or slice. Sized types have no metadata, and so return `0`.
+  fn len_of(_ptr: *const Ty) -> usize {
+    0
+  }
 }
 
 // lol, this garbage automatically associates `Provider<T>` to `T`, so that
@@ -103,8 +109,66 @@ where
   }
 }
 
+/// Drops the `T` behind the pointer; for use as [`SizedInfo::drop_fn`]
+///
+/// # Safety
+///
+/// See [`ptr::drop_in_place`]
+///
+/// [`SizedInfo::drop_fn`]: super::SizedInfo::drop_fn
+pub unsafe fn drop_erased<T>(ptr: *mut u8) {
+  unsafe { ptr::drop_in_place(ptr.cast::<T>()) }
+}
+
+/// Writes `T::default()` to the uninitialised memory behind the pointer; for
+/// use as [`SizedInfo::default_fn`]
+///
+/// # Safety
+///
+/// See [`ptr::write`]
+///
+/// [`SizedInfo::default_fn`]: super::SizedInfo::default_fn
+pub unsafe fn default_erased<T: Default>(ptr: *mut u8) {
+  unsafe { ptr.cast::<T>().write(T::default()) }
+}
+
 pub fn leak_erase_discriminant<T: 'static>(
   concrete: Discriminant<T>,
 ) -> DiscriminantErased {
   DiscriminantErased::leak_erase_discriminant(concrete)
 }
+
+/// The integer types that can be given as an enum's `#[repr(..)]`
+pub trait DiscriminantInt: Copy {
+  /// The value of the discriminant, with negative values wrapping around
+  fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_discriminant_int {
+  ($($ty:ty),*) => {
+    $(
+      impl DiscriminantInt for $ty {
+        fn to_usize(self) -> usize {
+          self as usize
+        }
+      }
+    )*
+  };
+}
+
+impl_discriminant_int! {
+  u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+}
+
+/// Reads the discriminant of an enum with a primitive representation; for use
+/// as [`EnumVariantInfo`]'s `variant_discriminant_value`
+///
+/// # Safety
+///
+/// `T` must be an enum with `#[repr(I)]`, which guarantees that its
+/// discriminant is stored as an `I` at the start of the value
+///
+/// [`EnumVariantInfo`]: super::EnumVariantInfo
+pub unsafe fn discriminant_value<I: DiscriminantInt, T>(value: &T) -> usize {
+  unsafe { ptr::from_ref(value).cast::<I>().read() }.to_usize()
+}
@@ -1,6 +1,6 @@
 use super::{
-  DiscriminantErased, Enum, EnumVariantInfo, Pointer, Primitive, Sequence,
-  Std, Struct, Tuple, TypeInfo,
+  DiscriminantErased, Enum, EnumVariantInfo, Pointer, Primitive, Repr,
+  Sequence, Std, Struct, Tuple, TypeInfo, Union,
 };
 
 impl TypeInfo {
@@ -49,7 +49,9 @@ impl TypeInfo {
         Std::Vec { id, .. }
         | Std::Option { id, .. }
         | Std::Result { id, .. }
-        | Std::PhantomData { id, .. } => id.type_id,
+        | Std::PhantomData { id, .. }
+        | Std::HashMap { id, .. }
+        | Std::BTreeMap { id, .. } => id.type_id,
       },
       TypeInfo::Tuple(Tuple::Tuple { id, .. }) => id.type_id,
       TypeInfo::Struct(structure) => match structure {
@@ -57,6 +59,7 @@ impl TypeInfo {
         | Struct::TupleStruct { id, .. }
         | Struct::Struct { id, .. } => id.type_id,
       },
+      TypeInfo::Union(Union::Union { id, .. }) => id.type_id,
       TypeInfo::Enum(enumeration) => match enumeration {
         Enum::Enum { id, .. } => id.type_id,
       },
@@ -111,19 +114,75 @@ impl TypeInfo {
         Std::Vec { id, .. }
         | Std::Option { id, .. }
         | Std::Result { id, .. }
-        | Std::PhantomData { id, .. } => id.type_name,
+        | Std::PhantomData { id, .. }
+        | Std::HashMap { id, .. }
+        | Std::BTreeMap { id, .. } => id.type_name,
       },
       TypeInfo::Struct(structure) => match structure {
         Struct::UnitStruct { id, .. }
         | Struct::TupleStruct { id, .. }
         | Struct::Struct { id, .. } => id.type_name,
       },
+      TypeInfo::Union(Union::Union { id, .. }) => id.type_name,
       TypeInfo::Enum(enumeration) => match enumeration {
         Enum::Enum { id, .. } => id.type_name,
       },
     }
   }
 
+  /// Get the name of the type without its path or generic arguments, as
+  /// `#[derive(Debug)]` would print it
+  pub(crate) fn bare_name(&self) -> &'static str {
+    let type_name = self.type_name();
+    let path = type_name.split('<').next().unwrap_or(type_name);
+    path.rsplit("::").next().unwrap_or(path)
+  }
+
+  /// Get the name of the type without its path, as an identifier, i.e.
+  /// `Pair_u8` for `Pair<u8>`
+  pub(crate) fn identifier(&self) -> String {
+    to_identifier(&::disqualified::ShortName(self.type_name()).to_string())
+  }
+
+  /// Get the full name of the type as an identifier, i.e. `a_b_Pair_u8` for
+  /// `a::b::Pair<u8>`
+  pub(crate) fn qualified_identifier(&self) -> String {
+    to_identifier(self.type_name())
+  }
+
+  /// Get the doc comment on the type, if it's a struct, union or enum that
+  /// has one
+  pub fn doc(&self) -> Option<&'static str> {
+    match self {
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { doc, .. }
+        | Struct::TupleStruct { doc, .. }
+        | Struct::Struct { doc, .. } => *doc,
+      },
+      TypeInfo::Union(Union::Union { doc, .. }) => *doc,
+      TypeInfo::Enum(enumeration) => match enumeration {
+        Enum::Enum { doc, .. } => *doc,
+      },
+      _ => None,
+    }
+  }
+
+  /// Get the `#[repr(..)]` of the type, if it's a struct, union or enum
+  pub fn repr(&self) -> Option<Repr> {
+    match self {
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { repr, .. }
+        | Struct::TupleStruct { repr, .. }
+        | Struct::Struct { repr, .. } => Some(*repr),
+      },
+      TypeInfo::Union(Union::Union { repr, .. }) => Some(*repr),
+      TypeInfo::Enum(enumeration) => match enumeration {
+        Enum::Enum { repr, .. } => Some(*repr),
+      },
+      _ => None,
+    }
+  }
+
   /// Get the [size] of the type, if it's a statically sized type
   ///
   /// [size]: ::core::mem::size_of
@@ -170,13 +229,16 @@ impl TypeInfo {
         Std::Vec { sized, .. }
         | Std::Option { sized, .. }
         | Std::Result { sized, .. }
-        | Std::PhantomData { sized, .. } => Some(sized.size),
+        | Std::PhantomData { sized, .. }
+        | Std::HashMap { sized, .. }
+        | Std::BTreeMap { sized, .. } => Some(sized.size),
       },
       TypeInfo::Struct(structure) => match structure {
         Struct::UnitStruct { sized, .. }
         | Struct::TupleStruct { sized, .. }
         | Struct::Struct { sized, .. } => Some(sized.size),
       },
+      TypeInfo::Union(Union::Union { sized, .. }) => Some(sized.size),
       TypeInfo::Enum(enumeration) => match enumeration {
         Enum::Enum { sized, .. } => Some(sized.size),
       },
@@ -229,19 +291,161 @@ impl TypeInfo {
         Std::Vec { sized, .. }
         | Std::Option { sized, .. }
         | Std::Result { sized, .. }
-        | Std::PhantomData { sized, .. } => Some(sized.align),
+        | Std::PhantomData { sized, .. }
+        | Std::HashMap { sized, .. }
+        | Std::BTreeMap { sized, .. } => Some(sized.align),
       },
       TypeInfo::Struct(structure) => match structure {
         Struct::UnitStruct { sized, .. }
         | Struct::TupleStruct { sized, .. }
         | Struct::Struct { sized, .. } => Some(sized.align),
       },
+      TypeInfo::Union(Union::Union { sized, .. }) => Some(sized.align),
       TypeInfo::Enum(enumeration) => match enumeration {
         Enum::Enum { sized, .. } => Some(sized.align),
       },
     }
   }
 
+  /// Get the [`Layout`] of the type, if it's a statically sized type
+  ///
+  /// [`Layout`]: ::core::alloc::Layout
+  pub fn layout(&self) -> Option<::core::alloc::Layout> {
+    Some(
+      ::core::alloc::Layout::from_size_align(self.size()?, self.align()?)
+        .unwrap(),
+    )
+  }
+
+  /// Get a function that [drops] a value of the type in place, if it's a
+  /// statically sized type
+  ///
+  /// [drops]: ::core::ptr::drop_in_place
+  pub fn drop_fn(&self) -> Option<unsafe fn(*mut u8)> {
+    use super::internal::drop_erased;
+
+    match self {
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { sized, .. }
+        | Pointer::RefMut { sized, .. }
+        | Pointer::RawConst { sized, .. }
+        | Pointer::RawMut { sized, .. }
+        | Pointer::Box { sized, .. } => Some(sized.drop_fn),
+      },
+      TypeInfo::Primitive(..) => Some(drop_erased::<()>),
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::str | Sequence::Slice { .. } => None,
+        Sequence::Array { sized, .. } => Some(sized.drop_fn),
+      },
+      TypeInfo::Tuple(Tuple::Tuple { sized, .. }) => Some(sized.drop_fn),
+      TypeInfo::Std(std) => match std {
+        Std::String => Some(drop_erased::<String>),
+        Std::Vec { sized, .. }
+        | Std::Option { sized, .. }
+        | Std::Result { sized, .. }
+        | Std::PhantomData { sized, .. }
+        | Std::HashMap { sized, .. }
+        | Std::BTreeMap { sized, .. } => Some(sized.drop_fn),
+      },
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { sized, .. }
+        | Struct::TupleStruct { sized, .. }
+        | Struct::Struct { sized, .. } => Some(sized.drop_fn),
+      },
+      TypeInfo::Union(Union::Union { sized, .. }) => Some(sized.drop_fn),
+      TypeInfo::Enum(enumeration) => match enumeration {
+        Enum::Enum { sized, .. } => Some(sized.drop_fn),
+      },
+    }
+  }
+
+  /// Get a function that writes the type's [`Default`] value to uninitialised
+  /// memory, if the type is known to implement `Default`.
+  ///
+  /// Derived types opt in with `#[inspect(default)]`.
+  pub fn default_fn(&self) -> Option<unsafe fn(*mut u8)> {
+    use super::internal::default_erased;
+
+    match self {
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { sized, .. }
+        | Pointer::RefMut { sized, .. }
+        | Pointer::RawConst { sized, .. }
+        | Pointer::RawMut { sized, .. }
+        | Pointer::Box { sized, .. } => sized.default_fn,
+      },
+      TypeInfo::Primitive(primitive) => Some(match primitive {
+        Primitive::u8 => default_erased::<u8>,
+        Primitive::u16 => default_erased::<u16>,
+        Primitive::u32 => default_erased::<u32>,
+        Primitive::u64 => default_erased::<u64>,
+        Primitive::u128 => default_erased::<u128>,
+
+        Primitive::i8 => default_erased::<i8>,
+        Primitive::i16 => default_erased::<i16>,
+        Primitive::i32 => default_erased::<i32>,
+        Primitive::i64 => default_erased::<i64>,
+        Primitive::i128 => default_erased::<i128>,
+
+        Primitive::f32 => default_erased::<f32>,
+        Primitive::f64 => default_erased::<f64>,
+
+        Primitive::usize => default_erased::<usize>,
+        Primitive::isize => default_erased::<isize>,
+        Primitive::Unit => default_erased::<()>,
+        Primitive::bool => default_erased::<bool>,
+        Primitive::char => default_erased::<char>,
+      }),
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::str | Sequence::Slice { .. } => None,
+        Sequence::Array { sized, .. } => sized.default_fn,
+      },
+      TypeInfo::Tuple(Tuple::Tuple { sized, .. }) => sized.default_fn,
+      TypeInfo::Std(std) => match std {
+        Std::String => Some(default_erased::<String>),
+        Std::Vec { sized, .. }
+        | Std::Option { sized, .. }
+        | Std::Result { sized, .. }
+        | Std::PhantomData { sized, .. }
+        | Std::HashMap { sized, .. }
+        | Std::BTreeMap { sized, .. } => sized.default_fn,
+      },
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { sized, .. }
+        | Struct::TupleStruct { sized, .. }
+        | Struct::Struct { sized, .. } => sized.default_fn,
+      },
+      TypeInfo::Union(Union::Union { sized, .. }) => sized.default_fn,
+      TypeInfo::Enum(enumeration) => match enumeration {
+        Enum::Enum { sized, .. } => sized.default_fn,
+      },
+    }
+  }
+
+  /// Whether values of the type may be built from the values of its fields
+  /// at run-time, by [`ValueBuilder`](crate::value::ValueBuilder),
+  /// [`bytes::unpack`](crate::bytes::unpack), deserializing and patching.
+  ///
+  /// Building a struct, union or enum field by field bypasses its
+  /// constructors and the privacy of its fields, so could break invariants
+  /// that `unsafe` code relies on. Derived types opt in with
+  /// `#[inspect(constructible)]`, which promises that any value of each field
+  /// is valid; every other type is constructible.
+  pub fn is_constructible(&self) -> bool {
+    match self {
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { constructible, .. }
+        | Struct::TupleStruct { constructible, .. }
+        | Struct::Struct { constructible, .. } => *constructible,
+      },
+      TypeInfo::Union(Union::Union { constructible, .. }) => *constructible,
+      TypeInfo::Enum(enumeration) => match enumeration {
+        Enum::Enum { constructible, .. } => *constructible,
+      },
+      _ => true,
+    }
+  }
+
   /// Get an iterator of [`DiscriminantErased`], the type-erased
   /// [discriminants], of the type when it is an enum
   ///
@@ -268,3 +472,56 @@ impl TypeInfo {
     })
   }
 }
+
+impl EnumVariantInfo {
+  /// Get the name of the variant
+  pub fn name(&self) -> &'static str {
+    match self {
+      EnumVariantInfo::Unit { variant_name, .. }
+      | EnumVariantInfo::Tuple { variant_name, .. }
+      | EnumVariantInfo::Struct { variant_name, .. } => variant_name,
+    }
+  }
+
+  /// Get the doc comment on the variant
+  pub fn doc(&self) -> Option<&'static str> {
+    match self {
+      EnumVariantInfo::Unit { variant_doc, .. }
+      | EnumVariantInfo::Tuple { variant_doc, .. }
+      | EnumVariantInfo::Struct { variant_doc, .. } => *variant_doc,
+    }
+  }
+
+  /// Get the numeric discriminant value of the variant, if the enum has a
+  /// primitive representation, like `#[repr(u8)]`
+  ///
+  /// Negative discriminants wrap around, as with `as usize`.
+  pub fn discriminant_value(&self) -> Option<usize> {
+    match self {
+      EnumVariantInfo::Unit {
+        variant_discriminant_value,
+        ..
+      }
+      | EnumVariantInfo::Tuple {
+        variant_discriminant_value,
+        ..
+      }
+      | EnumVariantInfo::Struct {
+        variant_discriminant_value,
+        ..
+      } => *variant_discriminant_value,
+    }
+  }
+}
+
+fn to_identifier(name: &str) -> String {
+  let name = name
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+    .collect::<String>();
+  name
+    .split('_')
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join("_")
+}
@@ -1,14 +1,21 @@
+mod accessors;
 mod discriminant_erased;
 mod display;
+mod fingerprint;
 mod impls;
 #[doc(hidden)]
 pub mod internal;
 mod methods;
+mod visitor;
 
-pub use discriminant_erased::DiscriminantErased;
 use {
   self::internal::{AssociatedProvider, Provider, ProviderOfTypeInfo},
-  ::core::any::TypeId,
+  ::core::{alloc::Layout, any::TypeId},
+};
+pub use {
+  accessors::{Field, Kind},
+  discriminant_erased::DiscriminantErased,
+  visitor::{TypeVisitor, Walker},
 };
 
 /// Implemented for any type that can provide type info via [`TypeInfo::of`]
@@ -58,10 +65,15 @@ pub enum TypeInfo {
   Std(Std),
   Tuple(Tuple),
   Struct(Struct),
+  Union(Union),
   Enum(Enum),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+  feature = "serde",
+  derive(::serde::Serialize, ::serde::Deserialize)
+)]
 #[expect(non_camel_case_types)]
 #[non_exhaustive]
 pub enum Primitive {
@@ -111,12 +123,14 @@ pub enum Std {
     id: IdInfo,
     sized: SizedInfo,
     item: ItemInfo,
+    info: VecInfo,
   },
   String,
   Option {
     id: IdInfo,
     sized: SizedInfo,
     item: ItemInfo,
+    info: OptionInfo,
   },
   Result {
     id: IdInfo,
@@ -128,6 +142,16 @@ pub enum Std {
     sized: SizedInfo,
     item: ItemInfo,
   },
+  HashMap {
+    id: IdInfo,
+    sized: SizedInfo,
+    info: MapInfo,
+  },
+  BTreeMap {
+    id: IdInfo,
+    sized: SizedInfo,
+    info: MapInfo,
+  },
 }
 
 #[derive(Debug)]
@@ -137,26 +161,31 @@ pub enum Pointer {
     id: IdInfo,
     sized: SizedInfo,
     item: ItemInfo,
+    info: PointerInfo,
   },
   RefMut {
     id: IdInfo,
     sized: SizedInfo,
     item: ItemInfo,
+    info: PointerInfo,
   },
   RawConst {
     id: IdInfo,
     sized: SizedInfo,
     item: ItemInfo,
+    info: PointerInfo,
   },
   RawMut {
     id: IdInfo,
     sized: SizedInfo,
     item: ItemInfo,
+    info: PointerInfo,
   },
   Box {
     id: IdInfo,
     sized: SizedInfo,
     item: ItemInfo,
+    info: PointerInfo,
   },
 }
 
@@ -176,16 +205,50 @@ pub enum Struct {
   UnitStruct {
     id: IdInfo,
     sized: SizedInfo,
+    repr: Repr,
+    /// Whether the struct opted in to being built from its fields at
+    /// run-time; see [`TypeInfo::is_constructible`]
+    constructible: bool,
+    /// The doc comment on the struct
+    doc: Option<&'static str>,
   },
   TupleStruct {
     id: IdInfo,
     sized: SizedInfo,
     fields: TupleStructInfo,
+    repr: Repr,
+    /// Whether the struct opted in to being built from its fields at
+    /// run-time; see [`TypeInfo::is_constructible`]
+    constructible: bool,
+    /// The doc comment on the struct
+    doc: Option<&'static str>,
   },
   Struct {
     id: IdInfo,
     sized: SizedInfo,
     fields: StructInfo,
+    repr: Repr,
+    /// Whether the struct opted in to being built from its fields at
+    /// run-time; see [`TypeInfo::is_constructible`]
+    constructible: bool,
+    /// The doc comment on the struct
+    doc: Option<&'static str>,
+  },
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Union {
+  Union {
+    id: IdInfo,
+    sized: SizedInfo,
+    fields: UnionInfo,
+    repr: Repr,
+    /// Whether the union opted in to being built from its fields at
+    /// run-time; see [`TypeInfo::is_constructible`]
+    constructible: bool,
+    /// The doc comment on the union
+    doc: Option<&'static str>,
   },
 }
 
@@ -196,6 +259,12 @@ pub enum Enum {
     id: IdInfo,
     sized: SizedInfo,
     variants: EnumInfo,
+    repr: Repr,
+    /// Whether the enum opted in to being built from its fields at
+    /// run-time; see [`TypeInfo::is_constructible`]
+    constructible: bool,
+    /// The doc comment on the enum
+    doc: Option<&'static str>,
   },
 }
 
@@ -209,6 +278,50 @@ pub struct IdInfo {
 pub struct SizedInfo {
   pub size: usize,
   pub align: usize,
+  /// Drops the value behind the pointer in place
+  pub drop_fn: unsafe fn(*mut u8),
+  /// Writes the type's `Default` value to the uninitialised memory behind the
+  /// pointer, if it's known to implement `Default`
+  pub default_fn: Option<unsafe fn(*mut u8)>,
+}
+
+impl SizedInfo {
+  /// The [`Layout`] of the type
+  pub fn layout(&self) -> Layout {
+    Layout::from_size_align(self.size, self.align).unwrap()
+  }
+}
+
+/// The layout options given to a struct, union or enum with `#[repr(..)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Repr {
+  /// `#[repr(C)]`
+  pub c: bool,
+  /// `#[repr(transparent)]`
+  pub transparent: bool,
+  /// `#[repr(u8)]`, `#[repr(i32)]`, etc. The type of an enum's discriminant
+  pub primitive: Option<Primitive>,
+  /// `#[repr(packed(n))]`, where `#[repr(packed)]` is `packed(1)`
+  pub packed: Option<usize>,
+  /// `#[repr(align(n))]`
+  pub align: Option<usize>,
+}
+
+impl Repr {
+  /// The default representation, without any `#[repr(..)]` options
+  pub const RUST: Repr = Repr {
+    c: false,
+    transparent: false,
+    primitive: None,
+    packed: None,
+    align: None,
+  };
+
+  /// Whether the layout of the type is defined by the options, rather than
+  /// left up to the compiler
+  pub fn is_defined(&self) -> bool {
+    self.c || self.transparent || self.primitive.is_some()
+  }
 }
 
 #[derive(Debug)]
@@ -236,11 +349,25 @@ pub struct StructInfo {
   pub field_infos: &'static [NamedFieldInfo],
 }
 
+#[derive(Debug)]
+pub struct UnionInfo {
+  pub field_infos: &'static [NamedFieldInfo],
+}
+
 #[derive(Debug)]
 pub struct NamedFieldInfo {
   pub field_name: &'static str,
   pub field_offset: usize,
   pub type_info_fn: fn() -> &'static TypeInfo,
+  pub attributes: FieldAttributes,
+}
+
+impl NamedFieldInfo {
+  /// The name of the field, or the name it was given with
+  /// `#[inspect(rename = "name")]`
+  pub fn serialized_name(&self) -> &'static str {
+    self.attributes.rename.unwrap_or(self.field_name)
+  }
 }
 
 #[derive(Debug)]
@@ -248,11 +375,46 @@ pub struct AnonymousFieldInfo {
   pub field_index: usize,
   pub field_offset: usize,
   pub type_info_fn: fn() -> &'static TypeInfo,
+  pub attributes: FieldAttributes,
+}
+
+/// Options set on a field with the `#[inspect(..)]` attribute
+#[derive(Debug)]
+pub struct FieldAttributes {
+  /// `#[inspect(skip)]`: the field is left out when inspecting values
+  pub skip: bool,
+  /// `#[inspect(rename = "name")]`: the name used for the field when
+  /// serializing values
+  pub rename: Option<&'static str>,
+  /// `#[inspect(proto = N)]`: the number of the field in Protobuf messages,
+  /// instead of its position
+  pub proto: Option<u32>,
+  /// The doc comment on the field
+  pub doc: Option<&'static str>,
+}
+
+impl FieldAttributes {
+  /// The attributes of a field without any `#[inspect(..)]` options
+  pub const NONE: FieldAttributes = FieldAttributes {
+    skip: false,
+    rename: None,
+    proto: None,
+    doc: None,
+  };
 }
 
 #[derive(Debug)]
 pub struct EnumInfo {
   pub variant_infos: &'static [EnumVariantInfo],
+  /// Gets the index into `variant_infos` of the active variant of the value
+  /// behind the pointer
+  pub variant_index_fn: unsafe fn(*const u8) -> usize,
+  /// Writes the variant at the given index into `variant_infos` to the
+  /// uninitialised memory behind the first pointer.
+  ///
+  /// The values of its fields are moved out of the pointers in the array
+  /// behind the last pointer, in the order they're declared.
+  pub construct_variant_fn: unsafe fn(*mut u8, usize, *const *const u8),
 }
 
 #[derive(Debug)]
@@ -264,6 +426,8 @@ pub enum EnumVariantInfo {
     /// The numeric discriminant value for enums that opt into an explicit
     /// (non Rust) repr
     variant_discriminant_value: Option<usize>,
+    /// The doc comment on the variant
+    variant_doc: Option<&'static str>,
   },
   Tuple {
     variant_name: &'static str,
@@ -272,6 +436,8 @@ pub enum EnumVariantInfo {
     /// The numeric discriminant value for enums that opt into an explicit
     /// (non Rust) repr
     variant_discriminant_value: Option<usize>,
+    /// The doc comment on the variant
+    variant_doc: Option<&'static str>,
     field_infos: &'static [AnonymousFieldInfo],
   },
   Struct {
@@ -281,6 +447,8 @@ pub enum EnumVariantInfo {
     /// The numeric discriminant value for enums that opt into an explicit
     /// (non Rust) repr
     variant_discriminant_value: Option<usize>,
+    /// The doc comment on the variant
+    variant_doc: Option<&'static str>,
     field_infos: &'static [NamedFieldInfo],
   },
 }
@@ -289,4 +457,67 @@ pub enum EnumVariantInfo {
 pub struct ResultInfo {
   pub ok_type_info_fn: fn() -> &'static TypeInfo,
   pub err_type_info_fn: fn() -> &'static TypeInfo,
+  /// Gets a pointer to the `Ok` or `Err` value of the `Result` behind the
+  /// pointer
+  pub as_result_fn: unsafe fn(*const u8) -> Result<*const u8, *const u8>,
+  /// Writes `Ok` to the uninitialised memory behind the first pointer, with
+  /// the value moved out of the last pointer
+  pub ok_fn: unsafe fn(*mut u8, *mut u8),
+  /// Writes `Err` to the uninitialised memory behind the first pointer, with
+  /// the value moved out of the last pointer
+  pub err_fn: unsafe fn(*mut u8, *mut u8),
+}
+
+#[derive(Debug)]
+pub struct VecInfo {
+  /// Gets a pointer to the items, and the length, of the `Vec` behind the
+  /// pointer
+  pub as_slice_fn: unsafe fn(*const u8) -> (*const u8, usize),
+  /// Gets a mutable pointer to the items, and the length, of the `Vec`
+  /// behind the pointer
+  pub as_mut_slice_fn: unsafe fn(*mut u8) -> (*mut u8, usize),
+  /// Gets the capacity of the `Vec` behind the pointer
+  pub capacity_fn: unsafe fn(*const u8) -> usize,
+  /// Pushes the item moved out of the last pointer onto the `Vec` behind the
+  /// first pointer
+  pub push_fn: unsafe fn(*mut u8, *mut u8),
+  /// Inserts the item moved out of the last pointer into the `Vec` behind
+  /// the first pointer, at the index, which must be at most its length
+  pub insert_fn: unsafe fn(*mut u8, usize, *mut u8),
+  /// Removes, and drops, the item at the index of the `Vec` behind the
+  /// pointer, which must be less than its length
+  pub remove_fn: unsafe fn(*mut u8, usize),
+}
+
+#[derive(Debug)]
+pub struct MapInfo {
+  pub key_type_info_fn: fn() -> &'static TypeInfo,
+  pub value_type_info_fn: fn() -> &'static TypeInfo,
+  /// Gets the number of entries in the map behind the pointer
+  pub len_fn: unsafe fn(*const u8) -> usize,
+  /// Calls the function with pointers to the key and value of each entry in
+  /// the map behind the pointer
+  pub for_each_fn: unsafe fn(*const u8, &mut dyn FnMut(*const u8, *const u8)),
+  /// Inserts the key and value moved out of the last two pointers into the
+  /// map behind the first pointer, replacing the value of any equal key
+  pub insert_fn: unsafe fn(*mut u8, *mut u8, *mut u8),
+}
+
+#[derive(Debug)]
+pub struct OptionInfo {
+  /// Gets a pointer to the `Some` value of the `Option` behind the pointer
+  pub as_option_fn: unsafe fn(*const u8) -> Option<*const u8>,
+  /// Writes `Some` to the uninitialised memory behind the first pointer, with
+  /// the value moved out of the last pointer
+  pub some_fn: unsafe fn(*mut u8, *mut u8),
+}
+
+#[derive(Debug)]
+pub struct PointerInfo {
+  /// Gets the address held by the pointer behind the pointer, along with
+  /// the length of the target if it is a `str` or slice.
+  ///
+  /// This does not read the target, so it's safe to call on dangling raw
+  /// pointers.
+  pub target_fn: unsafe fn(*const u8) -> (*const u8, usize),
 }
@@ -0,0 +1,407 @@
+use {
+  super::{
+    AnonymousFieldInfo, Enum, EnumVariantInfo, NamedFieldInfo, Pointer,
+    Primitive, Sequence, Std, Struct, Tuple, TypeInfo, Union,
+  },
+  ::core::any::TypeId,
+};
+
+/// Callbacks for each kind of type, field and variant met while walking the
+/// graph of types reachable from a type with a [`Walker`]
+///
+/// Every method does nothing but recurse into the children by default, so a
+/// visitor only needs to implement the methods for the things it's
+/// interested in, and new kinds of type don't break it. An overriding method
+/// can call the matching `walk_` method on the [`Walker`] to carry on into
+/// the children, or not to skip them.
+///
+/// ```rust
+/// # use ::inspect::{type_info::{TypeVisitor, Walker}, TypeInfo};
+/// #[derive(TypeInfo)]
+/// struct List {
+///   value: u8,
+///   next: Option<Box<List>>,
+/// }
+///
+/// #[derive(Default)]
+/// struct Depths {
+///   depths: Vec<usize>,
+///   cycles: usize,
+/// }
+///
+/// impl TypeVisitor for Depths {
+///   fn visit_type(&mut self, walker: &mut Walker, type_info: &'static TypeInfo) {
+///     self.depths.push(walker.depth());
+///     walker.walk_type(self, type_info);
+///   }
+///
+///   fn visit_cycle(&mut self, _: &mut Walker, _: &'static TypeInfo) {
+///     self.cycles += 1;
+///   }
+/// }
+///
+/// let mut depths = Depths::default();
+/// Walker::new().walk(&mut depths, TypeInfo::of::<List>());
+/// // `List`, `u8`, `Option<Box<List>>`, `Box<List>`
+/// assert_eq!(depths.depths, [0, 1, 1, 2]);
+/// // and the `Box` points back to `List`
+/// assert_eq!(depths.cycles, 1);
+/// ```
+#[allow(unused_variables)]
+pub trait TypeVisitor {
+  /// Called for each type, before the more specific method for its kind
+  fn visit_type(&mut self, walker: &mut Walker, type_info: &'static TypeInfo) {
+    walker.walk_type(self, type_info);
+  }
+
+  /// Called instead of [`visit_type`][TypeVisitor::visit_type] when a type
+  /// refers back to a type it's contained by
+  fn visit_cycle(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+  ) {
+  }
+
+  /// Called instead of [`visit_type`][TypeVisitor::visit_type] for a type
+  /// deeper than the walker's [maximum depth][Walker::max_depth]
+  fn visit_too_deep(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+  ) {
+  }
+
+  fn visit_primitive(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+    primitive: &'static Primitive,
+  ) {
+  }
+
+  fn visit_sequence(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+    sequence: &'static Sequence,
+  ) {
+    walker.walk_sequence(self, sequence);
+  }
+
+  fn visit_std(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+    std: &'static Std,
+  ) {
+    walker.walk_std(self, std);
+  }
+
+  fn visit_pointer(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+    pointer: &'static Pointer,
+  ) {
+    walker.walk_pointer(self, pointer);
+  }
+
+  fn visit_tuple(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+    tuple: &'static Tuple,
+  ) {
+    walker.walk_tuple(self, tuple);
+  }
+
+  fn visit_struct(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+    structure: &'static Struct,
+  ) {
+    walker.walk_struct(self, structure);
+  }
+
+  fn visit_union(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+    union: &'static Union,
+  ) {
+    walker.walk_union(self, union);
+  }
+
+  fn visit_enum(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+    enumeration: &'static Enum,
+  ) {
+    walker.walk_enum(self, enumeration);
+  }
+
+  fn visit_variant(
+    &mut self,
+    walker: &mut Walker,
+    variant: &'static EnumVariantInfo,
+  ) {
+    walker.walk_variant(self, variant);
+  }
+
+  /// Called for each field of a struct, union or struct-like variant
+  fn visit_named_field(
+    &mut self,
+    walker: &mut Walker,
+    field: &'static NamedFieldInfo,
+  ) {
+    walker.walk(self, (field.type_info_fn)());
+  }
+
+  /// Called for each field of a tuple, tuple struct or tuple-like variant,
+  /// with its index
+  fn visit_anonymous_field(
+    &mut self,
+    walker: &mut Walker,
+    index: usize,
+    field: &'static AnonymousFieldInfo,
+  ) {
+    walker.walk(self, (field.type_info_fn)());
+  }
+}
+
+/// Walks the graph of types reachable from a type, calling a
+/// [`TypeVisitor`]'s methods for everything it meets
+///
+/// Types are visited every time they're met, except when a type refers back
+/// to one of the types it's contained by, which would never end, and when
+/// they're deeper than the [maximum depth][Walker::max_depth].
+pub struct Walker {
+  /// The types being visited, from the root down to the current type
+  path: Vec<TypeId>,
+  max_depth: Option<usize>,
+}
+
+impl Walker {
+  pub fn new() -> Self {
+    Walker {
+      path: Vec::new(),
+      max_depth: None,
+    }
+  }
+
+  /// Don't visit types more than `max_depth` types below the root, which is
+  /// at depth 0
+  pub fn max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = Some(max_depth);
+    self
+  }
+
+  /// The depth of the type being visited, where the root is at depth 0
+  pub fn depth(&self) -> usize {
+    self.path.len().saturating_sub(1)
+  }
+
+  /// Visit the type described by `type_info` with
+  /// [`visit_type`][TypeVisitor::visit_type], unless it would be a cycle or
+  /// too deep
+  pub fn walk<V>(&mut self, visitor: &mut V, type_info: &'static TypeInfo)
+  where
+    V: ?Sized + TypeVisitor,
+  {
+    if self.path.contains(&type_info.type_id()) {
+      return visitor.visit_cycle(self, type_info);
+    }
+    if self.max_depth.is_some_and(|max| self.path.len() > max) {
+      return visitor.visit_too_deep(self, type_info);
+    }
+    self.path.push(type_info.type_id());
+    visitor.visit_type(self, type_info);
+    self.path.pop();
+  }
+
+  /// Call the method of the visitor for the kind of the type
+  pub fn walk_type<V>(&mut self, visitor: &mut V, type_info: &'static TypeInfo)
+  where
+    V: ?Sized + TypeVisitor,
+  {
+    match type_info {
+      TypeInfo::Pointer(pointer) => {
+        visitor.visit_pointer(self, type_info, pointer)
+      },
+      TypeInfo::Primitive(primitive) => {
+        visitor.visit_primitive(self, type_info, primitive)
+      },
+      TypeInfo::Sequence(sequence) => {
+        visitor.visit_sequence(self, type_info, sequence)
+      },
+      TypeInfo::Std(std) => visitor.visit_std(self, type_info, std),
+      TypeInfo::Tuple(tuple) => visitor.visit_tuple(self, type_info, tuple),
+      TypeInfo::Struct(structure) => {
+        visitor.visit_struct(self, type_info, structure)
+      },
+      TypeInfo::Union(union) => visitor.visit_union(self, type_info, union),
+      TypeInfo::Enum(enumeration) => {
+        visitor.visit_enum(self, type_info, enumeration)
+      },
+    }
+  }
+
+  /// Walk the item type of a slice or array
+  pub fn walk_sequence<V>(
+    &mut self,
+    visitor: &mut V,
+    sequence: &'static Sequence,
+  ) where
+    V: ?Sized + TypeVisitor,
+  {
+    match sequence {
+      Sequence::str => {},
+      Sequence::Slice { item, .. } | Sequence::Array { item, .. } => {
+        self.walk(visitor, (item.type_info_fn)())
+      },
+    }
+  }
+
+  /// Walk the item, key and value types of a standard library type
+  pub fn walk_std<V>(&mut self, visitor: &mut V, std: &'static Std)
+  where
+    V: ?Sized + TypeVisitor,
+  {
+    match std {
+      Std::String => {},
+      Std::Vec { item, .. }
+      | Std::Option { item, .. }
+      | Std::PhantomData { item, .. } => {
+        self.walk(visitor, (item.type_info_fn)())
+      },
+      Std::Result { info, .. } => {
+        self.walk(visitor, (info.ok_type_info_fn)());
+        self.walk(visitor, (info.err_type_info_fn)());
+      },
+      Std::HashMap { info, .. } | Std::BTreeMap { info, .. } => {
+        self.walk(visitor, (info.key_type_info_fn)());
+        self.walk(visitor, (info.value_type_info_fn)());
+      },
+    }
+  }
+
+  /// Walk the type a pointer points to
+  pub fn walk_pointer<V>(&mut self, visitor: &mut V, pointer: &'static Pointer)
+  where
+    V: ?Sized + TypeVisitor,
+  {
+    match pointer {
+      Pointer::Ref { item, .. }
+      | Pointer::RefMut { item, .. }
+      | Pointer::RawConst { item, .. }
+      | Pointer::RawMut { item, .. }
+      | Pointer::Box { item, .. } => self.walk(visitor, (item.type_info_fn)()),
+    }
+  }
+
+  /// Visit the fields of a tuple
+  pub fn walk_tuple<V>(&mut self, visitor: &mut V, tuple: &'static Tuple)
+  where
+    V: ?Sized + TypeVisitor,
+  {
+    match tuple {
+      Tuple::Tuple { info, .. } => {
+        self.walk_anonymous_fields(visitor, info.field_infos)
+      },
+    }
+  }
+
+  /// Visit the fields of a struct
+  pub fn walk_struct<V>(&mut self, visitor: &mut V, structure: &'static Struct)
+  where
+    V: ?Sized + TypeVisitor,
+  {
+    match structure {
+      Struct::UnitStruct { .. } => {},
+      Struct::TupleStruct { fields, .. } => {
+        self.walk_anonymous_fields(visitor, fields.field_infos)
+      },
+      Struct::Struct { fields, .. } => {
+        self.walk_named_fields(visitor, fields.field_infos)
+      },
+    }
+  }
+
+  /// Visit the fields of a union
+  pub fn walk_union<V>(&mut self, visitor: &mut V, union: &'static Union)
+  where
+    V: ?Sized + TypeVisitor,
+  {
+    match union {
+      Union::Union { fields, .. } => {
+        self.walk_named_fields(visitor, fields.field_infos)
+      },
+    }
+  }
+
+  /// Visit the variants of an enum
+  pub fn walk_enum<V>(&mut self, visitor: &mut V, enumeration: &'static Enum)
+  where
+    V: ?Sized + TypeVisitor,
+  {
+    match enumeration {
+      Enum::Enum { variants, .. } => {
+        for variant in variants.variant_infos {
+          visitor.visit_variant(self, variant);
+        }
+      },
+    }
+  }
+
+  /// Visit the fields of an enum variant
+  pub fn walk_variant<V>(
+    &mut self,
+    visitor: &mut V,
+    variant: &'static EnumVariantInfo,
+  ) where
+    V: ?Sized + TypeVisitor,
+  {
+    match variant {
+      EnumVariantInfo::Unit { .. } => {},
+      EnumVariantInfo::Tuple { field_infos, .. } => {
+        self.walk_anonymous_fields(visitor, field_infos)
+      },
+      EnumVariantInfo::Struct { field_infos, .. } => {
+        self.walk_named_fields(visitor, field_infos)
+      },
+    }
+  }
+
+  fn walk_named_fields<V>(
+    &mut self,
+    visitor: &mut V,
+    field_infos: &'static [NamedFieldInfo],
+  ) where
+    V: ?Sized + TypeVisitor,
+  {
+    for field in field_infos {
+      visitor.visit_named_field(self, field);
+    }
+  }
+
+  fn walk_anonymous_fields<V>(
+    &mut self,
+    visitor: &mut V,
+    field_infos: &'static [AnonymousFieldInfo],
+  ) where
+    V: ?Sized + TypeVisitor,
+  {
+    for (index, field) in field_infos.iter().enumerate() {
+      visitor.visit_anonymous_field(self, index, field);
+    }
+  }
+}
+
+impl Default for Walker {
+  fn default() -> Self {
+    Walker::new()
+  }
+}
@@ -0,0 +1,338 @@
+//! Generate TypeScript type definitions (`.d.ts`) describing how types are
+//! serialized
+//!
+//! The definitions describe values as they're written by
+//! [`serde::Reflected`], which matches the shape of `#[derive(Serialize)]`.
+//! Structs become interfaces, and enums become unions discriminated by the
+//! name of the variant. `Option`s are optional, and `null` when they're
+//! `None`. Every struct and enum that is referred to is defined once, after
+//! the types it depends on.
+//!
+//! ```rust
+//! # use ::inspect::{TypeInfo, typescript};
+//! /// A reading from a sensor
+//! #[derive(TypeInfo)]
+//! struct Reading {
+//!   sensor: String,
+//!   value: Option<f64>,
+//!   status: Status,
+//! }
+//!
+//! #[derive(TypeInfo)]
+//! enum Status {
+//!   Ok,
+//!   Failed { code: u16 },
+//! }
+//!
+//! assert_eq!(
+//!   typescript::generate::<Reading>(),
+//!   r#"export type Status =
+//!   | "Ok"
+//!   | { Failed: { code: number } };
+//!
+//! /** A reading from a sensor */
+//! export interface Reading {
+//!   sensor: string;
+//!   value?: number | null;
+//!   status: Status;
+//! }
+//! "#,
+//! );
+//! ```
+//!
+//! [`serde::Reflected`]: crate::serde::Reflected
+
+use {
+  crate::{
+    codegen::Names,
+    type_info::{
+      Enum, EnumVariantInfo, Field, Pointer, Primitive, Sequence, Std, Struct,
+      TypeInfo, TypeInfoProvider, Union,
+    },
+  },
+  ::core::fmt,
+};
+
+/// Generate the TypeScript definitions of `T`, and every struct and enum it
+/// refers to
+pub fn generate<T>() -> String
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  let mut definitions = TypeScript::new();
+  definitions.add::<T>();
+  definitions.to_string()
+}
+
+/// A set of TypeScript definitions that types can be added to one at a time.
+/// Formatting it with `Display` writes out the definitions
+#[derive(Clone, Default)]
+pub struct TypeScript {
+  /// The TypeScript names of the types that have been defined
+  names: Names,
+  /// Definitions, ordered so that types are defined after the types they
+  /// refer to, unless they refer to each other
+  definitions: Vec<String>,
+}
+
+impl TypeScript {
+  pub fn new() -> Self {
+    TypeScript::default()
+  }
+
+  /// Define `T`, and every struct and enum it refers to
+  pub fn add<T>(&mut self) -> &mut Self
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    self.add_type_info(TypeInfo::of::<T>())
+  }
+
+  /// Define the type described by `type_info`, and every struct and enum it
+  /// refers to
+  pub fn add_type_info(&mut self, type_info: &'static TypeInfo) -> &mut Self {
+    self.ts_type(type_info);
+    self
+  }
+
+  /// Define a struct or enum, returning its name in TypeScript
+  fn define(&mut self, type_info: &'static TypeInfo) -> String {
+    if let Some(name) = self.names.get(&type_info.type_id()) {
+      return name.clone();
+    }
+
+    let name = self.names.insert_type(type_info);
+
+    let mut definition = doc_comment(type_info.doc(), "");
+    match type_info {
+      TypeInfo::Struct(Struct::Struct { .. }) => {
+        definition += &format!("export interface {name} {{\n");
+        for field in self.named_fields(type_info.fields(), true) {
+          definition += &format!("  {field};\n");
+        }
+        definition += "}\n";
+      },
+      TypeInfo::Struct(Struct::TupleStruct { .. }) => {
+        let ts_type = self.tuple_struct(type_info.fields());
+        definition += &format!("export type {name} = {ts_type};\n");
+      },
+      TypeInfo::Struct(Struct::UnitStruct { .. }) => {
+        definition += &format!("export type {name} = null;\n");
+      },
+      TypeInfo::Enum(Enum::Enum { variants, .. }) => {
+        definition += &format!("export type {name} =");
+        if variants.variant_infos.is_empty() {
+          definition += " never";
+        }
+        for variant in variants.variant_infos {
+          definition += "\n";
+          definition += &self.variant(variant);
+        }
+        definition += ";\n";
+      },
+      _ => unreachable!("only structs and enums are defined"),
+    }
+    self.definitions.push(definition);
+    name
+  }
+
+  /// The TypeScript type of a value, defining any types it refers to
+  fn ts_type(&mut self, type_info: &'static TypeInfo) -> String {
+    match type_info {
+      TypeInfo::Primitive(primitive) => match primitive {
+        Primitive::bool => "boolean",
+        Primitive::char => "string",
+        Primitive::Unit => "null",
+        _ => "number",
+      }
+      .to_string(),
+      TypeInfo::Sequence(sequence) => match sequence {
+        Sequence::str => "string".to_string(),
+        Sequence::Slice { item, .. } | Sequence::Array { item, .. } => {
+          array_of(&self.ts_type((item.type_info_fn)()))
+        },
+      },
+      TypeInfo::Std(std) => match std {
+        Std::String => "string".to_string(),
+        Std::Vec { item, .. } => {
+          array_of(&self.ts_type((item.type_info_fn)()))
+        },
+        Std::Option { item, .. } => {
+          format!("{} | null", self.ts_type((item.type_info_fn)()))
+        },
+        Std::Result { info, .. } => format!(
+          "{{ Ok: {} }} | {{ Err: {} }}",
+          self.ts_type((info.ok_type_info_fn)()),
+          self.ts_type((info.err_type_info_fn)()),
+        ),
+        Std::PhantomData { .. } => "null".to_string(),
+        Std::HashMap { info, .. } | Std::BTreeMap { info, .. } => {
+          let key = self.ts_type((info.key_type_info_fn)());
+          let value = self.ts_type((info.value_type_info_fn)());
+          match key.as_str() {
+            // keys are written as strings, unless they're numbers
+            "number" | "string" => format!("Record<{key}, {value}>"),
+            // not every variant of an enum has to be a key
+            _ if is_defined(info.key_type_info_fn) => {
+              format!("Partial<Record<{key}, {value}>>")
+            },
+            _ => format!("Record<string, {value}>"),
+          }
+        },
+      },
+      TypeInfo::Pointer(pointer) => match pointer {
+        Pointer::Ref { item, .. }
+        | Pointer::RefMut { item, .. }
+        | Pointer::Box { item, .. } => self.ts_type((item.type_info_fn)()),
+        // raw pointers can't be serialized
+        Pointer::RawConst { .. } | Pointer::RawMut { .. } => {
+          "never".to_string()
+        },
+      },
+      TypeInfo::Tuple(..) => self.tuple(type_info.fields()),
+      // unions can't be serialized
+      TypeInfo::Union(Union::Union { .. }) => "never".to_string(),
+      TypeInfo::Struct(..) | TypeInfo::Enum(..) => self.define(type_info),
+    }
+  }
+
+  /// Tuple structs and variants with a single field are serialized as that
+  /// field, otherwise they're arrays like tuples
+  fn tuple_struct(&mut self, fields: impl Iterator<Item = Field>) -> String {
+    match fields.collect::<Vec<_>>().as_slice() {
+      [field] if !field.attributes.skip => self.ts_type(field.type_info()),
+      fields => self.tuple(fields.iter().copied()),
+    }
+  }
+
+  fn tuple(&mut self, fields: impl Iterator<Item = Field>) -> String {
+    let items = fields
+      .filter(|field| !field.attributes.skip)
+      .map(|field| self.ts_type(field.type_info()))
+      .collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
+  }
+
+  /// The properties of an object, i.e. `name?: string | null`, with their
+  /// doc comments if `docs` is set
+  fn named_fields(
+    &mut self,
+    fields: impl Iterator<Item = Field>,
+    docs: bool,
+  ) -> Vec<String> {
+    fields
+      .filter(|field| !field.attributes.skip)
+      .map(|field| {
+        let field_type_info = field.type_info();
+        let optional =
+          matches!(field_type_info, TypeInfo::Std(Std::Option { .. }));
+        let doc = match field.attributes.doc {
+          Some(doc) if docs => {
+            doc_comment(Some(doc), "").replace('\n', "\n  ")
+          },
+          _ => String::new(),
+        };
+        format!(
+          "{doc}{}{}: {}",
+          property_name(field.serialized_name().unwrap_or_default()),
+          if optional { "?" } else { "" },
+          self.ts_type(field_type_info),
+        )
+      })
+      .collect()
+  }
+
+  /// Unit variants are serialized as their name, and other variants as an
+  /// object with the variant's name as its only key
+  fn variant(&mut self, variant: &'static EnumVariantInfo) -> String {
+    let (name, doc, ts_type) = match variant {
+      EnumVariantInfo::Unit {
+        variant_name,
+        variant_doc,
+        ..
+      } => (variant_name, variant_doc, None),
+      EnumVariantInfo::Tuple {
+        variant_name,
+        variant_doc,
+        ..
+      } => (
+        variant_name,
+        variant_doc,
+        Some(self.tuple_struct(variant.fields())),
+      ),
+      EnumVariantInfo::Struct {
+        variant_name,
+        variant_doc,
+        ..
+      } => {
+        let fields = self.named_fields(variant.fields(), false);
+        (
+          variant_name,
+          variant_doc,
+          Some(format!("{{ {} }}", fields.join("; "))),
+        )
+      },
+    };
+    let ts_type = match ts_type {
+      Some(ts_type) => format!("{{ {}: {ts_type} }}", property_name(name)),
+      None => format!("{name:?}"),
+    };
+    format!("{}  | {ts_type}", doc_comment(*doc, "  "))
+  }
+}
+
+impl fmt::Display for TypeScript {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (index, definition) in self.definitions.iter().enumerate() {
+      if index > 0 {
+        writeln!(f)?;
+      }
+      f.write_str(definition)?;
+    }
+    Ok(())
+  }
+}
+
+/// Whether the type is a struct or enum, which have their own definitions
+fn is_defined(type_info_fn: fn() -> &'static TypeInfo) -> bool {
+  matches!(type_info_fn(), TypeInfo::Struct(..) | TypeInfo::Enum(..))
+}
+
+fn array_of(item: &str) -> String {
+  if item.contains(" | ") {
+    format!("({item})[]")
+  } else {
+    format!("{item}[]")
+  }
+}
+
+/// The name of a property, quoted if it isn't an identifier
+fn property_name(name: &str) -> String {
+  let is_identifier = name.chars().enumerate().all(|(index, c)| match c {
+    'a'..='z' | 'A'..='Z' | '_' | '$' => true,
+    '0'..='9' => index > 0,
+    _ => false,
+  });
+  if is_identifier && !name.is_empty() {
+    name.to_string()
+  } else {
+    format!("{name:?}")
+  }
+}
+
+/// A JSDoc comment, ending in a new line
+fn doc_comment(doc: Option<&str>, indent: &str) -> String {
+  let Some(doc) = doc else {
+    return String::new();
+  };
+  let doc = doc.replace("*/", "*\\/");
+  if doc.contains('\n') {
+    let lines = doc
+      .lines()
+      .map(|line| format!("{indent} * {line}").trim_end().to_string())
+      .collect::<Vec<_>>();
+    format!("{indent}/**\n{}\n{indent} */\n", lines.join("\n"))
+  } else {
+    format!("{indent}/** {doc} */\n")
+  }
+}
@@ -0,0 +1,407 @@
+use {
+  super::{
+    dyn_value::{allocate, deallocate},
+    DynValue,
+  },
+  crate::type_info::{
+    AnonymousFieldInfo, Enum, EnumVariantInfo, NamedFieldInfo, Struct, Tuple,
+    TypeInfo,
+  },
+  ::core::{error::Error, fmt, ptr},
+};
+
+/// Builds a value of a struct, tuple, or enum variant at run-time, by moving
+/// in the values of each of its fields
+///
+/// Every field must be given a value before the value can be built. Since
+/// this bypasses constructors and the privacy of fields, derived types can
+/// only be built once they opt in with `#[inspect(constructible)]`, which
+/// promises that any value of each field is valid; see
+/// [`TypeInfo::is_constructible`].
+///
+/// ```rust
+/// # use ::inspect::{TypeInfo, value::{DynValue, ValueBuilder}};
+/// #[derive(TypeInfo, Debug, PartialEq)]
+/// #[inspect(constructible)]
+/// enum Shape {
+///   Circle { radius: f32 },
+///   Square(f32),
+/// }
+///
+/// let mut builder =
+///   ValueBuilder::variant(TypeInfo::of::<Shape>(), "Circle").unwrap();
+/// builder.set("radius", DynValue::new(2.0f32)).unwrap();
+/// let shape = builder.build().unwrap();
+///
+/// assert_eq!(
+///   shape.downcast::<Shape>().ok(),
+///   Some(Shape::Circle { radius: 2.0 }),
+/// );
+/// ```
+pub struct ValueBuilder {
+  type_info: &'static TypeInfo,
+  /// The index of the variant being built, when building an enum
+  variant_index: Option<usize>,
+  fields: Vec<FieldSlot>,
+}
+
+struct FieldSlot {
+  name: FieldName,
+  offset: usize,
+  type_info: &'static TypeInfo,
+  value: Option<DynValue>,
+}
+
+impl ValueBuilder {
+  /// Start building a struct or tuple
+  pub fn new(type_info: &'static TypeInfo) -> Result<Self, BuildError> {
+    check_constructible(type_info)?;
+    let fields = match type_info {
+      TypeInfo::Tuple(Tuple::Tuple { info, .. }) => {
+        anonymous_slots(info.field_infos)
+      },
+      TypeInfo::Struct(structure) => match structure {
+        Struct::UnitStruct { .. } => Vec::new(),
+        Struct::TupleStruct { fields, .. } => {
+          anonymous_slots(fields.field_infos)
+        },
+        Struct::Struct { fields, .. } => named_slots(fields.field_infos),
+      },
+      _ => {
+        return Err(BuildError::NotAStruct {
+          type_name: type_info.type_name(),
+        })
+      },
+    };
+
+    Ok(ValueBuilder {
+      type_info,
+      variant_index: None,
+      fields,
+    })
+  }
+
+  /// Start building the variant of an enum called `variant_name`
+  pub fn variant(
+    type_info: &'static TypeInfo,
+    variant_name: &str,
+  ) -> Result<Self, BuildError> {
+    let TypeInfo::Enum(Enum::Enum { variants, .. }) = type_info else {
+      return Err(BuildError::NotAnEnum {
+        type_name: type_info.type_name(),
+      });
+    };
+    check_constructible(type_info)?;
+    let (variant_index, fields) = variants
+      .variant_infos
+      .iter()
+      .enumerate()
+      .find_map(|(index, variant)| match variant {
+        EnumVariantInfo::Unit {
+          variant_name: name, ..
+        } => (*name == variant_name).then(|| (index, Vec::new())),
+        EnumVariantInfo::Tuple {
+          variant_name: name,
+          field_infos,
+          ..
+        } => (*name == variant_name)
+          .then(|| (index, anonymous_slots(field_infos))),
+        EnumVariantInfo::Struct {
+          variant_name: name,
+          field_infos,
+          ..
+        } => {
+          (*name == variant_name).then(|| (index, named_slots(field_infos)))
+        },
+      })
+      .ok_or_else(|| BuildError::UnknownVariant {
+        type_name: type_info.type_name(),
+        variant_name: variant_name.to_string(),
+      })?;
+
+    Ok(ValueBuilder {
+      type_info,
+      variant_index: Some(variant_index),
+      fields,
+    })
+  }
+
+  /// The [`TypeInfo`] of the type being built
+  pub fn type_info(&self) -> &'static TypeInfo {
+    self.type_info
+  }
+
+  /// The [`TypeInfo`] of the field called `name`. The fields of tuples and
+  /// tuple structs are named by their index, i.e. `"0"`
+  pub fn field_type_info(&self, name: &str) -> Option<&'static TypeInfo> {
+    self
+      .fields
+      .iter()
+      .find(|field| field.name.matches(name))
+      .map(|field| field.type_info)
+  }
+
+  /// Set the value of the field called `name`, replacing any value it was
+  /// already given. The fields of tuples and tuple structs are named by their
+  /// index, i.e. `"0"`
+  pub fn set(
+    &mut self,
+    name: &str,
+    value: DynValue,
+  ) -> Result<(), BuildError> {
+    let type_name = self.type_info.type_name();
+    let field = self
+      .fields
+      .iter_mut()
+      .find(|field| field.name.matches(name))
+      .ok_or_else(|| BuildError::UnknownField {
+        type_name,
+        field_name: name.to_string(),
+      })?;
+    field.set(value)
+  }
+
+  /// Set the value of the first field, in the order they're declared, that
+  /// hasn't been given a value yet
+  pub fn push(&mut self, value: DynValue) -> Result<(), BuildError> {
+    let type_name = self.type_info.type_name();
+    let field = self
+      .fields
+      .iter_mut()
+      .find(|field| field.value.is_none())
+      .ok_or(BuildError::TooManyFields { type_name })?;
+    field.set(value)
+  }
+
+  /// Set the value of the field called `name`, see [`ValueBuilder::set`]
+  pub fn with(
+    mut self,
+    name: &str,
+    value: DynValue,
+  ) -> Result<Self, BuildError> {
+    self.set(name, value)?;
+    Ok(self)
+  }
+
+  /// Build the value, if every field has been given a value
+  pub fn build(self) -> Result<DynValue, BuildError> {
+    let missing = self
+      .fields
+      .iter()
+      .filter(|field| field.value.is_none())
+      .map(|field| field.name.to_string())
+      .collect::<Vec<_>>();
+    if !missing.is_empty() {
+      return Err(BuildError::MissingFields {
+        type_name: self.type_info.type_name(),
+        field_names: missing,
+      });
+    }
+
+    let values = self
+      .fields
+      .into_iter()
+      .map(|field| (field.offset, field.value.unwrap().into_raw()))
+      .collect::<Vec<_>>();
+    let ptr = allocate(self.type_info);
+
+    match self.variant_index {
+      None => {
+        for (offset, (value, type_info)) in &values {
+          unsafe {
+            ptr::copy_nonoverlapping(
+              value.as_ptr(),
+              ptr.as_ptr().add(*offset),
+              type_info.size().unwrap(),
+            )
+          };
+        }
+      },
+      Some(variant_index) => {
+        let TypeInfo::Enum(Enum::Enum { variants, .. }) = self.type_info
+        else {
+          unreachable!()
+        };
+        let field_ptrs = values
+          .iter()
+          .map(|(_, (value, _))| value.as_ptr().cast_const())
+          .collect::<Vec<_>>();
+        unsafe {
+          (variants.construct_variant_fn)(
+            ptr.as_ptr(),
+            variant_index,
+            field_ptrs.as_ptr(),
+          )
+        };
+      },
+    }
+
+    // the field values have been moved out, so only their allocations remain
+    for (_, (value, type_info)) in values {
+      unsafe { deallocate(value, type_info) };
+    }
+
+    Ok(unsafe { DynValue::from_raw(ptr, self.type_info) })
+  }
+}
+
+impl FieldSlot {
+  fn set(&mut self, value: DynValue) -> Result<(), BuildError> {
+    if value.type_info().type_id() != self.type_info.type_id() {
+      return Err(BuildError::MismatchedType {
+        field_name: self.name.to_string(),
+        expected: self.type_info.type_name(),
+        found: value.type_info().type_name(),
+      });
+    }
+    self.value = Some(value);
+    Ok(())
+  }
+}
+
+fn check_constructible(
+  type_info: &'static TypeInfo,
+) -> Result<(), BuildError> {
+  if type_info.is_constructible() {
+    Ok(())
+  } else {
+    Err(BuildError::NotConstructible {
+      type_name: type_info.type_name(),
+    })
+  }
+}
+
+fn named_slots(field_infos: &'static [NamedFieldInfo]) -> Vec<FieldSlot> {
+  field_infos
+    .iter()
+    .map(|field| FieldSlot {
+      name: FieldName::Named(field.field_name),
+      offset: field.field_offset,
+      type_info: (field.type_info_fn)(),
+      value: None,
+    })
+    .collect()
+}
+
+fn anonymous_slots(
+  field_infos: &'static [AnonymousFieldInfo],
+) -> Vec<FieldSlot> {
+  field_infos
+    .iter()
+    .map(|field| FieldSlot {
+      name: FieldName::Index(field.field_index),
+      offset: field.field_offset,
+      type_info: (field.type_info_fn)(),
+      value: None,
+    })
+    .collect()
+}
+
+enum FieldName {
+  Named(&'static str),
+  Index(usize),
+}
+
+impl FieldName {
+  fn matches(&self, name: &str) -> bool {
+    match self {
+      FieldName::Named(field_name) => *field_name == name,
+      FieldName::Index(index) => name.parse() == Ok(*index),
+    }
+  }
+}
+
+impl fmt::Display for FieldName {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FieldName::Named(name) => f.write_str(name),
+      FieldName::Index(index) => write!(f, "{index}"),
+    }
+  }
+}
+
+/// The reasons a [`ValueBuilder`] can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+  /// [`ValueBuilder::new`] was given a type that isn't a struct or tuple
+  NotAStruct { type_name: &'static str },
+  /// [`ValueBuilder::variant`] was given a type that isn't an enum
+  NotAnEnum { type_name: &'static str },
+  /// The enum has no variant with the given name
+  UnknownVariant {
+    type_name: &'static str,
+    variant_name: String,
+  },
+  /// The type, or variant, has no field with the given name
+  UnknownField {
+    type_name: &'static str,
+    field_name: String,
+  },
+  /// Every field already has a value
+  TooManyFields { type_name: &'static str },
+  /// The value given for a field is of the wrong type
+  MismatchedType {
+    field_name: String,
+    expected: &'static str,
+    found: &'static str,
+  },
+  /// Some fields were not given a value
+  MissingFields {
+    type_name: &'static str,
+    field_names: Vec<String>,
+  },
+  /// The type hasn't opted in to being built with
+  /// `#[inspect(constructible)]`
+  NotConstructible { type_name: &'static str },
+}
+
+impl fmt::Display for BuildError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      BuildError::NotAStruct { type_name } => {
+        write!(f, "`{type_name}` is not a struct or tuple")
+      },
+      BuildError::NotAnEnum { type_name } => {
+        write!(f, "`{type_name}` is not an enum")
+      },
+      BuildError::UnknownVariant {
+        type_name,
+        variant_name,
+      } => {
+        write!(f, "`{type_name}` has no variant called `{variant_name}`")
+      },
+      BuildError::UnknownField {
+        type_name,
+        field_name,
+      } => write!(f, "`{type_name}` has no field called `{field_name}`"),
+      BuildError::TooManyFields { type_name } => {
+        write!(f, "every field of `{type_name}` already has a value")
+      },
+      BuildError::MismatchedType {
+        field_name,
+        expected,
+        found,
+      } => write!(
+        f,
+        "field `{field_name}` expected a value of type `{expected}`, found \
+         `{found}`"
+      ),
+      BuildError::MissingFields {
+        type_name,
+        field_names,
+      } => write!(
+        f,
+        "missing values for fields of `{type_name}`: `{}`",
+        field_names.join("`, `")
+      ),
+      BuildError::NotConstructible { type_name } => write!(
+        f,
+        "`{type_name}` can't be built from its fields, since it isn't marked \
+         `#[inspect(constructible)]`"
+      ),
+    }
+  }
+}
+
+impl Error for BuildError {}
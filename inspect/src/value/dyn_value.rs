@@ -0,0 +1,308 @@
+use {
+  super::{ValueMut, ValueRef},
+  crate::{
+    type_info::{TypeInfo, TypeInfoProvider},
+    DebugValue,
+  },
+  ::core::{
+    alloc::Layout, any::TypeId, fmt, mem::ManuallyDrop, ops::Deref,
+    ptr::NonNull,
+  },
+  ::std::alloc,
+};
+
+/// An owned, type-erased value along with the [`TypeInfo`] of its type
+///
+/// This is like a `Box<dyn Any>`, except that the value can also be inspected
+/// through its `TypeInfo`.
+///
+/// ```rust
+/// # use ::inspect::{TypeInfo, value::DynValue};
+/// #[derive(TypeInfo)]
+/// struct Message {
+///   id: u32,
+///   body: String,
+/// }
+///
+/// let value = DynValue::new(Message { id: 7, body: "hi".to_string() });
+/// assert_eq!(format!("{}", value.field("id").unwrap().type_info()), "u32");
+///
+/// let message: Message = value.downcast().ok().unwrap();
+/// assert_eq!(message.body, "hi");
+/// ```
+///
+/// Whether a type is [`Send`] can't be told from its `TypeInfo`, so a
+/// `DynValue` can't be moved to another thread, even if its value could be.
+/// Values of `Send` types can be moved between threads as a [`SendDynValue`]
+/// instead, which checks that their type is `Send` when they're created.
+///
+/// ```compile_fail
+/// # use ::inspect::value::DynValue;
+/// let value = DynValue::new(7u32);
+/// ::std::thread::spawn(move || drop(value));
+/// ```
+pub struct DynValue {
+  ptr: NonNull<u8>,
+  type_info: &'static TypeInfo,
+}
+
+impl DynValue {
+  /// Move a value into a new allocation, erasing its type
+  pub fn new<T>(value: T) -> Self
+  where
+    T: TypeInfoProvider + 'static,
+  {
+    let type_info = TypeInfo::of::<T>();
+    let ptr = allocate(type_info);
+    unsafe { ptr.cast::<T>().write(value) };
+    DynValue { ptr, type_info }
+  }
+
+  /// Create the [`Default`] value of the type described by `type_info`, if
+  /// it's known to implement `Default`. See [`TypeInfo::default_fn`]
+  pub fn new_default(type_info: &'static TypeInfo) -> Option<Self> {
+    let default_fn = type_info.default_fn()?;
+    let ptr = allocate(type_info);
+    unsafe { default_fn(ptr.as_ptr()) };
+    Some(DynValue { ptr, type_info })
+  }
+
+  /// Create a `DynValue` from a pointer, as returned by
+  /// [`DynValue::into_raw`]
+  ///
+  /// # Safety
+  ///
+  /// `ptr` must point to a valid value of the sized type described by
+  /// `type_info`, in an allocation made by the global allocator with the
+  /// layout of [`TypeInfo::layout`]. Ownership of the value and the
+  /// allocation is transferred to the `DynValue`. If the layout has a size of
+  /// `0`, then `ptr` must be well aligned and non-null instead.
+  pub unsafe fn from_raw(
+    ptr: NonNull<u8>,
+    type_info: &'static TypeInfo,
+  ) -> Self {
+    DynValue { ptr, type_info }
+  }
+
+  /// Leak the value, returning a pointer to it and the [`TypeInfo`] of its
+  /// type. See [`DynValue::from_raw`]
+  pub fn into_raw(self) -> (NonNull<u8>, &'static TypeInfo) {
+    let this = ManuallyDrop::new(self);
+    (this.ptr, this.type_info)
+  }
+
+  /// The [`TypeInfo`] of the type of the value
+  pub fn type_info(&self) -> &'static TypeInfo {
+    self.type_info
+  }
+
+  /// Borrow the value as a [`ValueRef`]
+  pub fn as_value_ref(&self) -> ValueRef<'_> {
+    unsafe { ValueRef::from_raw_parts(self.ptr.as_ptr(), 0, self.type_info) }
+  }
+
+  /// Borrow the value as a [`ValueMut`]
+  pub fn as_value_mut(&mut self) -> ValueMut<'_> {
+    unsafe { ValueMut::from_raw_parts(self.ptr.as_ptr(), 0, self.type_info) }
+  }
+
+  /// The field called `name`, see [`ValueRef::field`]
+  pub fn field(&self, name: &str) -> Option<ValueRef<'_>> {
+    self.as_value_ref().field(name)
+  }
+
+  /// The field called `name`, see [`ValueRef::field`]
+  pub fn field_mut(&mut self, name: &str) -> Option<ValueMut<'_>> {
+    self.as_value_mut().into_field(name)
+  }
+
+  /// Whether the value is a `T`
+  pub fn is<T>(&self) -> bool
+  where
+    T: 'static,
+  {
+    self.type_info.type_id() == TypeId::of::<T>()
+  }
+
+  /// Get a reference to the value, if it is a `T`
+  pub fn downcast_ref<T>(&self) -> Option<&T>
+  where
+    T: 'static,
+  {
+    self
+      .is::<T>()
+      .then(|| unsafe { self.ptr.cast::<T>().as_ref() })
+  }
+
+  /// Get a mutable reference to the value, if it is a `T`
+  pub fn downcast_mut<T>(&mut self) -> Option<&mut T>
+  where
+    T: 'static,
+  {
+    self
+      .is::<T>()
+      .then(|| unsafe { self.ptr.cast::<T>().as_mut() })
+  }
+
+  /// Move the value out, if it is a `T`
+  pub fn downcast<T>(self) -> Result<T, Self>
+  where
+    T: 'static,
+  {
+    if !self.is::<T>() {
+      return Err(self);
+    }
+    let (ptr, type_info) = self.into_raw();
+    let value = unsafe { ptr.cast::<T>().read() };
+    unsafe { deallocate(ptr, type_info) };
+    Ok(value)
+  }
+
+  /// Create a value by writing it to a new allocation with `init`
+  ///
+  /// # Safety
+  ///
+  /// `init` must write a valid value of the type described by `type_info` to
+  /// the uninitialised memory behind the pointer
+  pub(crate) unsafe fn new_with(
+    type_info: &'static TypeInfo,
+    init: impl FnOnce(*mut u8),
+  ) -> Self {
+    let ptr = allocate(type_info);
+    init(ptr.as_ptr());
+    DynValue { ptr, type_info }
+  }
+
+  /// Move the value out with `take`, and then free its allocation
+  ///
+  /// # Safety
+  ///
+  /// `take` must move the value out from behind the pointer, since it won't
+  /// be dropped
+  pub(crate) unsafe fn take_with(self, take: impl FnOnce(*mut u8)) {
+    let (ptr, type_info) = self.into_raw();
+    take(ptr.as_ptr());
+    unsafe { deallocate(ptr, type_info) };
+  }
+
+  /// Create a value of the type described by `type_info` with `wrap_fn`,
+  /// moving this value into it, as with the `some_fn` of an `Option`
+  ///
+  /// # Safety
+  ///
+  /// `wrap_fn` must write a valid value of the type described by `type_info`
+  /// to the uninitialised memory behind its first pointer, moving this value
+  /// out from behind its second pointer
+  pub(crate) unsafe fn wrap(
+    self,
+    type_info: &'static TypeInfo,
+    wrap_fn: unsafe fn(*mut u8, *mut u8),
+  ) -> Self {
+    unsafe {
+      DynValue::new_with(type_info, |ptr| {
+        self.take_with(|value| wrap_fn(ptr, value))
+      })
+    }
+  }
+}
+
+impl Drop for DynValue {
+  fn drop(&mut self) {
+    let drop_fn = self.type_info.drop_fn().unwrap();
+    unsafe {
+      drop_fn(self.ptr.as_ptr());
+      deallocate(self.ptr, self.type_info);
+    }
+  }
+}
+
+impl fmt::Debug for DynValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(&DebugValue::new(self.as_value_ref()), f)
+  }
+}
+
+/// A [`DynValue`] of a type that's [`Send`], so that it can be moved to
+/// another thread
+///
+/// It can only be created from a value whose type is known to be `Send`, and
+/// only gives shared access to the `DynValue`, so the value can't be replaced
+/// with one of another type.
+///
+/// ```rust
+/// # use ::inspect::{TypeInfo, value::SendDynValue};
+/// #[derive(TypeInfo)]
+/// struct Message {
+///   id: u32,
+/// }
+///
+/// let value = SendDynValue::new(Message { id: 7 });
+/// let message = ::std::thread::spawn(move || {
+///   value.into_inner().downcast::<Message>().ok().unwrap()
+/// });
+/// assert_eq!(message.join().unwrap().id, 7);
+/// ```
+pub struct SendDynValue(DynValue);
+
+// the value could only be created from a `T: Send`
+unsafe impl Send for SendDynValue {}
+
+impl SendDynValue {
+  /// Move a value into a new allocation, erasing its type
+  pub fn new<T>(value: T) -> Self
+  where
+    T: TypeInfoProvider + Send + 'static,
+  {
+    SendDynValue(DynValue::new(value))
+  }
+
+  /// The `DynValue`, which can't be moved to another thread any more
+  pub fn into_inner(self) -> DynValue {
+    self.0
+  }
+}
+
+impl Deref for SendDynValue {
+  type Target = DynValue;
+
+  fn deref(&self) -> &DynValue {
+    &self.0
+  }
+}
+
+impl fmt::Debug for SendDynValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(&self.0, f)
+  }
+}
+
+/// Allocate uninitialised memory for a value of a sized type
+pub(crate) fn allocate(type_info: &'static TypeInfo) -> NonNull<u8> {
+  let layout = type_info
+    .layout()
+    .expect("DynValue only supports sized types");
+  if layout.size() == 0 {
+    return dangling(layout);
+  }
+  let ptr = unsafe { alloc::alloc(layout) };
+  NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout))
+}
+
+/// Free memory from [`allocate`], without dropping its contents
+///
+/// # Safety
+///
+/// `ptr` must have been allocated by [`allocate`] with the same `type_info`
+pub(crate) unsafe fn deallocate(
+  ptr: NonNull<u8>,
+  type_info: &'static TypeInfo,
+) {
+  let layout = type_info.layout().unwrap();
+  if layout.size() != 0 {
+    unsafe { alloc::dealloc(ptr.as_ptr(), layout) };
+  }
+}
+
+fn dangling(layout: Layout) -> NonNull<u8> {
+  NonNull::new(::core::ptr::without_provenance_mut(layout.align())).unwrap()
+}
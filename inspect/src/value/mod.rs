@@ -0,0 +1,389 @@
+//! Type-erased access to values, driven by their [`TypeInfo`]
+
+mod build;
+mod dyn_value;
+
+pub use self::{
+  build::{BuildError, ValueBuilder},
+  dyn_value::{DynValue, SendDynValue},
+};
+use {
+  crate::type_info::{
+    internal::{AssociatedProvider, ProviderOfTypeInfo},
+    Enum, EnumVariantInfo, Field, Pointer, Sequence, Std, TypeInfo,
+    TypeInfoProvider,
+  },
+  ::core::{marker::PhantomData, ptr},
+};
+
+/// A type-erased shared reference to a value, along with the [`TypeInfo`] of
+/// its type
+#[derive(Clone, Copy)]
+pub struct ValueRef<'v> {
+  ptr: *const u8,
+  /// The length of the value, if it is a `str` or slice
+  len: usize,
+  type_info: &'static TypeInfo,
+  marker: PhantomData<&'v ()>,
+}
+
+impl<'v> ValueRef<'v> {
+  /// Erase the type of a reference to some value
+  pub fn new<T>(value: &'v T) -> Self
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    let ptr = ptr::from_ref(value);
+    ValueRef {
+      ptr: ptr.cast(),
+      len: len_of(ptr),
+      type_info: TypeInfo::of::<T>(),
+      marker: PhantomData,
+    }
+  }
+
+  /// Create a `ValueRef` from a pointer to a value, the length of the value if
+  /// it is a `str` or slice, and the `TypeInfo` of its type
+  ///
+  /// # Safety
+  ///
+  /// `ptr` must point to a valid value of the type described by `type_info`,
+  /// which must remain valid and unmutated for `'v`
+  pub unsafe fn from_raw_parts(
+    ptr: *const u8,
+    len: usize,
+    type_info: &'static TypeInfo,
+  ) -> Self {
+    ValueRef {
+      ptr,
+      len,
+      type_info,
+      marker: PhantomData,
+    }
+  }
+
+  /// The [`TypeInfo`] of the type of the value
+  pub fn type_info(&self) -> &'static TypeInfo {
+    self.type_info
+  }
+
+  /// A pointer to the value
+  pub fn as_ptr(&self) -> *const u8 {
+    self.ptr
+  }
+
+  /// The field called `name` of a struct, tuple, or the active variant of an
+  /// enum. The fields of tuples and tuple structs are named by their index,
+  /// i.e. `"0"`
+  pub fn field(&self, name: &str) -> Option<ValueRef<'v>> {
+    let field = unsafe { find_field(self.type_info, self.ptr, name) }?;
+    Some(unsafe { self.field_at(field.offset, field.type_info()) })
+  }
+
+  /// The length of the value, if it is a `str` or slice, or `0` otherwise
+  pub(crate) fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Reinterpret the value as a `T`
+  ///
+  /// # Safety
+  ///
+  /// The value must be a valid `T`
+  pub(crate) unsafe fn cast<T>(&self) -> &'v T {
+    unsafe { &*self.ptr.cast::<T>() }
+  }
+
+  /// The field, described by `type_info`, that is `offset` bytes into the
+  /// value
+  ///
+  /// # Safety
+  ///
+  /// There must be such a field in the value
+  pub(crate) unsafe fn field_at(
+    &self,
+    offset: usize,
+    type_info: &'static TypeInfo,
+  ) -> ValueRef<'v> {
+    unsafe { ValueRef::from_raw_parts(self.ptr.add(offset), 0, type_info) }
+  }
+
+  /// The items of an array, slice or `Vec`
+  pub(crate) fn items(
+    &self,
+  ) -> Option<impl ExactSizeIterator<Item = ValueRef<'v>> + 'v> {
+    let (ptr, len, item) = match self.type_info {
+      TypeInfo::Sequence(Sequence::Array { item, info, .. }) => {
+        (self.ptr, info.array_length, item)
+      },
+      TypeInfo::Sequence(Sequence::Slice { item, .. }) => {
+        (self.ptr, self.len, item)
+      },
+      TypeInfo::Std(Std::Vec { item, info, .. }) => {
+        let (ptr, len) = unsafe { (info.as_slice_fn)(self.ptr) };
+        (ptr, len, item)
+      },
+      _ => return None,
+    };
+    let item_type_info = (item.type_info_fn)();
+    let stride = item_type_info.size().unwrap();
+    Some((0..len).map(move |index| unsafe {
+      ValueRef::from_raw_parts(ptr.add(index * stride), 0, item_type_info)
+    }))
+  }
+
+  /// The keys and values of a `HashMap` or `BTreeMap`
+  pub(crate) fn entries(&self) -> Option<Vec<(ValueRef<'v>, ValueRef<'v>)>> {
+    let info = match self.type_info {
+      TypeInfo::Std(
+        Std::HashMap { info, .. } | Std::BTreeMap { info, .. },
+      ) => info,
+      _ => return None,
+    };
+    let key_type_info = (info.key_type_info_fn)();
+    let value_type_info = (info.value_type_info_fn)();
+    let mut entries = Vec::with_capacity(unsafe { (info.len_fn)(self.ptr) });
+    unsafe {
+      (info.for_each_fn)(self.ptr, &mut |key, value| {
+        entries.push((
+          ValueRef::from_raw_parts(key, 0, key_type_info),
+          ValueRef::from_raw_parts(value, 0, value_type_info),
+        ))
+      })
+    };
+    Some(entries)
+  }
+
+  /// The target of a reference or `Box`
+  ///
+  /// Raw pointers are not followed, see [`ValueRef::address`]
+  pub(crate) fn target(&self) -> Option<ValueRef<'v>> {
+    match self.type_info {
+      TypeInfo::Pointer(
+        Pointer::Ref { item, info, .. }
+        | Pointer::RefMut { item, info, .. }
+        | Pointer::Box { item, info, .. },
+      ) => {
+        let (ptr, len) = unsafe { (info.target_fn)(self.ptr) };
+        Some(unsafe {
+          ValueRef::from_raw_parts(ptr, len, (item.type_info_fn)())
+        })
+      },
+      _ => None,
+    }
+  }
+
+  /// The address held by a pointer
+  pub(crate) fn address(&self) -> Option<*const u8> {
+    match self.type_info {
+      TypeInfo::Pointer(
+        Pointer::Ref { info, .. }
+        | Pointer::RefMut { info, .. }
+        | Pointer::RawConst { info, .. }
+        | Pointer::RawMut { info, .. }
+        | Pointer::Box { info, .. },
+      ) => Some(unsafe { (info.target_fn)(self.ptr) }.0),
+      _ => None,
+    }
+  }
+
+  /// The contents of an `Option`
+  pub(crate) fn option(&self) -> Option<Option<ValueRef<'v>>> {
+    match self.type_info {
+      TypeInfo::Std(Std::Option { item, info, .. }) => {
+        let some = unsafe { (info.as_option_fn)(self.ptr) };
+        Some(some.map(|ptr| unsafe {
+          ValueRef::from_raw_parts(ptr, 0, (item.type_info_fn)())
+        }))
+      },
+      _ => None,
+    }
+  }
+
+  /// The contents of a `Result`
+  pub(crate) fn result(&self) -> Option<Result<ValueRef<'v>, ValueRef<'v>>> {
+    match self.type_info {
+      TypeInfo::Std(Std::Result { info, .. }) => {
+        Some(match unsafe { (info.as_result_fn)(self.ptr) } {
+          Ok(ptr) => Ok(unsafe {
+            ValueRef::from_raw_parts(ptr, 0, (info.ok_type_info_fn)())
+          }),
+          Err(ptr) => Err(unsafe {
+            ValueRef::from_raw_parts(ptr, 0, (info.err_type_info_fn)())
+          }),
+        })
+      },
+      _ => None,
+    }
+  }
+
+  /// The active variant of an `Option` or `Result`, and its contents
+  pub(crate) fn std_variant(
+    &self,
+  ) -> Option<(&'static str, Option<ValueRef<'v>>)> {
+    match (self.option(), self.result()) {
+      (Some(Some(some)), _) => Some(("Some", Some(some))),
+      (Some(None), _) => Some(("None", None)),
+      (_, Some(Ok(ok))) => Some(("Ok", Some(ok))),
+      (_, Some(Err(err))) => Some(("Err", Some(err))),
+      _ => None,
+    }
+  }
+
+  /// The active variant of an enum, and its index
+  pub(crate) fn variant(&self) -> Option<(usize, &'static EnumVariantInfo)> {
+    match self.type_info {
+      TypeInfo::Enum(Enum::Enum { variants, .. }) => {
+        let index = unsafe { (variants.variant_index_fn)(self.ptr) };
+        Some((index, &variants.variant_infos[index]))
+      },
+      _ => None,
+    }
+  }
+
+  /// The size of the value in bytes, like [`size_of_val`], for sized types,
+  /// `str`s and slices
+  pub(crate) fn size(&self) -> Option<usize> {
+    match self.type_info {
+      TypeInfo::Sequence(Sequence::str) => Some(self.len),
+      TypeInfo::Sequence(Sequence::Slice { item, .. }) => {
+        Some(self.len * (item.type_info_fn)().size()?)
+      },
+      type_info => type_info.size(),
+    }
+  }
+
+  /// The contents of a `str` or `String`
+  pub(crate) fn as_str(&self) -> Option<&'v str> {
+    match self.type_info {
+      TypeInfo::Sequence(Sequence::str) => Some(unsafe {
+        let bytes = ::core::slice::from_raw_parts(self.ptr, self.len);
+        ::core::str::from_utf8_unchecked(bytes)
+      }),
+      TypeInfo::Std(Std::String) => Some(unsafe { self.cast::<String>() }),
+      _ => None,
+    }
+  }
+}
+
+/// A type-erased exclusive reference to a value, along with the [`TypeInfo`]
+/// of its type
+pub struct ValueMut<'v> {
+  ptr: *mut u8,
+  /// The length of the value, if it is a `str` or slice
+  len: usize,
+  type_info: &'static TypeInfo,
+  marker: PhantomData<&'v mut ()>,
+}
+
+impl<'v> ValueMut<'v> {
+  /// Erase the type of a mutable reference to some value
+  pub fn new<T>(value: &'v mut T) -> Self
+  where
+    T: ?Sized + TypeInfoProvider,
+  {
+    let ptr = ptr::from_mut(value);
+    ValueMut {
+      ptr: ptr.cast(),
+      len: len_of(ptr.cast_const()),
+      type_info: TypeInfo::of::<T>(),
+      marker: PhantomData,
+    }
+  }
+
+  /// Create a `ValueMut` from a pointer to a value, the length of the value if
+  /// it is a `str` or slice, and the `TypeInfo` of its type
+  ///
+  /// # Safety
+  ///
+  /// `ptr` must point to a valid value of the type described by `type_info`,
+  /// which must not be accessed through any other pointer for `'v`
+  pub unsafe fn from_raw_parts(
+    ptr: *mut u8,
+    len: usize,
+    type_info: &'static TypeInfo,
+  ) -> Self {
+    ValueMut {
+      ptr,
+      len,
+      type_info,
+      marker: PhantomData,
+    }
+  }
+
+  /// The [`TypeInfo`] of the type of the value
+  pub fn type_info(&self) -> &'static TypeInfo {
+    self.type_info
+  }
+
+  /// A pointer to the value
+  pub fn as_ptr(&self) -> *const u8 {
+    self.ptr
+  }
+
+  /// A mutable pointer to the value
+  pub fn as_mut_ptr(&mut self) -> *mut u8 {
+    self.ptr
+  }
+
+  /// Borrow the value as a [`ValueRef`]
+  pub fn as_value_ref(&self) -> ValueRef<'_> {
+    unsafe { ValueRef::from_raw_parts(self.ptr, self.len, self.type_info) }
+  }
+
+  /// Reborrow the value, for a shorter lifetime
+  pub fn reborrow(&mut self) -> ValueMut<'_> {
+    unsafe { ValueMut::from_raw_parts(self.ptr, self.len, self.type_info) }
+  }
+
+  /// The length of the value, see [`ValueRef::len`]
+  pub(crate) fn len(&self) -> usize {
+    self.len
+  }
+
+  /// The field called `name`, see [`ValueRef::field`]
+  pub fn field_mut(&mut self, name: &str) -> Option<ValueMut<'_>> {
+    self.reborrow().into_field(name)
+  }
+
+  /// Convert into the field called `name`, see [`ValueRef::field`]
+  pub fn into_field(self, name: &str) -> Option<ValueMut<'v>> {
+    let field = unsafe { find_field(self.type_info, self.ptr, name) }?;
+    Some(unsafe {
+      ValueMut::from_raw_parts(
+        self.ptr.add(field.offset),
+        0,
+        field.type_info(),
+      )
+    })
+  }
+}
+
+fn len_of<T>(ptr: *const T) -> usize
+where
+  T: ?Sized + TypeInfoProvider,
+{
+  <<T as AssociatedProvider>::Provider as ProviderOfTypeInfo<T>>::len_of(ptr)
+}
+
+/// Find the field called `name` in the value behind `ptr`
+///
+/// # Safety
+///
+/// `ptr` must point to a valid value of the type described by `type_info`
+unsafe fn find_field(
+  type_info: &'static TypeInfo,
+  ptr: *const u8,
+  name: &str,
+) -> Option<Field> {
+  match type_info {
+    TypeInfo::Tuple(_) | TypeInfo::Struct(_) => {
+      Field::find(type_info.fields(), name)
+    },
+    TypeInfo::Enum(Enum::Enum { variants, .. }) => {
+      let index = unsafe { (variants.variant_index_fn)(ptr) };
+      Field::find(variants.variant_infos[index].fields(), name)
+    },
+    _ => None,
+  }
+}
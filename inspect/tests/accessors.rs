@@ -0,0 +1,132 @@
+use ::inspect::{
+  type_info::{Enum, Kind, Primitive, Struct},
+  TypeInfo,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Named {
+  id: u32,
+  #[inspect(rename = "text")]
+  body: String,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Wrapper(u8, Named);
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+enum Event {
+  #[default]
+  Empty,
+  Moved(i16, i16),
+  Renamed {
+    from: String,
+    to: String,
+  },
+}
+
+#[test]
+fn accessors_fields() {
+  let fields = TypeInfo::of::<Named>().fields().collect::<Vec<_>>();
+  assert_eq!(fields.len(), 2);
+  assert_eq!(fields[0].index, 0);
+  assert_eq!(fields[0].name, Some("id"));
+  assert_eq!(fields[0].offset, ::core::mem::offset_of!(Named, id));
+  assert_eq!(
+    fields[0].type_info().type_id(),
+    TypeInfo::of::<u32>().type_id()
+  );
+  assert_eq!(fields[1].serialized_name(), Some("text"));
+
+  let fields = TypeInfo::of::<Wrapper>().fields().collect::<Vec<_>>();
+  assert_eq!(
+    fields.iter().map(|field| field.name).collect::<Vec<_>>(),
+    [None, None]
+  );
+  assert_eq!(fields[1].index, 1);
+  assert_eq!(fields[1].offset, ::core::mem::offset_of!(Wrapper, 1));
+
+  let tuple = TypeInfo::of::<(u8, bool)>();
+  assert_eq!(tuple.fields().count(), 2);
+  assert_eq!(TypeInfo::of::<Named>().field("body").unwrap().index, 1);
+  assert!(TypeInfo::of::<Named>().field("text").is_none());
+  assert_eq!(TypeInfo::of::<u8>().fields().count(), 0);
+}
+
+#[test]
+fn accessors_variants() {
+  let info = TypeInfo::of::<Event>();
+  assert_eq!(
+    info.variants().iter().map(|v| v.name()).collect::<Vec<_>>(),
+    ["Empty", "Moved", "Renamed"]
+  );
+  let moved = info.variant("Moved").unwrap();
+  assert_eq!(
+    moved.fields().map(|field| field.index).collect::<Vec<_>>(),
+    [0, 1]
+  );
+  let renamed = info.variant("Renamed").unwrap();
+  assert_eq!(
+    renamed.fields().map(|field| field.name).collect::<Vec<_>>(),
+    [Some("from"), Some("to")]
+  );
+  assert_eq!(info.variant("Empty").unwrap().fields().count(), 0);
+  assert!(info.variant("Missing").is_none());
+  assert!(info.fields().next().is_none());
+  assert!(TypeInfo::of::<Named>().variants().is_empty());
+}
+
+#[test]
+fn accessors_items_and_generic_args() {
+  let id = |info: Option<&TypeInfo>| info.map(TypeInfo::type_id);
+  let u8_id = Some(TypeInfo::of::<u8>().type_id());
+  assert_eq!(id(TypeInfo::of::<Vec<u8>>().item()), u8_id);
+  assert_eq!(id(TypeInfo::of::<[u8; 4]>().item()), u8_id);
+  assert_eq!(
+    id(TypeInfo::of::<&[u8]>().item()),
+    Some(TypeInfo::of::<[u8]>().type_id())
+  );
+  assert_eq!(id(TypeInfo::of::<Box<u8>>().item()), u8_id);
+  assert!(TypeInfo::of::<String>().item().is_none());
+  assert!(TypeInfo::of::<Result<u8, ()>>().item().is_none());
+
+  let names = |info: &TypeInfo| {
+    info
+      .generic_args()
+      .into_iter()
+      .map(TypeInfo::type_name)
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(names(TypeInfo::of::<Result<u8, bool>>()), ["u8", "bool"]);
+  assert_eq!(
+    names(TypeInfo::of::<::std::collections::BTreeMap<char, u16>>()),
+    ["char", "u16"]
+  );
+  assert_eq!(names(TypeInfo::of::<(i8, f32)>()), ["i8", "f32"]);
+  assert_eq!(names(TypeInfo::of::<Option<u8>>()), ["u8"]);
+  assert!(names(TypeInfo::of::<Named>()).is_empty());
+}
+
+#[test]
+fn accessors_kinds_and_downcasts() {
+  assert_eq!(TypeInfo::of::<u8>().kind(), Kind::Primitive);
+  assert_eq!(TypeInfo::of::<str>().kind(), Kind::Str);
+  assert_eq!(TypeInfo::of::<&mut u8>().kind(), Kind::RefMut);
+  assert_eq!(TypeInfo::of::<Named>().kind(), Kind::Struct);
+  assert_eq!(TypeInfo::of::<Wrapper>().kind(), Kind::TupleStruct);
+  assert_eq!(TypeInfo::of::<Event>().kind(), Kind::Enum);
+
+  assert!(matches!(
+    TypeInfo::of::<Named>().as_struct(),
+    Some(Struct::Struct { .. })
+  ));
+  assert!(TypeInfo::of::<Named>().as_enum().is_none());
+  assert!(matches!(
+    TypeInfo::of::<Event>().as_enum(),
+    Some(Enum::Enum { .. })
+  ));
+  assert_eq!(TypeInfo::of::<u8>().as_primitive(), Some(&Primitive::u8));
+  assert!(TypeInfo::of::<(u8,)>().as_tuple().is_some());
+}
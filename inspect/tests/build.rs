@@ -0,0 +1,178 @@
+use ::inspect::{
+  value::{BuildError, DynValue, ValueBuilder},
+  TypeInfo,
+};
+
+#[derive(TypeInfo, Debug, PartialEq)]
+#[inspect(constructible)]
+struct Fixture {
+  name: String,
+  count: u16,
+  tags: Vec<&'static str>,
+}
+
+#[derive(TypeInfo, Debug, PartialEq)]
+#[inspect(constructible)]
+struct Pair(u8, Option<u64>);
+
+#[derive(TypeInfo, Debug, PartialEq)]
+#[inspect(constructible)]
+struct Marker;
+
+#[derive(TypeInfo, Debug, PartialEq)]
+#[inspect(constructible)]
+enum Event {
+  Started,
+  Moved(i32, i32),
+  Renamed { from: String, to: String },
+}
+
+#[test]
+fn build_struct() {
+  let value = ValueBuilder::new(TypeInfo::of::<Fixture>())
+    .unwrap()
+    .with("tags", DynValue::new(vec!["a", "b"]))
+    .unwrap()
+    .with("name", DynValue::new("fixture".to_string()))
+    .unwrap()
+    .with("count", DynValue::new(3u16))
+    .unwrap()
+    .build()
+    .unwrap();
+
+  assert_eq!(
+    value.downcast::<Fixture>().ok(),
+    Some(Fixture {
+      name: "fixture".to_string(),
+      count: 3,
+      tags: vec!["a", "b"],
+    }),
+  );
+}
+
+#[test]
+fn build_tuple_struct_and_tuple() {
+  let mut builder = ValueBuilder::new(TypeInfo::of::<Pair>()).unwrap();
+  builder.push(DynValue::new(1u8)).unwrap();
+  builder.push(DynValue::new(Some(2u64))).unwrap();
+  assert_eq!(
+    builder.push(DynValue::new(3u8)),
+    Err(BuildError::TooManyFields {
+      type_name: TypeInfo::of::<Pair>().type_name(),
+    }),
+  );
+  let value = builder.build().unwrap();
+  assert_eq!(value.downcast::<Pair>().ok(), Some(Pair(1, Some(2))));
+
+  let value = ValueBuilder::new(TypeInfo::of::<(bool, char)>())
+    .unwrap()
+    .with("1", DynValue::new('c'))
+    .unwrap()
+    .with("0", DynValue::new(true))
+    .unwrap()
+    .build()
+    .unwrap();
+  assert_eq!(value.downcast::<(bool, char)>().ok(), Some((true, 'c')));
+
+  let value = ValueBuilder::new(TypeInfo::of::<Marker>())
+    .unwrap()
+    .build()
+    .unwrap();
+  assert_eq!(value.downcast::<Marker>().ok(), Some(Marker));
+}
+
+#[test]
+fn build_enum_variants() {
+  let type_info = TypeInfo::of::<Event>();
+
+  let started = ValueBuilder::variant(type_info, "Started")
+    .unwrap()
+    .build()
+    .unwrap();
+  assert_eq!(started.downcast::<Event>().ok(), Some(Event::Started));
+
+  let moved = ValueBuilder::variant(type_info, "Moved")
+    .unwrap()
+    .with("0", DynValue::new(-1i32))
+    .unwrap()
+    .with("1", DynValue::new(1i32))
+    .unwrap()
+    .build()
+    .unwrap();
+  assert_eq!(moved.downcast::<Event>().ok(), Some(Event::Moved(-1, 1)));
+
+  let renamed = ValueBuilder::variant(type_info, "Renamed")
+    .unwrap()
+    .with("to", DynValue::new("new".to_string()))
+    .unwrap()
+    .with("from", DynValue::new("old".to_string()))
+    .unwrap()
+    .build()
+    .unwrap();
+  assert_eq!(
+    renamed.downcast::<Event>().ok(),
+    Some(Event::Renamed {
+      from: "old".to_string(),
+      to: "new".to_string(),
+    }),
+  );
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Sealed {
+  nonzero: u8,
+}
+
+#[test]
+fn build_requires_opting_in() {
+  let type_info = TypeInfo::of::<Sealed>();
+  let error = ValueBuilder::new(type_info).err().unwrap();
+  assert_eq!(
+    error,
+    BuildError::NotConstructible {
+      type_name: type_info.type_name(),
+    },
+  );
+  assert!(error.to_string().ends_with("`#[inspect(constructible)]`"));
+  assert!(!type_info.is_constructible());
+  assert!(TypeInfo::of::<Fixture>().is_constructible());
+  assert!(TypeInfo::of::<(u8, String)>().is_constructible());
+}
+
+#[test]
+fn build_rejects_incomplete_values() {
+  let mut builder = ValueBuilder::new(TypeInfo::of::<Fixture>()).unwrap();
+  builder.set("count", DynValue::new(1u16)).unwrap();
+
+  let error = builder.build().err().unwrap();
+  assert_eq!(
+    error,
+    BuildError::MissingFields {
+      type_name: TypeInfo::of::<Fixture>().type_name(),
+      field_names: vec!["name".to_string(), "tags".to_string()],
+    },
+  );
+  assert!(error.to_string().ends_with("`name`, `tags`"));
+}
+
+#[test]
+fn build_rejects_mismatched_fields() {
+  let mut builder = ValueBuilder::new(TypeInfo::of::<Fixture>()).unwrap();
+  assert!(matches!(
+    builder.set("count", DynValue::new(1u32)),
+    Err(BuildError::MismatchedType { .. }),
+  ));
+  assert!(matches!(
+    builder.set("missing", DynValue::new(1u16)),
+    Err(BuildError::UnknownField { .. }),
+  ));
+  assert!(matches!(
+    ValueBuilder::variant(TypeInfo::of::<Event>(), "Stopped"),
+    Err(BuildError::UnknownVariant { .. }),
+  ));
+  assert!(matches!(
+    ValueBuilder::new(TypeInfo::of::<Event>()),
+    Err(BuildError::NotAStruct { .. }),
+  ));
+}
@@ -60,6 +60,7 @@ assert_type_id_matches!(type ref_mut_u8 = &'static mut u8);
 assert_type_id_matches!(type PhantomData_u8 = ::core::marker::PhantomData<u8>);
 
 assert_type_id_matches!(type slice_u8 = [u8]);
+assert_type_id_matches!(type array_u8 = [u8; 4]);
 
 #[test]
 fn type_id_matches_ref_u8_non_static() {
@@ -0,0 +1,289 @@
+use {
+  ::inspect::{
+    bytes::{
+      self, DecodeError, Decoded, Endian, PackError, PrimitiveValue, MAX_DEPTH,
+    },
+    TypeInfo,
+  },
+  ::std::collections::BTreeMap,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+#[repr(C)]
+struct Point {
+  x: i8,
+  y: i16,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+#[repr(i8)]
+enum Shape {
+  #[default]
+  Empty = -1,
+  Line {
+    from: Point,
+    to: Point,
+  },
+  Dot(Point),
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Flags {
+  on: bool,
+  codes: [u16; 2],
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Unordered {
+  a: u8,
+  b: u32,
+}
+
+#[test]
+fn decode_little_and_big_endian() {
+  let little = [0xff, 0, 0x02, 0x01];
+  let point =
+    bytes::decode(&little, TypeInfo::of::<Point>(), Endian::Little).unwrap();
+  assert_eq!(point.to_string(), "Point { x: -1, y: 258 }");
+  assert_eq!(
+    point.field("y").and_then(Decoded::primitive),
+    Some(PrimitiveValue::i16(258))
+  );
+
+  let big = [1, 0, 0x12, 0x34, 0, 0x01];
+  let flags =
+    bytes::decode(&big, TypeInfo::of::<Flags>(), Endian::Big).unwrap();
+  assert_eq!(flags.to_string(), "Flags { on: true, codes: [4660, 1] }");
+  assert_eq!(
+    flags.field("codes").and_then(Decoded::items).unwrap().len(),
+    2
+  );
+}
+
+#[test]
+fn decode_enum_variants() {
+  let shape = TypeInfo::of::<Shape>();
+  let size = shape.size().unwrap();
+
+  let mut data = vec![0; size];
+  data[0] = 0xff;
+  let empty = bytes::decode(&data, shape, Endian::Little).unwrap();
+  assert_eq!(empty.to_string(), "Empty");
+
+  let offset = shape
+    .variant("Dot")
+    .unwrap()
+    .fields()
+    .next()
+    .unwrap()
+    .offset;
+  data[0] = 1;
+  data[offset] = 7;
+  data[offset + 2] = 9;
+  let dot = bytes::decode(&data, shape, Endian::Little).unwrap();
+  assert_eq!(dot.to_string(), "Dot(Point { x: 7, y: 9 })");
+  assert_eq!(
+    dot.field("0").and_then(|point| point.field("x")),
+    Some(&Decoded::Primitive(PrimitiveValue::i8(7)))
+  );
+}
+
+#[test]
+fn decode_invalid_values() {
+  let shape = TypeInfo::of::<Shape>();
+  let mut data = vec![0; shape.size().unwrap()];
+  data[0] = 5;
+  assert_eq!(
+    bytes::decode(&data, shape, Endian::Little),
+    Err(DecodeError::UnknownDiscriminant {
+      type_name: shape.type_name(),
+      offset: 0,
+      discriminant: PrimitiveValue::i8(5),
+    })
+  );
+
+  let flags = TypeInfo::of::<Flags>();
+  assert_eq!(
+    bytes::decode(&[2, 0, 0, 0, 0, 0], flags, Endian::Little),
+    Err(DecodeError::InvalidValue {
+      type_name: "bool",
+      offset: 0,
+    })
+  );
+  assert_eq!(
+    bytes::decode(&[0; 4], flags, Endian::Little),
+    Err(DecodeError::Length {
+      type_name: flags.type_name(),
+      expected: 6,
+      found: 4,
+    })
+  );
+}
+
+#[test]
+fn decode_unsupported_types() {
+  let error =
+    bytes::decode(&[0; 8], TypeInfo::of::<Unordered>(), Endian::NATIVE)
+      .unwrap_err();
+  assert!(matches!(error, DecodeError::Unsupported { .. }));
+  assert!(error.to_string().contains("`#[repr(C)]`"));
+
+  let error = bytes::decode(
+    &[0; size_of::<&u8>()],
+    TypeInfo::of::<&u8>(),
+    Endian::NATIVE,
+  )
+  .unwrap_err();
+  assert!(matches!(error, DecodeError::Unsupported { .. }));
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Debug, Clone, PartialEq, Default)]
+#[inspect(constructible)]
+enum Input {
+  #[default]
+  Idle,
+  Move {
+    dx: i16,
+    dy: i16,
+  },
+  Say(String),
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Debug, Clone, PartialEq)]
+#[inspect(constructible)]
+struct Frame {
+  tick: u64,
+  inputs: Vec<Input>,
+  seed: Option<[u8; 2]>,
+  outcome: Result<bool, char>,
+  names: BTreeMap<u8, String>,
+  #[inspect(skip)]
+  cache: Vec<u8>,
+}
+
+#[test]
+fn pack_and_unpack_round_trip() {
+  let frame = Frame {
+    tick: 9,
+    inputs: vec![
+      Input::Move { dx: -1, dy: 2 },
+      Input::Idle,
+      Input::Say("gg".to_string()),
+    ],
+    seed: Some([4, 5]),
+    outcome: Err('x'),
+    names: BTreeMap::from([(1, "a".to_string())]),
+    cache: vec![1, 2, 3],
+  };
+  for endian in [Endian::Little, Endian::Big] {
+    let packed = bytes::pack(&frame, endian).unwrap();
+    let unpacked = bytes::unpack(&packed, TypeInfo::of::<Frame>(), endian)
+      .unwrap()
+      .downcast::<Frame>()
+      .ok()
+      .unwrap();
+    assert_eq!(
+      unpacked,
+      Frame {
+        cache: vec![],
+        ..frame.clone()
+      }
+    );
+  }
+}
+
+#[test]
+fn pack_layout() {
+  let input = Input::Move { dx: -1, dy: 2 };
+  assert_eq!(
+    bytes::pack(&input, Endian::Big).unwrap(),
+    [0, 0, 0, 1, 0xff, 0xff, 0, 2]
+  );
+  assert_eq!(
+    bytes::pack(&(Some('a'), "hi"), Endian::Little).unwrap(),
+    [1, 97, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, b'h', b'i']
+  );
+}
+
+#[test]
+fn unpack_errors() {
+  let input = TypeInfo::of::<Input>();
+  assert_eq!(
+    bytes::unpack(&[0, 0, 0, 7], input, Endian::Big).err(),
+    Some(PackError::UnknownVariant {
+      type_name: input.type_name(),
+      offset: 0,
+      index: 7,
+    })
+  );
+  assert_eq!(
+    bytes::unpack(&[0, 0, 0, 1, 0xff], input, Endian::Big).err(),
+    Some(PackError::UnexpectedEnd {
+      type_name: "i16",
+      offset: 4,
+    })
+  );
+  assert_eq!(
+    bytes::unpack(&[0, 0, 0, 0, 9], input, Endian::Big).err(),
+    Some(PackError::TrailingBytes {
+      offset: 4,
+      count: 1,
+    })
+  );
+  assert!(matches!(
+    bytes::unpack(&[2], TypeInfo::of::<bool>(), Endian::Big),
+    Err(PackError::InvalidValue { offset: 0, .. })
+  ));
+  assert_eq!(
+    bytes::unpack(&[0, 0, 0], TypeInfo::of::<Point>(), Endian::Big).err(),
+    Some(PackError::Unsupported {
+      type_name: TypeInfo::of::<Point>().type_name(),
+      reason: "it isn't marked `#[inspect(constructible)]`",
+    })
+  );
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Debug)]
+#[inspect(constructible)]
+struct List {
+  value: u8,
+  next: Option<Box<List>>,
+}
+
+#[test]
+fn unpack_untrusted_bytes() {
+  let list = TypeInfo::of::<List>();
+  let shallow = [0, 1, 1, 1, 2, 0];
+  assert!(bytes::unpack(&shallow, list, Endian::Little).is_ok());
+
+  let deep = [0, 1].repeat(200_000);
+  let error = bytes::unpack(&deep, list, Endian::Little).unwrap_err();
+  assert!(matches!(error, PackError::TooDeep { .. }), "{error}");
+  assert!(error.to_string().contains(&format!("{MAX_DEPTH} levels")));
+
+  let units = TypeInfo::of::<Vec<()>>();
+  assert_eq!(
+    bytes::unpack(&u64::MAX.to_le_bytes(), units, Endian::Little).err(),
+    Some(PackError::InvalidValue {
+      type_name: units.type_name(),
+      offset: 0,
+    })
+  );
+  let packed = bytes::pack(&vec![(); 3], Endian::Little).unwrap();
+  let mut padded = packed.clone();
+  padded.extend([0; 3]);
+  let mut rest = &padded[..];
+  let unpacked = bytes::unpack_from(&mut rest, units, Endian::Little);
+  assert_eq!(
+    unpacked.unwrap().downcast::<Vec<()>>().ok(),
+    Some(vec![(); 3])
+  );
+}
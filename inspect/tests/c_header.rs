@@ -0,0 +1,208 @@
+use {
+  ::core::marker::PhantomData,
+  ::inspect::{
+    c_header::{self, CHeader, CHeaderError},
+    TypeInfo,
+  },
+};
+
+/// A node in a linked list
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Node {
+  /// The next node, or null
+  next: *mut Node,
+  value: Value,
+  kind: Kind,
+  tags: [[u8; 3]; 2],
+  name: *const u8,
+  parent: Option<&'static Node>,
+  marker: PhantomData<String>,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+union Value {
+  whole: i64,
+  real: f64,
+  rgb: Rgb,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Clone, Copy)]
+#[repr(C, align(8))]
+struct Rgb(u8, u8, u8);
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+#[repr(i16)]
+enum Kind {
+  /// The first kind
+  #[default]
+  Leaf = -1,
+  Branch = 7,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(transparent)]
+struct Handle(*const Node);
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Bad {
+  ok: u32,
+  names: Vec<String>,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Keyword {
+  int: u32,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Outer {
+  inner: Inner,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Inner {
+  x: u8,
+}
+
+#[test]
+fn c_header_declarations() {
+  let header = c_header::generate::<Node>().unwrap();
+  assert!(header.starts_with("#pragma once\n"));
+  for line in [
+    "typedef struct Node Node;",
+    "typedef union Value Value;",
+    "typedef struct Rgb Rgb;",
+    "typedef int16_t Kind;",
+    "// A node in a linked list\nstruct Node {",
+    "  // The next node, or null\n  Node *next;",
+    "  Value value;",
+    "  Kind kind;",
+    "  uint8_t tags[2][3];",
+    "  const uint8_t *name;",
+    "  const Node *parent;",
+    "  _Alignas(8) uint8_t _0;",
+    "  // The first kind\n  Kind_Leaf = -1,\n  Kind_Branch = 7,",
+  ] {
+    assert!(header.contains(line), "missing {line:?} in:\n{header}");
+  }
+  assert!(!header.contains("marker"));
+}
+
+#[test]
+fn c_header_static_asserts() {
+  let header = c_header::generate::<Node>().unwrap();
+  let offset = ::core::mem::offset_of!(Node, kind);
+  for line in [
+    format!(
+      "_Static_assert(sizeof(Node) == {}, \"size of Node\");",
+      size_of::<Node>()
+    ),
+    "_Static_assert(_Alignof(Rgb) == 8, \"alignment of Rgb\");".to_string(),
+    format!(
+      "_Static_assert(offsetof(Node, kind) == {offset}, \"offset of \
+       Node.kind\");"
+    ),
+    "_Static_assert(offsetof(Value, rgb) == 0, \"offset of Value.rgb\");"
+      .to_string(),
+  ] {
+    assert!(header.contains(&line), "missing {line:?} in:\n{header}");
+  }
+}
+
+#[test]
+fn c_header_transparent() {
+  let mut header = CHeader::new();
+  header.add::<Handle>().unwrap().add::<Node>().unwrap();
+  let header = header.to_string();
+  assert!(header.contains("typedef const Node *Handle;"));
+  // types are only declared once
+  assert_eq!(header.matches("struct Node {").count(), 1);
+}
+
+#[test]
+fn c_header_rejects_incompatible_layouts() {
+  assert_eq!(
+    c_header::generate::<Bad>(),
+    Err(CHeaderError::Field {
+      type_name: ::core::any::type_name::<Bad>(),
+      field_name: "names".to_string(),
+      field_type: ::core::any::type_name::<Vec<String>>(),
+      reason: "its layout is unspecified",
+    })
+  );
+
+  let err = c_header::generate::<Outer>().unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    format!(
+      "field `inner` of `{}`: `{}` can't be declared in C: it doesn't have \
+       `#[repr(C)]`, so its layout is unspecified",
+      ::core::any::type_name::<Outer>(),
+      ::core::any::type_name::<Inner>()
+    )
+  );
+
+  assert_eq!(
+    c_header::generate::<Keyword>().unwrap_err().to_string(),
+    format!(
+      "`int`, in `{}`, is a keyword in C",
+      ::core::any::type_name::<Keyword>()
+    )
+  );
+
+  // the header is left as it was
+  let mut header = CHeader::new();
+  assert!(header.add::<Outer>().is_err());
+  assert_eq!(header.to_string(), CHeader::new().to_string());
+}
+
+#[test]
+fn c_header_opaque_pointers() {
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  #[repr(C)]
+  struct Handles {
+    inner: *const Inner,
+    names: *mut Vec<String>,
+    node: *const Node,
+  }
+
+  let err = c_header::generate::<Handles>().unwrap_err();
+  assert_eq!(
+    err,
+    CHeaderError::Nested {
+      type_name: ::core::any::type_name::<Handles>(),
+      field_name: "inner".to_string(),
+      error: Box::new(CHeaderError::Type {
+        type_name: ::core::any::type_name::<Inner>(),
+        reason: "it doesn't have `#[repr(C)]`, so its layout is unspecified",
+      }),
+    }
+  );
+
+  let mut header = CHeader::new();
+  header.opaque_pointers(true).add::<Handles>().unwrap();
+  let header = header.to_string();
+  for line in [
+    "  const void *inner;",
+    "  void *names;",
+    "  const Node *node;",
+  ] {
+    assert!(header.contains(line), "missing {line:?} in:\n{header}");
+  }
+  assert!(!header.contains("Inner"));
+}
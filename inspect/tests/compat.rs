@@ -0,0 +1,193 @@
+use ::inspect::schema::{ChangeKind, Encoding, Report, Schema};
+
+mod v1 {
+  use ::inspect::TypeInfo;
+
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  pub struct Message {
+    pub id: u32,
+    pub body: String,
+    pub tags: Vec<Tag>,
+    pub priority: u8,
+    pub next: Option<Box<Message>>,
+  }
+
+  #[allow(unused)]
+  #[derive(TypeInfo, Default)]
+  pub enum Tag {
+    #[default]
+    Urgent,
+    Later,
+    Custom(String),
+    Removed,
+  }
+}
+
+mod v2 {
+  use ::inspect::TypeInfo;
+
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  pub struct Message {
+    pub id: u64,
+    pub tags: Vec<Tag>,
+    pub content: String,
+    #[inspect(rename = "priority")]
+    pub importance: u8,
+    pub next: Option<Box<Message>>,
+    pub sender: Option<String>,
+  }
+
+  #[allow(unused)]
+  #[derive(TypeInfo, Default)]
+  pub enum Tag {
+    #[default]
+    Urgent,
+    Eventually,
+    Custom(String),
+    Archived {
+      at: u64,
+    },
+  }
+}
+
+mod v3 {
+  use ::inspect::TypeInfo;
+
+  #[allow(unused)]
+  #[derive(TypeInfo, Default)]
+  pub enum Tag {
+    #[default]
+    Urgent,
+    Later,
+    Custom(String),
+    Removed,
+    Snoozed,
+  }
+}
+
+/// The changes, other than to the layout, with their compatibility
+fn changes(report: &Report) -> Vec<String> {
+  report
+    .changes
+    .iter()
+    .filter(|change| {
+      !matches!(
+        change.kind,
+        ChangeKind::SizeChanged { .. }
+          | ChangeKind::AlignChanged { .. }
+          | ChangeKind::OffsetChanged { .. }
+      )
+    })
+    .map(ToString::to_string)
+    .collect()
+}
+
+#[test]
+fn compat_named() {
+  let old = Schema::of::<v1::Message>();
+  let new = Schema::of::<v2::Message>();
+  let report = old.compare(&new, Encoding::Named);
+  assert_eq!(
+    changes(&report),
+    [
+      "Message.id: type changed from `u32` to `u64` (backward compatible)",
+      "Message.tags[]::Eventually: variant renamed from `Later` to \
+       `Eventually` (incompatible)",
+      "Message.tags[]::Archived: variant added (backward compatible)",
+      "Message.tags[]::Removed: variant removed (forward compatible)",
+      "Message.content: field renamed from `body` to `content` (compatible)",
+      "Message.sender: field added (compatible)",
+      "Message.tags: field moved from position 2 to 1 (compatible)",
+      "Message.content: field moved from position 1 to 2 (compatible)",
+    ]
+  );
+  assert!(!report.is_backward_compatible());
+  assert!(!report.is_forward_compatible());
+  assert!(report.changes.contains(&::inspect::schema::Change {
+    path: "Message".to_string(),
+    kind: ChangeKind::SizeChanged {
+      old: Some(size_of::<v1::Message>()),
+      new: Some(size_of::<v2::Message>()),
+    },
+    backward: true,
+    forward: true,
+  }));
+}
+
+#[test]
+fn compat_positional_and_memory() {
+  let old = Schema::of::<v1::Message>();
+  let new = Schema::of::<v2::Message>();
+  let report = old.compare(&new, Encoding::Positional);
+  assert_eq!(
+    changes(&report),
+    [
+      "Message.id: type changed from `u32` to `u64` (incompatible)",
+      "Message.tags[]::Eventually: variant renamed from `Later` to \
+       `Eventually` (compatible)",
+      // it has the index of the removed variant
+      "Message.tags[]::Archived: variant added (incompatible)",
+      "Message.tags[]::Removed: variant removed (incompatible)",
+      "Message.content: field renamed from `body` to `content` (compatible)",
+      "Message.sender: field added (incompatible)",
+      "Message.tags: field moved from position 2 to 1 (incompatible)",
+      "Message.content: field moved from position 1 to 2 (incompatible)",
+    ]
+  );
+
+  let report = old.compare(&new, Encoding::Memory);
+  assert!(report
+    .changes
+    .iter()
+    .filter(|change| matches!(change.kind, ChangeKind::OffsetChanged { .. }))
+    .all(|change| !change.backward && !change.forward));
+}
+
+#[test]
+fn compat_unchanged() {
+  let schema = Schema::of::<v2::Message>();
+  for encoding in [Encoding::Named, Encoding::Positional, Encoding::Memory] {
+    let report = schema.compare(&schema, encoding);
+    assert_eq!(report.changes, []);
+    assert!(report.is_backward_compatible() && report.is_forward_compatible());
+  }
+
+  // appending a variant only stops old builds reading new values
+  let report = Schema::of::<Option<v1::Tag>>()
+    .compare(&Schema::of::<Option<v3::Tag>>(), Encoding::Positional);
+  assert_eq!(report.changes.len(), 1);
+  assert_eq!(report.changes[0].path, "Option<Tag>?::Snoozed");
+  assert_eq!(report.changes[0].kind, ChangeKind::VariantAdded);
+  assert!(report.is_backward_compatible());
+  assert!(!report.is_forward_compatible());
+}
+
+#[test]
+fn compat_malformed_schemas() {
+  let old = Schema::of::<v1::Message>();
+  let mut new = Schema::of::<v2::Message>();
+  new.types.remove("Tag");
+  let report = old.compare(&new, Encoding::Named);
+  let missing = report
+    .changes
+    .iter()
+    .find(|change| matches!(change.kind, ChangeKind::MissingType { .. }))
+    .unwrap();
+  assert_eq!(
+    missing.to_string(),
+    "Message.tags[]: type `Tag` isn't defined in the schema (incompatible)"
+  );
+  assert!(!report.is_backward_compatible());
+
+  new.types.clear();
+  assert!(new.root_def().is_none());
+  let report = old.compare(&new, Encoding::Positional);
+  assert_eq!(
+    report.changes[0].kind,
+    ChangeKind::MissingType {
+      name: "Message".to_string()
+    }
+  );
+}
@@ -0,0 +1,135 @@
+use ::inspect::TypeInfo;
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct NotDebug {
+  name: String,
+  scores: Vec<(u8, f32)>,
+  parent: Option<Box<NotDebug>>,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Secret(&'static str, #[inspect(skip)] u64);
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Account {
+  user: &'static str,
+  #[inspect(skip)]
+  password: &'static str,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+enum MyEnum {
+  One,
+  Two(Vec<u8>, char),
+  Three { f0: Option<bool>, f1: (i16, i16) },
+}
+
+#[test]
+fn debug_struct() {
+  let value = NotDebug {
+    name: "child".to_string(),
+    scores: vec![(1, 0.5), (2, 1.0)],
+    parent: Some(Box::new(NotDebug {
+      name: "parent".to_string(),
+      scores: vec![],
+      parent: None,
+    })),
+  };
+
+  assert_eq!(
+    format!("{:?}", inspect::debug(&value)),
+    "NotDebug { name: \"child\", scores: [(1, 0.5), (2, 1.0)], parent: \
+     Some(NotDebug { name: \"parent\", scores: [], parent: None }) }",
+  );
+}
+
+#[test]
+fn debug_struct_alternate() {
+  let value = Account {
+    user: "ickk",
+    password: "hunter2",
+  };
+
+  assert_eq!(
+    format!("{:#?}", inspect::debug(&value)),
+    "Account {\n    user: \"ickk\",\n    ..\n}",
+  );
+}
+
+#[test]
+fn debug_skips_fields() {
+  assert_eq!(
+    format!("{:?}", inspect::debug(&Secret("visible", 42))),
+    "Secret(\"visible\", ..)",
+  );
+  assert_eq!(
+    format!(
+      "{:?}",
+      inspect::debug(&Account {
+        user: "ickk",
+        password: "hunter2",
+      })
+    ),
+    "Account { user: \"ickk\", .. }",
+  );
+}
+
+#[test]
+fn debug_enum() {
+  assert_eq!(format!("{:?}", inspect::debug(&MyEnum::One)), "One");
+  assert_eq!(
+    format!("{:?}", inspect::debug(&MyEnum::Two(vec![1, 2], 'x'))),
+    "Two([1, 2], 'x')",
+  );
+  assert_eq!(
+    format!(
+      "{:?}",
+      inspect::debug(&MyEnum::Three {
+        f0: Some(true),
+        f1: (-1, 1),
+      })
+    ),
+    "Three { f0: Some(true), f1: (-1, 1) }",
+  );
+}
+
+#[test]
+fn debug_unsized() {
+  let slice: &[&str] = &["a", "b"];
+  assert_eq!(format!("{:?}", inspect::debug(slice)), "[\"a\", \"b\"]");
+  assert_eq!(format!("{:?}", inspect::debug("str")), "\"str\"");
+}
+
+#[test]
+fn debug_maps() {
+  let map = ::std::collections::BTreeMap::from([(1u8, "one"), (2, "two")]);
+  assert_eq!(
+    format!("{:?}", inspect::debug(&map)),
+    "{1: \"one\", 2: \"two\"}"
+  );
+}
+
+#[test]
+fn debug_raw_pointers_are_not_followed() {
+  let value = 5u8;
+  let pointer: *const u8 = &value;
+  assert_eq!(
+    format!("{:?}", inspect::debug(&pointer)),
+    format!("{pointer:p}"),
+  );
+}
+
+#[test]
+fn debug_depth() {
+  let value = Some(Some(Some(1u8)));
+  assert_eq!(format!("{:.0?}", inspect::debug(&value)), "..");
+  assert_eq!(format!("{:.2?}", inspect::debug(&value)), "Some(Some(..))");
+  assert_eq!(
+    format!("{:.3?}", inspect::debug(&value)),
+    "Some(Some(Some(1)))",
+  );
+}
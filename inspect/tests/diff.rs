@@ -0,0 +1,86 @@
+use {
+  ::inspect::{path::FieldPath, value::ValueRef, Change, Diff, TypeInfo},
+  ::std::collections::HashMap,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+enum Shape {
+  #[default]
+  Empty,
+  Circle {
+    radius: f32,
+  },
+  Square(f32),
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Drawing {
+  title: String,
+  shapes: Vec<Shape>,
+  layers: HashMap<String, u8>,
+  saved: Result<u32, String>,
+  #[inspect(skip)]
+  revision: u64,
+}
+
+fn drawing() -> Drawing {
+  Drawing {
+    title: "plan".to_string(),
+    shapes: vec![Shape::Circle { radius: 1.0 }, Shape::Square(2.0)],
+    layers: HashMap::from([("base".to_string(), 0), ("top".to_string(), 1)]),
+    saved: Ok(1),
+    revision: 0,
+  }
+}
+
+#[test]
+fn diff_of_equal_values_is_empty() {
+  let mut other = drawing();
+  other.revision = 5;
+  let diff = inspect::diff(&drawing(), &other);
+  assert!(diff.is_empty());
+  assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn diff_lists_changes_by_path() {
+  let mut new = drawing();
+  new.title = "final plan".to_string();
+  new.shapes[0] = Shape::Circle { radius: 1.5 };
+  new.shapes[1] = Shape::Empty;
+  new.shapes.push(Shape::Square(3.0));
+  new.layers.remove("top");
+  new.layers.insert("base".to_string(), 2);
+  new.layers.insert("notes".to_string(), 3);
+  new.saved = Err("disk full".to_string());
+
+  assert_eq!(
+    inspect::diff(&drawing(), &new).to_string(),
+    "title: \"plan\" -> \"final plan\"\n\
+     shapes[0]::Circle.radius: 1.0 -> 1.5\n\
+     shapes[1]: Square(2.0) -> Empty\n\
+     shapes[2]: added Square(3.0)\n\
+     layers[\"base\"]: 0 -> 2\n\
+     layers[\"notes\"]: added 3\n\
+     layers[\"top\"]: removed 1\n\
+     saved: Ok(1) -> Err(\"disk full\")\n"
+  );
+}
+
+#[test]
+fn diff_of_different_types_replaces_the_root() {
+  let diff = Diff::new(ValueRef::new(&1u8), ValueRef::new(&1u16));
+  assert_eq!(
+    diff.changes,
+    [Change {
+      path: FieldPath::new(),
+      old: Some("1".to_string()),
+      new: Some("1".to_string()),
+    }]
+  );
+
+  let diff = inspect::diff(&Some((1, 'a')), &Some((1, 'b')));
+  assert_eq!(diff.to_string(), "Some.1: 'a' -> 'b'\n");
+}
@@ -0,0 +1,84 @@
+use {
+  ::inspect::{dot, TypeInfo},
+  ::std::collections::HashMap,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Tree {
+  label: String,
+  children: Vec<Tree>,
+  parent: Option<Box<Tree>>,
+  #[inspect(skip)]
+  cache: Option<Box<Tree>>,
+  shape: Shape,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+enum Shape {
+  Leaf,
+  Weighted(f32),
+  Pair {
+    left: u8,
+    right: HashMap<String, f32>,
+  },
+}
+
+#[test]
+fn dot_nodes_and_edges() {
+  let graph = dot::render::<Tree>();
+  assert_eq!(
+    graph.lines().take(10).collect::<Vec<_>>(),
+    [
+      "digraph {",
+      "  node [shape=box];",
+      &format!(
+        "  t0 [label=\"Tree\\nsize {}, align {}\"];",
+        size_of::<Tree>(),
+        align_of::<Tree>()
+      ),
+      "  t0 -> t1 [label=\"label\"];",
+      "  t0 -> t2 [label=\"children\"];",
+      "  t0 -> t3 [label=\"parent\"];",
+      "  t0 -> t3 [label=\"cache\", style=dashed];",
+      "  t0 -> t4 [label=\"shape\"];",
+      &format!(
+        "  t1 [label=\"String\\nsize {}, align {}\"];",
+        size_of::<String>(),
+        align_of::<String>()
+      ),
+      &format!(
+        "  t2 [label=\"Vec<Tree>\\nsize {}, align {}\"];",
+        size_of::<Vec<Tree>>(),
+        align_of::<Vec<Tree>>()
+      ),
+    ]
+  );
+  assert!(graph.ends_with("}\n"));
+}
+
+#[test]
+fn dot_recursive_types() {
+  let graph = dot::render::<Tree>();
+  // every type has one node
+  assert_eq!(graph.matches("label=\"Tree\\n").count(), 1);
+  assert_eq!(graph.matches("label=\"Option<Box<Tree>>\\n").count(), 1);
+  // and the cycles lead back to it
+  assert!(graph.contains("  t2 -> t0 [label=\"item\"];\n"));
+  assert!(graph.contains("[label=\"target\"];\n"));
+  assert_eq!(graph.matches(" -> t0 ").count(), 2);
+}
+
+#[test]
+fn dot_enum_variants() {
+  let graph = dot::render::<Shape>();
+  assert!(graph.contains("  t0 -> t1 [label=\"Weighted::0\"];\n"));
+  assert!(graph.contains("  t0 -> t2 [label=\"Pair::left\"];\n"));
+  assert!(graph.contains("  t0 -> t3 [label=\"Pair::right\"];\n"));
+  assert!(graph.contains("  t3 -> t4 [label=\"key\"];\n"));
+  // `f32` already has a node
+  assert!(graph.contains("  t3 -> t1 [label=\"value\"];\n"));
+  assert!(!graph.contains("Leaf"));
+  assert!(graph.contains("  t4 [label=\"String\\nsize "));
+}
@@ -0,0 +1,135 @@
+use {
+  ::core::sync::atomic::{AtomicUsize, Ordering},
+  ::inspect::{
+    value::{DynValue, SendDynValue},
+    TypeInfo,
+  },
+};
+
+#[derive(TypeInfo)]
+struct Counted(u8);
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+impl Drop for Counted {
+  fn drop(&mut self) {
+    DROPPED.fetch_add(self.0 as usize, Ordering::Relaxed);
+  }
+}
+
+#[derive(TypeInfo, Debug, PartialEq)]
+struct Payload {
+  id: u32,
+  tags: Vec<String>,
+  unit: (),
+}
+
+#[test]
+fn dyn_value_drops() {
+  // the only test that touches `DROPPED`
+  let value = DynValue::new(Counted(1));
+  drop(value);
+  assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+
+  let value = DynValue::new(vec![Counted(2), Counted(3)]);
+  drop(value);
+  assert_eq!(DROPPED.load(Ordering::Relaxed), 6);
+
+  let value = DynValue::new(Counted(4));
+  let counted = value.downcast::<Counted>().ok().unwrap();
+  assert_eq!(DROPPED.load(Ordering::Relaxed), 6);
+  drop(counted);
+  assert_eq!(DROPPED.load(Ordering::Relaxed), 10);
+}
+
+#[test]
+fn dyn_value_downcast() {
+  let value = DynValue::new(Payload {
+    id: 1,
+    tags: vec!["a".to_string()],
+    unit: (),
+  });
+  assert!(value.is::<Payload>());
+  assert!(value.downcast_ref::<u32>().is_none());
+
+  let value = value.downcast::<u32>().unwrap_err();
+  assert_eq!(
+    value.downcast::<Payload>().ok(),
+    Some(Payload {
+      id: 1,
+      tags: vec!["a".to_string()],
+      unit: (),
+    }),
+  );
+}
+
+#[test]
+fn dyn_value_zero_sized() {
+  let mut value = DynValue::new(());
+  assert_eq!(value.downcast_mut::<()>(), Some(&mut ()));
+  assert_eq!(format!("{value:?}"), "()");
+}
+
+#[test]
+fn dyn_value_fields() {
+  let mut value = DynValue::new(Payload {
+    id: 1,
+    tags: vec![],
+    unit: (),
+  });
+
+  let id = value.field("id").unwrap();
+  assert_eq!(id.type_info().type_id(), TypeInfo::of::<u32>().type_id());
+  assert!(value.field("missing").is_none());
+
+  let mut tags = value.field_mut("tags").unwrap();
+  unsafe { &mut *tags.as_mut_ptr().cast::<Vec<String>>() }
+    .push("new".to_string());
+
+  assert_eq!(
+    format!("{value:?}"),
+    "Payload { id: 1, tags: [\"new\"], unit: () }",
+  );
+}
+
+#[test]
+fn dyn_value_tuple_fields() {
+  let value = DynValue::new((1u8, "two"));
+  assert_eq!(
+    format!("{:?}", inspect::DebugValue::new(value.field("1").unwrap())),
+    "\"two\""
+  );
+  assert!(value.field("2").is_none());
+}
+
+#[test]
+fn dyn_value_send() {
+  let values = vec![
+    SendDynValue::new(Payload {
+      id: 1,
+      tags: vec!["a".to_string()],
+      unit: (),
+    }),
+    SendDynValue::new(2u64),
+  ];
+  let values = ::std::thread::spawn(move || {
+    assert_eq!(
+      values[0].field("id").unwrap().type_info().type_name(),
+      "u32"
+    );
+    values
+  })
+  .join()
+  .unwrap();
+
+  let mut values = values.into_iter().map(SendDynValue::into_inner);
+  assert_eq!(
+    values.next().unwrap().downcast::<Payload>().ok(),
+    Some(Payload {
+      id: 1,
+      tags: vec!["a".to_string()],
+      unit: (),
+    })
+  );
+  assert_eq!(values.next().unwrap().downcast::<u64>().ok(), Some(2));
+}
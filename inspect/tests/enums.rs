@@ -1,6 +1,9 @@
 use {
   ::core::any::TypeId,
-  ::inspect::{type_info::DiscriminantErased, TypeInfo},
+  ::inspect::{
+    type_info::{DiscriminantErased, Enum, EnumVariantInfo, Primitive},
+    TypeInfo,
+  },
 };
 
 #[allow(unused)]
@@ -19,6 +22,15 @@ enum EnumWithLifetime<'s, 't> {
   Three { f0: &'t str, f1: Vec<&'s [&'s str]> },
 }
 
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(i8)]
+enum ReprEnum {
+  Minus = -2,
+  Data(u8),
+  Struct { f0: u16 } = 40,
+}
+
 #[test]
 fn type_id_matches_enum() {
   assert_eq!(TypeInfo::of::<MyEnum>().type_id(), TypeId::of::<MyEnum>());
@@ -105,3 +117,35 @@ fn discriminants_hash() {
     )
   }
 }
+
+#[test]
+fn discriminant_values() {
+  let TypeInfo::Enum(Enum::Enum { variants, .. }) = TypeInfo::of::<ReprEnum>()
+  else {
+    panic!("expected an enum");
+  };
+  let values = variants
+    .variant_infos
+    .iter()
+    .map(|variant| match variant {
+      EnumVariantInfo::Unit {
+        variant_discriminant_value,
+        ..
+      }
+      | EnumVariantInfo::Tuple {
+        variant_discriminant_value,
+        ..
+      }
+      | EnumVariantInfo::Struct {
+        variant_discriminant_value,
+        ..
+      } => variant_discriminant_value.map(|value| value as isize),
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(values, [Some(-2), Some(-1), Some(40)]);
+
+  let repr = TypeInfo::of::<ReprEnum>().repr().unwrap();
+  assert_eq!(repr.primitive, Some(Primitive::i8));
+  assert!(!repr.c);
+  assert_eq!(TypeInfo::of::<MyEnum>().repr().unwrap().primitive, None);
+}
@@ -0,0 +1,109 @@
+use ::inspect::TypeInfo;
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Point {
+  x: i32,
+  y: i32,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct List {
+  value: u8,
+  next: Option<Box<List>>,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+enum Shape {
+  #[default]
+  Empty,
+  Circle(f32),
+  Rect {
+    width: f32,
+    height: f32,
+  },
+}
+
+mod changed {
+  use ::inspect::TypeInfo;
+
+  /// Fields in a different order
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  pub struct Point {
+    pub y: i32,
+    pub x: i32,
+  }
+
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  pub struct List {
+    pub value: u16,
+    pub next: Option<Box<List>>,
+  }
+
+  /// A renamed variant
+  #[allow(unused)]
+  #[derive(TypeInfo, Default)]
+  pub enum Shape {
+    #[default]
+    Empty,
+    Round(f32),
+    Rect {
+      width: f32,
+      height: f32,
+    },
+  }
+}
+
+mod moved {
+  use ::inspect::TypeInfo;
+
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  pub struct Point {
+    pub x: i32,
+    pub y: i32,
+  }
+}
+
+#[test]
+fn fingerprint_is_stable() {
+  // it mustn't change between builds, or versions of this crate
+  assert_eq!(
+    TypeInfo::of::<Point>().fingerprint(),
+    0xc3a24f7dbfa8ba6107d3dd06efea06a0
+  );
+  assert_eq!(
+    TypeInfo::of::<Point>().fingerprint(),
+    TypeInfo::of::<moved::Point>().fingerprint()
+  );
+}
+
+#[test]
+fn fingerprint_changes_with_structure() {
+  let pairs = [
+    (TypeInfo::of::<Point>(), TypeInfo::of::<changed::Point>()),
+    (TypeInfo::of::<List>(), TypeInfo::of::<changed::List>()),
+    (TypeInfo::of::<Shape>(), TypeInfo::of::<changed::Shape>()),
+    (TypeInfo::of::<Vec<u8>>(), TypeInfo::of::<Vec<i8>>()),
+    (TypeInfo::of::<[u8; 3]>(), TypeInfo::of::<[u8; 4]>()),
+    (TypeInfo::of::<&u8>(), TypeInfo::of::<&mut u8>()),
+  ];
+  for (before, after) in pairs {
+    assert_ne!(
+      before.fingerprint(),
+      after.fingerprint(),
+      "{before} and {after} have the same fingerprint"
+    );
+  }
+}
+
+#[test]
+fn fingerprint_recursive_types() {
+  let list = TypeInfo::of::<List>().fingerprint();
+  assert_eq!(list, TypeInfo::of::<List>().fingerprint());
+  assert_ne!(list, TypeInfo::of::<Option<Box<List>>>().fingerprint());
+}
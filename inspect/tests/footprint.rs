@@ -0,0 +1,114 @@
+use {
+  ::inspect::{value::ValueRef, FootprintOptions, TypeInfo},
+  ::std::collections::BTreeMap,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+enum Entry {
+  #[default]
+  Empty,
+  Text(String),
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Cache {
+  name: String,
+  entries: Vec<Entry>,
+  index: BTreeMap<u32, u64>,
+  #[inspect(skip)]
+  scratch: Vec<u8>,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Views<'a> {
+  first: &'a [u64; 4],
+  second: &'a [u64; 4],
+  owned: Box<[u64; 4]>,
+}
+
+#[test]
+fn footprint_breaks_down_fields() {
+  let mut entries = Vec::with_capacity(3);
+  entries.push(Entry::Text(String::with_capacity(10)));
+  entries.push(Entry::Empty);
+  let cache = Cache {
+    name: String::with_capacity(5),
+    entries,
+    index: BTreeMap::from([(1, 2), (3, 4)]),
+    scratch: Vec::with_capacity(100),
+  };
+  let footprint = inspect::footprint(&cache);
+
+  let entry_size = size_of::<Entry>();
+  let heaps = footprint
+    .fields
+    .iter()
+    .map(|field| (field.name.as_str(), field.footprint.heap))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    heaps,
+    [
+      ("name", 5),
+      ("entries", 3 * entry_size + 10),
+      ("index", 2 * 12),
+      ("scratch", 100),
+    ]
+  );
+  assert_eq!(footprint.inline, size_of::<Cache>());
+  assert_eq!(footprint.heap, 5 + 3 * entry_size + 10 + 24 + 100);
+}
+
+#[test]
+fn footprint_of_enums_and_options() {
+  let entry = Entry::Text("abc".to_string());
+  let footprint = inspect::footprint(&entry);
+  assert_eq!(footprint.variant, Some("Text"));
+  assert_eq!(footprint.fields[0].name, "0");
+  assert_eq!(footprint.heap, 3);
+
+  let option = Some(Box::new(7u32));
+  let footprint = inspect::footprint(&option);
+  assert_eq!(footprint.variant, Some("Some"));
+  assert_eq!(footprint.total(), size_of::<Option<Box<u32>>>() + 4);
+  assert_eq!(
+    footprint.to_string(),
+    format!(
+      "Option<Box<u32>>::Some = {0} bytes ({1} inline + 4 heap)\n\
+       \x20 0: Box<u32> = {0} bytes ({1} inline + 4 heap)\n",
+      size_of::<usize>() + 4,
+      size_of::<usize>(),
+    )
+  );
+}
+
+#[test]
+fn footprint_of_shared_references() {
+  let shared = [1u64; 4];
+  let views = Views {
+    first: &shared,
+    second: &shared,
+    owned: Box::new([2; 4]),
+  };
+
+  let footprint = inspect::footprint(&views);
+  assert_eq!(footprint.heap, 32);
+
+  let footprint = FootprintOptions::new()
+    .follow_references(true)
+    .footprint(ValueRef::new(&views));
+  assert_eq!(footprint.heap, 32 + 32);
+  assert_eq!(footprint.fields[0].footprint.heap, 32);
+  assert_eq!(footprint.fields[1].footprint.heap, 0);
+
+  let owned = Box::new([3u64; 4]);
+  let borrowed_first = (&*owned, &owned);
+  let footprint = FootprintOptions::new()
+    .follow_references(true)
+    .footprint(ValueRef::new(&borrowed_first));
+  assert_eq!(footprint.fields[0].footprint.heap, 32);
+  assert_eq!(footprint.fields[1].footprint.heap, 8);
+  assert_eq!(footprint.heap, 32 + 8);
+}
@@ -0,0 +1,102 @@
+use ::inspect::TypeInfo;
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+#[repr(C)]
+struct Point {
+  x: i8,
+  y: i16,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+#[repr(u8)]
+enum Shape {
+  #[default]
+  Empty,
+  Line {
+    from: Point,
+    to: Point,
+  },
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Record {
+  id: u128,
+  name: String,
+}
+
+#[test]
+fn hex_dump_arrays_and_padding() {
+  let points = [Point { x: -1, y: 258 }, Point { x: 5, y: -2 }];
+  assert_eq!(
+    inspect::hex_dump(&points).to_string(),
+    "[Point; 2]: 8 bytes\n\
+     \x20 0000  ff                       [0].x: i8 = -1\n\
+     \x20 0001  --                       <padding>\n\
+     \x20 0002  02 01                    [0].y: i16 = 258\n\
+     \x20 0004  05                       [1].x: i8 = 5\n\
+     \x20 0005  --                       <padding>\n\
+     \x20 0006  fe ff                    [1].y: i16 = -2\n"
+  );
+}
+
+#[test]
+fn hex_dump_enum_variants() {
+  let line = Shape::Line {
+    from: Point { x: 1, y: 2 },
+    to: Point { x: 3, y: 4 },
+  };
+  assert_eq!(
+    inspect::hex_dump(&line).to_string(),
+    "Shape: 10 bytes\n\
+     \x20 0000  ?? ??                    <Line>\n\
+     \x20 0002  01                       Line.from.x: i8 = 1\n\
+     \x20 0003  --                       <padding>\n\
+     \x20 0004  02 00                    Line.from.y: i16 = 2\n\
+     \x20 0006  03                       Line.to.x: i8 = 3\n\
+     \x20 0007  --                       <padding>\n\
+     \x20 0008  04 00                    Line.to.y: i16 = 4\n"
+  );
+  assert_eq!(
+    inspect::hex_dump(&Shape::Empty).to_string(),
+    "Shape: 10 bytes\n\
+     \x20 0000  ?? ?? ?? ?? ?? ?? ?? ??  <Empty>\n\
+     \x20 0008  ?? ??\n"
+  );
+}
+
+#[test]
+fn hex_dump_long_and_opaque_fields() {
+  let record = Record {
+    id: 0x0102030405060708090a0b0c0d0e0f10,
+    name: "ok".to_string(),
+  };
+  let dump = inspect::hex_dump(&record).to_string();
+  let lines = dump.lines().collect::<Vec<_>>();
+  assert_eq!(lines[0], format!("Record: {} bytes", size_of::<Record>()));
+  assert_eq!(
+    lines[1],
+    "  0000  10 0f 0e 0d 0c 0b 0a 09  id: u128 = \
+     1339673755198158349044581307228491536"
+  );
+  assert_eq!(lines[2], "  0008  08 07 06 05 04 03 02 01");
+  // the bytes of a `String` aren't read, but its value is
+  assert_eq!(
+    lines[3],
+    "  0010  ?? ?? ?? ?? ?? ?? ?? ??  name: String = \"ok\""
+  );
+  // `u128` is aligned to 16 bytes
+  assert_eq!(
+    lines.last().unwrap(),
+    &"  0028  -- -- -- -- -- -- -- --  <padding>"
+  );
+
+  let number = 7_u16;
+  assert_eq!(
+    inspect::hex_dump(&number).to_string(),
+    "u16: 2 bytes\n  0000  07 00                    u16 = 7\n"
+  );
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "json")]
+
+use {
+  ::inspect::{json_schema, TypeInfo},
+  ::serde_json::json,
+};
+
+/// A node in the scene
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+struct Node {
+  /// What the node is called
+  name: String,
+  #[inspect(rename = "z-index")]
+  depth: i8,
+  parent: Option<Box<Node>>,
+  children: Vec<Child>,
+  #[inspect(skip)]
+  cache: u64,
+  position: (f32, f32),
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+enum Child {
+  /// Nothing to see here
+  Empty,
+  Wrapped(Node),
+  Light {
+    intensity: u16,
+  },
+}
+
+#[test]
+fn json_schema_struct() {
+  let schema = json_schema::generate::<Node>();
+  assert_eq!(
+    schema["$schema"],
+    "https://json-schema.org/draft/2020-12/schema"
+  );
+  assert_eq!(schema["title"], "Node");
+  assert_eq!(schema["description"], "A node in the scene");
+  assert_eq!(schema["type"], "object");
+  assert_eq!(
+    schema["required"],
+    json!(["name", "z-index", "children", "position"])
+  );
+
+  let properties = &schema["properties"];
+  assert_eq!(
+    properties["name"],
+    json!({ "type": "string", "description": "What the node is called" })
+  );
+  assert_eq!(
+    properties["z-index"],
+    json!({ "type": "integer", "minimum": -128, "maximum": 127 })
+  );
+  assert_eq!(
+    properties["parent"],
+    json!({ "anyOf": [{ "$ref": "#" }, { "type": "null" }] })
+  );
+  assert_eq!(
+    properties["children"],
+    json!({ "type": "array", "items": { "$ref": "#/$defs/Child" } })
+  );
+  assert_eq!(properties["position"]["prefixItems"][1]["type"], "number");
+  assert!(properties.get("cache").is_none());
+}
+
+#[test]
+fn json_schema_enum() {
+  let schema = json_schema::generate::<Vec<Child>>();
+  assert_eq!(schema["items"], json!({ "$ref": "#/$defs/Child" }));
+  assert_eq!(
+    schema["$defs"]["Child"]["oneOf"],
+    json!([
+      { "const": "Empty", "description": "Nothing to see here" },
+      {
+        "type": "object",
+        "properties": { "Wrapped": { "$ref": "#/$defs/Node" } },
+        "required": ["Wrapped"],
+        "additionalProperties": false,
+      },
+      {
+        "type": "object",
+        "properties": {
+          "Light": {
+            "type": "object",
+            "properties": {
+              "intensity": { "type": "integer", "minimum": 0, "maximum": 65535 },
+            },
+            "required": ["intensity"],
+          },
+        },
+        "required": ["Light"],
+        "additionalProperties": false,
+      },
+    ])
+  );
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Wrapper<T> {
+  inner: T,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Inner<T> {
+  value: T,
+}
+
+#[allow(unused, non_camel_case_types)]
+#[derive(TypeInfo)]
+struct Inner_u8 {
+  value: u8,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Both {
+  nested: Wrapper<Inner<u8>>,
+  flat: Wrapper<Inner_u8>,
+}
+
+#[test]
+fn json_schema_distinct_def_names() {
+  let schema = json_schema::generate::<Both>();
+  let defs = schema["$defs"].as_object().unwrap();
+  assert_eq!(defs.len(), 4);
+  assert_eq!(
+    schema["properties"]["nested"],
+    json!({ "$ref": "#/$defs/Wrapper_Inner_u8" })
+  );
+  assert_eq!(
+    schema["properties"]["flat"],
+    json!({ "$ref": "#/$defs/Wrapper_Inner_u8_2" })
+  );
+  assert_eq!(
+    defs["Wrapper_Inner_u8_2"]["properties"]["inner"],
+    json!({ "$ref": "#/$defs/Inner_u8_2" })
+  );
+}
@@ -0,0 +1,224 @@
+use {
+  ::core::marker::PhantomData,
+  ::inspect::{
+    layout::{self, Hole, Row},
+    TypeInfo,
+  },
+};
+
+/// The compiler reorders the fields, so it has no padding itself
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Hot {
+  flag: bool,
+  position: u64,
+  tag: u8,
+  count: u32,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Inner {
+  a: u8,
+  b: u16,
+  c: u8,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Outer {
+  inner: Inner,
+  d: u32,
+  e: u8,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+#[repr(u8)]
+enum Command {
+  #[default]
+  Stop,
+  Move(u16),
+  Jump {
+    height: u32,
+  },
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Point(i16, i16);
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+#[repr(C)]
+struct Shape {
+  kind: Command,
+  points: [Point; 2],
+  marker: PhantomData<u64>,
+}
+
+#[test]
+fn layout_repr_rust() {
+  let analysis = layout::analyze::<Hot>();
+  assert_eq!(analysis.size, 16);
+  assert_eq!(analysis.padding(), 2);
+  assert_eq!(analysis.holes, [Hole { offset: 14, len: 2 }]);
+  // in offset order, which isn't the declared order
+  assert_eq!(analysis.fields[0].name, "position");
+  assert_eq!(analysis.fields[0].offset, 0);
+
+  // `#[repr(C)]` would put `position` after 7 bytes of padding
+  let reordering = analysis.reordering.unwrap();
+  assert_eq!(reordering.declared_size, 24);
+  assert_eq!(reordering.order, ["position", "count", "flag", "tag"]);
+  assert_eq!(reordering.size, 16);
+}
+
+#[test]
+fn layout_nested() {
+  let analysis = layout::analyze::<Outer>();
+  assert_eq!(analysis.size, 16);
+  assert_eq!(
+    analysis.holes,
+    [Hole { offset: 6, len: 2 }, Hole { offset: 13, len: 3 }]
+  );
+  assert_eq!(
+    analysis.fields[0].analysis.holes,
+    [Hole { offset: 1, len: 1 }, Hole { offset: 5, len: 1 }]
+  );
+  assert_eq!(analysis.padding(), 5);
+  assert_eq!(analysis.wasted(), 7);
+  let reordering = analysis.reordering.as_ref().unwrap();
+  assert_eq!(reordering.order, ["d", "inner", "e"]);
+  assert_eq!(reordering.size, 12);
+
+  assert_eq!(
+    analysis.to_string(),
+    "Outer: size 16, align 4, 5 bytes of padding\n\
+     \x20 0..6 inner: Inner\n\
+     \x20   0..1 a: u8\n\
+     \x20   1..2 (padding)\n\
+     \x20   2..4 b: u16\n\
+     \x20   4..5 c: u8\n\
+     \x20   5..6 (padding)\n\
+     \x20   with `#[repr(C)]`, the declared order would take 6 bytes, and \
+     `b, a, c` would take 4\n\
+     \x20 6..8 (padding)\n\
+     \x20 8..12 d: u32\n\
+     \x20 12..13 e: u8\n\
+     \x20 13..16 (padding)\n\
+     \x20 with `#[repr(C)]`, the declared order would take 16 bytes, and \
+     `d, inner, e` would take 12\n"
+  );
+  // nothing to gain from reordering primitives
+  assert_eq!(analysis.fields[1].analysis.reordering, None);
+}
+
+#[test]
+fn layout_enum_variants() {
+  let analysis = layout::analyze::<Command>();
+  assert_eq!(analysis.size, 8);
+  assert!(analysis.fields.is_empty());
+  let names = analysis
+    .variants
+    .iter()
+    .map(|variant| variant.name)
+    .collect::<Vec<_>>();
+  assert_eq!(names, ["Stop", "Move", "Jump"]);
+  assert_eq!(analysis.variants[0].holes, [Hole { offset: 0, len: 8 }]);
+  // the discriminant is in the first byte
+  assert_eq!(
+    analysis.variants[1].holes,
+    [Hole { offset: 0, len: 2 }, Hole { offset: 4, len: 4 }]
+  );
+  assert_eq!(analysis.variants[2].holes, [Hole { offset: 0, len: 4 }]);
+  assert_eq!(analysis.padding(), 4);
+  assert!(layout::analyze_type_info(TypeInfo::of::<[u8]>()).is_none());
+}
+
+#[test]
+fn layout_leaves() {
+  let leaves = layout::leaves::<Shape>()
+    .map(|leaf| (leaf.path, leaf.offset, leaf.size))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    leaves,
+    [
+      // enums aren't broken down, and `PhantomData` has no bytes
+      ("kind".to_string(), 0, 8),
+      ("points[0].0".to_string(), 8, 2),
+      ("points[0].1".to_string(), 10, 2),
+      ("points[1].0".to_string(), 12, 2),
+      ("points[1].1".to_string(), 14, 2),
+    ]
+  );
+
+  let leaf = layout::leaves::<u32>().collect::<Vec<_>>();
+  assert_eq!(leaf.len(), 1);
+  assert_eq!(leaf[0].path, "");
+  assert_eq!(leaf[0].type_info.type_name(), "u32");
+  assert!(layout::leaves_type_info(TypeInfo::of::<str>()).is_none());
+}
+
+#[test]
+fn layout_byte_map() {
+  let map = layout::byte_map::<Outer>();
+  assert_eq!(map.size, 16);
+  let rows = map
+    .rows
+    .iter()
+    .map(|row| match row {
+      Row::Field(leaf) => (row.offset(), row.len(), leaf.path.as_str()),
+      Row::Padding(_) => (row.offset(), row.len(), "<padding>"),
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(
+    rows,
+    [
+      (0, 1, "inner.a"),
+      (1, 1, "<padding>"),
+      (2, 2, "inner.b"),
+      (4, 1, "inner.c"),
+      (5, 3, "<padding>"),
+      (8, 4, "d"),
+      (12, 1, "e"),
+      (13, 3, "<padding>"),
+    ]
+  );
+  assert_eq!(map.to_string().lines().nth(5), Some("  5..8   <padding>"));
+}
+
+#[test]
+fn layout_arrays() {
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  #[repr(C)]
+  struct Batch {
+    items: [Inner; 4],
+    count: u16,
+  }
+
+  let analysis = layout::analyze::<Batch>();
+  assert_eq!(analysis.padding(), 0);
+  let items = analysis.fields[0].analysis.items.as_ref().unwrap();
+  assert_eq!(items.length, 4);
+  assert_eq!(items.analysis.padding(), 2);
+  assert_eq!(analysis.wasted(), 4 * 2);
+  assert_eq!(
+    analysis.to_string(),
+    "Batch: size 26, align 2, 0 bytes of padding\n\
+     \x20 0..24 items: [Inner; 4]\n\
+     \x20   [0..4]: Inner\n\
+     \x20     0..1 a: u8\n\
+     \x20     1..2 (padding)\n\
+     \x20     2..4 b: u16\n\
+     \x20     4..5 c: u8\n\
+     \x20     5..6 (padding)\n\
+     \x20     with `#[repr(C)]`, the declared order would take 6 bytes, and \
+     `b, a, c` would take 4\n\
+     \x20 24..26 count: u16\n"
+  );
+}
@@ -0,0 +1,224 @@
+use {
+  ::inspect::{
+    patch::{Patch, PatchError, PatchOp},
+    path::{FieldPath, PathError, Segment},
+    TypeInfo,
+  },
+  ::std::collections::HashMap,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo, Debug, Clone, PartialEq, Default)]
+#[inspect(constructible)]
+enum Tile {
+  #[default]
+  Floor,
+  Wall {
+    height: u8,
+  },
+  Door(Option<String>),
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Debug, Clone, PartialEq)]
+#[inspect(constructible)]
+struct Level {
+  name: String,
+  tiles: Vec<Tile>,
+  exit: Option<Box<(u16, u16)>>,
+}
+
+fn level() -> Level {
+  Level {
+    name: "cave".to_string(),
+    tiles: vec![
+      Tile::Floor,
+      Tile::Wall { height: 2 },
+      Tile::Door(Some("key".to_string())),
+      Tile::Floor,
+    ],
+    exit: Some(Box::new((3, 4))),
+  }
+}
+
+#[test]
+fn patch_from_diff_applies_and_undoes() {
+  let before = level();
+  let mut after = level();
+  after.tiles[1] = Tile::Wall { height: 5 };
+  after.tiles[2] = Tile::Door(None);
+  after.tiles.truncate(3);
+  after.tiles.remove(0);
+  after.exit.as_mut().unwrap().1 = 9;
+
+  let patch = Patch::diff(&before, &after).unwrap();
+  let paths = patch
+    .ops
+    .iter()
+    .map(|op| op.path().to_string())
+    .collect::<Vec<_>>();
+  assert_eq!(paths[0], "tiles[0]");
+  assert!(matches!(
+    patch.ops.last(),
+    Some(PatchOp::Set { path, .. }) if path.segments() == [
+      Segment::Field("exit".to_string()),
+      Segment::Variant("Some".to_string()),
+      Segment::Field("1".to_string()),
+    ]
+  ));
+
+  let mut level = before.clone();
+  patch.apply(&mut level).unwrap();
+  assert_eq!(level, after);
+  patch.inverse().apply(&mut level).unwrap();
+  assert_eq!(level, before);
+  assert!(Patch::diff(&before, &level).unwrap().is_empty());
+}
+
+#[test]
+fn patch_ops_switch_variants_and_edit_vecs() {
+  let tiles = |path: &[Segment]| {
+    [&[Segment::Field("tiles".to_string())][..], path]
+      .concat()
+      .into()
+  };
+  let patch = Patch {
+    ops: vec![
+      PatchOp::set(
+        tiles(&[Segment::Index(0)]),
+        &Tile::Floor,
+        &Tile::Wall { height: 1 },
+      )
+      .unwrap(),
+      PatchOp::set(
+        tiles(&[
+          Segment::Index(1),
+          Segment::Variant("Wall".to_string()),
+          Segment::Field("height".to_string()),
+        ]),
+        &2u8,
+        &3u8,
+      )
+      .unwrap(),
+      PatchOp::insert(tiles(&[]), 4, &Tile::Door(None)).unwrap(),
+      PatchOp::remove(tiles(&[]), 3, &Tile::Floor).unwrap(),
+    ],
+  };
+
+  let mut level = level();
+  patch.apply(&mut level).unwrap();
+  assert_eq!(
+    level.tiles,
+    [
+      Tile::Wall { height: 1 },
+      Tile::Wall { height: 3 },
+      Tile::Door(Some("key".to_string())),
+      Tile::Door(None),
+    ]
+  );
+  patch.inverse().apply(&mut level).unwrap();
+  assert_eq!(level, self::level());
+}
+
+#[test]
+fn patch_sets_maps_that_differ() {
+  let keys = (0..32).map(|key| (key, key.to_string()));
+  let ascending = keys.clone().collect::<HashMap<u8, String>>();
+  let descending = keys.rev().collect::<HashMap<u8, String>>();
+  assert!(Patch::diff(&ascending, &descending).unwrap().is_empty());
+
+  let mut changed = descending.clone();
+  changed.insert(7, "seven".to_string());
+  let patch = Patch::diff(&ascending, &changed).unwrap();
+  assert!(matches!(
+    &patch.ops[..],
+    [PatchOp::Set { path, .. }] if path.is_empty()
+  ));
+  let mut map = ascending.clone();
+  patch.apply(&mut map).unwrap();
+  assert_eq!(map, changed);
+}
+
+#[test]
+fn patch_errors_name_the_path() {
+  let mut level = level();
+  let wrong_variant = Patch {
+    ops: vec![PatchOp::set(
+      "tiles[0]::Wall.height".parse().unwrap(),
+      &0u8,
+      &1u8,
+    )
+    .unwrap()],
+  };
+  assert_eq!(
+    wrong_variant.apply(&mut level),
+    Err(PatchError::Path(PathError::InactiveVariant {
+      path: "tiles[0]::Wall".parse().unwrap(),
+      segment: Segment::Variant("Wall".to_string()),
+      active: "Floor",
+    }))
+  );
+
+  let out_of_bounds = Patch {
+    ops: vec![PatchOp::remove(
+      FieldPath::new().field("tiles"),
+      7,
+      &Tile::Floor,
+    )
+    .unwrap()],
+  };
+  assert_eq!(
+    out_of_bounds.apply(&mut level),
+    Err(PatchError::IndexOutOfBounds {
+      path: FieldPath::new().field("tiles"),
+      index: 7,
+      len: 4,
+    })
+  );
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Sealed {
+  level: Level,
+}
+
+#[test]
+fn patch_requires_opting_in() {
+  let mut sealed = Sealed { level: level() };
+  let patch = Patch {
+    ops: vec![PatchOp::set(
+      "level.name".parse().unwrap(),
+      &String::new(),
+      &"sealed".to_string(),
+    )
+    .unwrap()],
+  };
+  assert_eq!(
+    patch.apply(&mut sealed),
+    Err(PatchError::NotConstructible {
+      path: FieldPath::new(),
+      type_name: TypeInfo::of::<Sealed>().type_name(),
+    })
+  );
+  assert_eq!(sealed.level.name, level().name);
+
+  let mut levels = vec![level()];
+  let patch = Patch::diff(&levels, &vec![level(), level()]).unwrap();
+  patch.apply(&mut levels).unwrap();
+  assert_eq!(levels.len(), 2);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn patch_serializes() {
+  let mut after = level();
+  after.name = "pit".to_string();
+  let patch = Patch::diff(&level(), &after).unwrap();
+
+  let json = serde_json::to_string(&patch).unwrap();
+  let patch = serde_json::from_str::<Patch>(&json).unwrap();
+  let mut level = level();
+  patch.apply(&mut level).unwrap();
+  assert_eq!(level, after);
+}
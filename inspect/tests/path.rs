@@ -0,0 +1,259 @@
+use {
+  ::core::mem::offset_of,
+  ::inspect::{
+    path::{FieldPath, ParsePathError, PathError, Segment},
+    value::{ValueMut, ValueRef},
+    DebugValue, TypeInfo,
+  },
+  ::std::collections::BTreeMap,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+#[repr(C)]
+struct Server {
+  port: u16,
+  addr: [u8; 4],
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+enum State {
+  #[default]
+  Idle,
+  Running {
+    since: u64,
+    server: Box<Server>,
+  },
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Config {
+  servers: Vec<Server>,
+  primary: Server,
+  state: State,
+  labels: BTreeMap<String, Option<u8>>,
+}
+
+fn config() -> Config {
+  Config {
+    servers: vec![
+      Server {
+        port: 80,
+        addr: [10, 0, 0, 1],
+      },
+      Server {
+        port: 443,
+        addr: [10, 0, 0, 2],
+      },
+    ],
+    primary: Server {
+      port: 22,
+      addr: [127, 0, 0, 1],
+    },
+    state: State::Running {
+      since: 7,
+      server: Box::new(Server {
+        port: 8080,
+        addr: [0; 4],
+      }),
+    },
+    labels: BTreeMap::from([("zone".to_string(), Some(3))]),
+  }
+}
+
+fn parse(path: &str) -> FieldPath {
+  path.parse().unwrap()
+}
+
+#[test]
+fn parse_and_print_paths() {
+  assert_eq!(
+    parse("servers[1].addr[3]"),
+    FieldPath::new()
+      .field("servers")
+      .index(1)
+      .field("addr")
+      .index(3)
+  );
+  assert_eq!(
+    parse("state::Running.since").segments(),
+    [
+      Segment::Field("state".to_string()),
+      Segment::Variant("Running".to_string()),
+      Segment::Field("since".to_string()),
+    ]
+  );
+  assert_eq!(
+    parse(r#"labels["a.\"b\"]"]::Some"#),
+    FieldPath::new()
+      .field("labels")
+      .key(r#""a.\"b\"]""#)
+      .variant("Some")
+  );
+  assert!(parse("").is_empty());
+
+  for path in [
+    "servers[1].addr[3]",
+    "state::Running.since",
+    "state.Running.server.port",
+    "labels[\"zone\"]",
+    "[0].1",
+  ] {
+    assert_eq!(parse(path).to_string(), path);
+  }
+}
+
+#[test]
+fn parse_errors_point_at_the_problem() {
+  let error = |path: &str| path.parse::<FieldPath>().unwrap_err();
+  assert_eq!(
+    error("servers..addr"),
+    ParsePathError {
+      position: 8,
+      expected: "a name",
+    }
+  );
+  assert_eq!(error("servers[1").position, 9);
+  assert_eq!(error("servers[]").expected, "an index or key");
+  assert_eq!(error("labels[\"zone]").expected, "`\"`");
+  assert_eq!(
+    error("state Running").to_string(),
+    "expected `.`, `::` or `[` at byte 5 of the path"
+  );
+}
+
+#[test]
+fn resolve_against_a_type() {
+  let config = TypeInfo::of::<Config>();
+  let resolve = |path: &str| parse(path).resolve_type(config);
+
+  let port = resolve("primary.addr[2]").unwrap();
+  assert_eq!(port.type_info.type_name(), "u8");
+  assert_eq!(
+    port.offset,
+    Some(offset_of!(Config, primary) + offset_of!(Server, addr) + 2)
+  );
+
+  let since = resolve("state.Running.since").unwrap();
+  assert_eq!(since.type_info.type_name(), "u64");
+  assert_eq!(
+    since.offset,
+    Some(
+      offset_of!(Config, state)
+        + TypeInfo::of::<State>()
+          .variant("Running")
+          .unwrap()
+          .fields()
+          .next()
+          .unwrap()
+          .offset
+    )
+  );
+
+  let port = resolve("state::Running.server.port").unwrap();
+  assert_eq!(port.type_info.type_name(), "u16");
+  assert_eq!(port.offset, None);
+  let label = resolve("labels[\"zone\"]::Some").unwrap();
+  assert_eq!(label.type_info.type_name(), "u8");
+  assert_eq!(label.offset, None);
+
+  assert_eq!(
+    resolve("state.since").unwrap_err(),
+    PathError::NoMatch {
+      path: parse("state.since"),
+      segment: Segment::Field("since".to_string()),
+      type_name: TypeInfo::of::<State>().type_name(),
+    }
+  );
+  assert!(matches!(
+    resolve("primary.addr[4]"),
+    Err(PathError::OutOfBounds { len: 4, .. })
+  ));
+}
+
+#[test]
+fn resolve_against_a_value() {
+  let mut config = config();
+  let resolve = |config: &Config, path: &str| {
+    parse(path)
+      .resolve(ValueRef::new(config))
+      .map(|value| format!("{:?}", DebugValue::new(value)))
+  };
+
+  assert_eq!(resolve(&config, "servers[1].port").unwrap(), "443");
+  assert_eq!(
+    resolve(&config, "state.Running.server.port").unwrap(),
+    "8080"
+  );
+  assert_eq!(resolve(&config, "labels[\"zone\"].Some").unwrap(), "3");
+  assert_eq!(
+    resolve(&config, "servers[2].port"),
+    Err(PathError::OutOfBounds {
+      path: parse("servers[2]"),
+      segment: Segment::Index(2),
+      len: 2,
+    })
+  );
+  assert_eq!(
+    resolve(&config, "labels[\"region\"]")
+      .unwrap_err()
+      .to_string(),
+    format!(
+      "`[\"region\"]` doesn't match `{}`, at `labels[\"region\"]`",
+      ::core::any::type_name::<BTreeMap<String, Option<u8>>>()
+    )
+  );
+
+  let mut port = parse("state::Running.server.port")
+    .resolve_mut(ValueMut::new(&mut config))
+    .unwrap();
+  unsafe { *port.as_mut_ptr().cast::<u16>() = 9090 };
+  assert_eq!(
+    resolve(&config, "state.Running.server.port").unwrap(),
+    "9090"
+  );
+
+  config.state = State::Idle;
+  assert_eq!(
+    resolve(&config, "state.Running.since"),
+    Err(PathError::InactiveVariant {
+      path: parse("state.Running"),
+      segment: Segment::Field("Running".to_string()),
+      active: "Idle",
+    })
+  );
+}
+
+#[test]
+fn resolve_union_fields() {
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  #[repr(C)]
+  union Bits {
+    whole: u32,
+    bytes: [u8; 4],
+  }
+  #[derive(TypeInfo)]
+  struct Register {
+    bits: Bits,
+  }
+
+  // which field is active isn't known, so neither the type nor a value has
+  // one to lead to
+  let path = parse("bits.whole");
+  let no_match = PathError::NoMatch {
+    path: path.clone(),
+    segment: Segment::Field("whole".to_string()),
+    type_name: TypeInfo::of::<Bits>().type_name(),
+  };
+  assert_eq!(
+    path.resolve_type(TypeInfo::of::<Register>()).unwrap_err(),
+    no_match
+  );
+  let register = Register {
+    bits: Bits { whole: 1 },
+  };
+  assert_eq!(path.resolve(ValueRef::new(&register)).err(), Some(no_match));
+}
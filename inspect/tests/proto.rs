@@ -0,0 +1,271 @@
+use {
+  ::core::marker::PhantomData,
+  ::inspect::{
+    proto::{self, Proto, ProtoError},
+    TypeInfo,
+  },
+  ::std::collections::{BTreeMap, HashMap},
+};
+
+/// A packet sent by a client
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Packet {
+  sequence: u64,
+  #[inspect(skip)]
+  received_at: u64,
+  marker: PhantomData<u8>,
+  #[inspect(proto = 16)]
+  payload: Vec<u8>,
+  /// Numbered by its position, not after the field before it
+  checksum: u32,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+struct Point {
+  x: f32,
+  y: f32,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+enum Shape {
+  /// Shadows the top-level `Point` inside `Shape`
+  Point {
+    at: Point,
+  },
+  Circle(Point, f32),
+  Path(Vec<Point>),
+  Label(Option<String>),
+  Origin(Point),
+  Empty,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, PartialEq, Eq, Hash)]
+enum Direction {
+  North,
+  SouthWest,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Keys {
+  by_id: HashMap<i64, String>,
+  by_flag: BTreeMap<bool, u32>,
+  by_char: BTreeMap<char, Direction>,
+  by_name: HashMap<&'static str, Point>,
+}
+
+#[test]
+fn proto_numbers_follow_positions() {
+  assert_eq!(
+    proto::generate::<Packet>().unwrap(),
+    r#"syntax = "proto3";
+
+// A packet sent by a client
+message Packet {
+  uint64 sequence = 1;
+  bytes payload = 16;
+  // Numbered by its position, not after the field before it
+  uint32 checksum = 5;
+}
+"#
+  );
+}
+
+#[test]
+fn proto_nests_variants_in_a_oneof() {
+  assert_eq!(
+    proto::generate::<Shape>().unwrap(),
+    r#"syntax = "proto3";
+
+message Point {
+  float x = 1;
+  float y = 2;
+}
+
+message Shape {
+  message Point {
+    .Point at = 1;
+  }
+  message Circle {
+    .Point field_0 = 1;
+    float field_1 = 2;
+  }
+  message Path {
+    repeated .Point field_0 = 1;
+  }
+  message Label {
+    optional string field_0 = 1;
+  }
+  message Empty {}
+  oneof variant {
+    // Shadows the top-level `Point` inside `Shape`
+    Point point = 1;
+    Circle circle = 2;
+    Path path = 3;
+    Label label = 4;
+    .Point origin = 5;
+    Empty empty = 6;
+  }
+}
+"#
+  );
+}
+
+#[test]
+fn proto_map_keys() {
+  assert_eq!(
+    proto::generate::<Keys>().unwrap(),
+    r#"syntax = "proto3";
+
+enum Direction {
+  DIRECTION_NORTH = 0;
+  DIRECTION_SOUTH_WEST = 1;
+}
+
+message Point {
+  float x = 1;
+  float y = 2;
+}
+
+message Keys {
+  map<int64, string> by_id = 1;
+  map<bool, uint32> by_flag = 2;
+  map<string, Direction> by_char = 3;
+  map<string, Point> by_name = 4;
+}
+"#
+  );
+
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  struct BytesKeys {
+    by_hash: BTreeMap<Vec<u8>, u32>,
+  }
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  struct MessageKeys {
+    positions: HashMap<Direction, Point>,
+  }
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  struct NestedValues {
+    groups: BTreeMap<String, Vec<u32>>,
+  }
+
+  let reason = |result: Result<String, ProtoError>| match result {
+    Err(ProtoError::Field {
+      field_name, reason, ..
+    }) => (field_name, reason),
+    other => panic!("expected a field error, found {other:?}"),
+  };
+  assert_eq!(
+    reason(proto::generate::<BytesKeys>()),
+    (
+      "by_hash".to_string(),
+      "map keys have to be integers, `bool`s or strings"
+    )
+  );
+  assert_eq!(
+    reason(proto::generate::<MessageKeys>()),
+    (
+      "positions".to_string(),
+      "map keys have to be integers, `bool`s or strings"
+    )
+  );
+  assert_eq!(
+    reason(proto::generate::<NestedValues>()),
+    (
+      "groups".to_string(),
+      "map values can't be `Vec`s, maps or `Option`s"
+    )
+  );
+}
+
+#[test]
+fn proto_rejects_reserved_numbers() {
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  struct Edges {
+    #[inspect(proto = 18_999)]
+    below: u8,
+    #[inspect(proto = 20_000)]
+    above: u8,
+    #[inspect(proto = 536_870_911)]
+    last: u8,
+  }
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  struct Reserved {
+    #[inspect(proto = 19_500)]
+    inside: u8,
+  }
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  struct TooLarge {
+    #[inspect(proto = 536_870_912)]
+    past_the_end: u8,
+  }
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  struct Renumbered {
+    first: u8,
+    #[inspect(proto = 1)]
+    second: u8,
+  }
+
+  assert!(proto::generate::<Edges>()
+    .unwrap()
+    .contains("uint32 last = 536870911;"));
+  assert_eq!(
+    proto::generate::<Reserved>(),
+    Err(ProtoError::FieldNumber {
+      type_name: ::core::any::type_name::<Reserved>(),
+      field_name: "inside".to_string(),
+      number: 19_500,
+      reason: "field numbers from 19000 to 19999 are reserved by Protobuf",
+    })
+  );
+  assert!(matches!(
+    proto::generate::<TooLarge>(),
+    Err(ProtoError::FieldNumber {
+      number: 536_870_912,
+      ..
+    })
+  ));
+  assert_eq!(
+    proto::generate::<Renumbered>().unwrap_err().to_string(),
+    format!(
+      "field `second` of `{}` can't be number 1: another field has the same \
+       number",
+      ::core::any::type_name::<Renumbered>()
+    )
+  );
+}
+
+#[test]
+fn proto_rejects_names_and_keeps_the_file() {
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  struct Renamed {
+    heading: Direction,
+    #[inspect(rename = "file-name")]
+    name: String,
+  }
+
+  assert!(matches!(
+    proto::generate::<Renamed>(),
+    Err(ProtoError::Name { name, .. }) if name == "file-name"
+  ));
+
+  // the file is left as it was, without `Direction`, which was defined
+  // before the error
+  let mut definitions = Proto::new();
+  definitions.add::<Point>().unwrap();
+  let before = definitions.to_string();
+  assert!(definitions.add::<Renamed>().is_err());
+  assert_eq!(definitions.to_string(), before);
+}
@@ -0,0 +1,82 @@
+use {
+  ::core::any::{type_name, TypeId},
+  ::inspect::{Registry, TypeInfo},
+  ::std::collections::BTreeMap,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Node {
+  name: String,
+  children: Vec<Node>,
+  attributes: BTreeMap<String, Value>,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+enum Value {
+  #[default]
+  Null,
+  Number(f64),
+  Pair {
+    left: u8,
+    right: i16,
+  },
+}
+
+#[test]
+fn registry_reachable_types() {
+  let registry = Registry::new();
+  assert!(registry.is_empty());
+  let node = registry.register::<Node>();
+  assert_eq!(node.type_id(), TypeId::of::<Node>());
+
+  for type_id in [
+    TypeId::of::<Node>(),
+    TypeId::of::<String>(),
+    TypeId::of::<Vec<Node>>(),
+    TypeId::of::<BTreeMap<String, Value>>(),
+    TypeId::of::<Value>(),
+    TypeId::of::<f64>(),
+    TypeId::of::<u8>(),
+    TypeId::of::<i16>(),
+  ] {
+    assert_eq!(registry.get(type_id).unwrap().type_id(), type_id);
+  }
+  assert_eq!(registry.len(), 8);
+  assert!(registry.get(TypeId::of::<u32>()).is_none());
+}
+
+#[test]
+fn registry_lookup_by_name() {
+  let registry = Registry::new();
+  registry.register::<Node>();
+  let value = registry.get_by_name(type_name::<Value>()).unwrap();
+  assert_eq!(value.type_id(), TypeId::of::<Value>());
+  assert_eq!(
+    registry
+      .get_by_name("alloc::string::String")
+      .unwrap()
+      .type_id(),
+    TypeId::of::<String>()
+  );
+  assert!(registry.get_by_name("Value").is_none());
+}
+
+#[test]
+fn registry_iteration_order() {
+  let registry = Registry::new();
+  registry.register::<u32>();
+  registry.register::<(u32, bool)>();
+  // registering again doesn't add anything
+  registry.register::<bool>();
+  let names = registry.iter().map(TypeInfo::type_name).collect::<Vec<_>>();
+  assert_eq!(names, ["u32", "(u32, bool)", "bool"]);
+}
+
+#[test]
+fn registry_global() {
+  Registry::global().register::<Option<Node>>();
+  assert!(Registry::global().contains(TypeId::of::<Value>()));
+  assert!(!Registry::new().contains(TypeId::of::<Value>()));
+}
@@ -0,0 +1,109 @@
+use ::inspect::{
+  schema::{Field, Kind, Schema, Variant},
+  type_info::Primitive,
+  TypeInfo,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Tree {
+  label: String,
+  #[inspect(rename = "kids")]
+  children: Vec<Tree>,
+  shape: Shape,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+enum Shape {
+  Leaf,
+  Weighted(f32),
+  Pair {
+    left: Option<Box<Tree>>,
+    right: (u8, u8),
+  },
+}
+
+mod other {
+  #[allow(unused)]
+  #[derive(::inspect::TypeInfo)]
+  pub struct Shape(pub u8);
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Ambiguous(Shape, other::Shape);
+
+#[test]
+fn schema_recursive_types() {
+  let schema = Schema::of::<Tree>();
+  assert_eq!(schema.root, "Tree");
+  assert_eq!(
+    schema.types.keys().collect::<Vec<_>>(),
+    [
+      "(u8, u8)",
+      "Box<Tree>",
+      "Option<Box<Tree>>",
+      "Shape",
+      "String",
+      "Tree",
+      "Vec<Tree>",
+      "f32",
+      "u8",
+    ],
+  );
+
+  let Kind::Struct { fields } = &schema.root_def().unwrap().kind else {
+    panic!("expected a struct");
+  };
+  assert_eq!(
+    fields[1],
+    Field {
+      name: Some("children".to_string()),
+      offset: std::mem::offset_of!(Tree, children),
+      ty: "Vec<Tree>".to_string(),
+      rename: Some("kids".to_string()),
+      skip: false,
+      doc: None,
+    }
+  );
+  assert_eq!(
+    schema.types["u8"].kind,
+    Kind::Primitive {
+      primitive: Primitive::u8
+    }
+  );
+}
+
+#[test]
+fn schema_enum_variants() {
+  let schema = Schema::of::<Shape>();
+  let Kind::Enum { variants } = &schema.root_def().unwrap().kind else {
+    panic!("expected an enum");
+  };
+  assert_eq!(
+    variants.iter().map(Variant::name).collect::<Vec<_>>(),
+    ["Leaf", "Weighted", "Pair"]
+  );
+  assert_eq!(variants[2].fields()[0].ty, "Option<Box<Tree>>");
+  assert_eq!(schema.types["Shape"].size, Some(size_of::<Shape>()));
+}
+
+#[test]
+fn schema_ambiguous_names() {
+  let schema = Schema::of::<Ambiguous>();
+  let Kind::TupleStruct { fields } = &schema.root_def().unwrap().kind else {
+    panic!("expected a tuple struct");
+  };
+  assert_eq!(fields[0].ty, "Shape");
+  assert_eq!(fields[1].ty, "schema::other::Shape");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn schema_json_round_trip() {
+  let schema = Schema::of::<Tree>();
+  let json = schema.to_json();
+  assert!(json.contains(r#""kind": "vec""#));
+  assert_eq!(Schema::from_json(&json).unwrap(), schema);
+}
@@ -0,0 +1,314 @@
+#![cfg(feature = "serde")]
+
+use {
+  ::inspect::{
+    serde::{Reflected, ReflectedMut, TypeSeed},
+    value::DynValue,
+    TypeInfo,
+  },
+  ::serde::de::DeserializeSeed,
+  ::serde_json::json,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Account {
+  user: String,
+  #[inspect(rename = "display-name")]
+  display_name: Option<&'static str>,
+  #[inspect(skip)]
+  password: &'static str,
+  roles: Vec<Role>,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+enum Role {
+  Guest,
+  Member(u32),
+  Admin { level: u8, scopes: Vec<String> },
+}
+
+#[derive(TypeInfo)]
+#[inspect(constructible)]
+struct Meters(f64);
+
+#[derive(TypeInfo)]
+struct Unit;
+
+fn to_json<T: ?Sized + inspect::type_info::TypeInfoProvider>(
+  value: &T,
+) -> serde_json::Value {
+  serde_json::to_value(Reflected(value)).unwrap()
+}
+
+#[test]
+fn serialize_struct() {
+  let account = Account {
+    user: "ickk".to_string(),
+    display_name: Some("Ickk"),
+    password: "hunter2",
+    roles: vec![
+      Role::Guest,
+      Role::Member(7),
+      Role::Admin {
+        level: 2,
+        scopes: vec!["read".to_string()],
+      },
+    ],
+  };
+  assert_eq!(
+    to_json(&account),
+    json!({
+      "user": "ickk",
+      "display-name": "Ickk",
+      "roles": [
+        "Guest",
+        { "Member": 7 },
+        { "Admin": { "level": 2, "scopes": ["read"] } },
+      ],
+    }),
+  );
+}
+
+#[test]
+fn serialize_std() {
+  assert_eq!(to_json(&None::<u8>), json!(null));
+  assert_eq!(to_json(&Ok::<_, bool>(1u8)), json!({ "Ok": 1 }));
+  assert_eq!(to_json(&Err::<u8, _>(false)), json!({ "Err": false }));
+  assert_eq!(to_json("str"), json!("str"));
+  assert_eq!(to_json(&[1u16, 2][..]), json!([1, 2]));
+  assert_eq!(to_json(&Box::new((1i8, 'x'))), json!([1, "x"]));
+}
+
+#[test]
+fn serialize_structs() {
+  assert_eq!(to_json(&Meters(1.5)), json!(1.5));
+  assert_eq!(to_json(&Unit), json!(null));
+}
+
+#[test]
+fn serialize_dyn_value() {
+  let value = DynValue::new(Role::Member(3));
+  assert_eq!(
+    serde_json::to_value(&value).unwrap(),
+    json!({ "Member": 3 })
+  );
+}
+
+#[test]
+fn serialize_raw_pointer() {
+  let pointer = &0u8 as *const u8;
+  assert!(serde_json::to_value(Reflected(&pointer)).is_err());
+}
+
+#[derive(TypeInfo, Debug, PartialEq)]
+#[inspect(constructible)]
+struct SaveGame {
+  player: String,
+  #[inspect(rename = "hp")]
+  health: u32,
+  inventory: Vec<Item>,
+  settings: Settings,
+  #[inspect(skip)]
+  dirty: bool,
+  last_error: Result<(), String>,
+  companion: Option<Box<Meters>>,
+  position: [i16; 3],
+}
+
+#[derive(TypeInfo, Debug, PartialEq)]
+#[inspect(constructible)]
+enum Item {
+  Potion,
+  Sword(u8),
+  Bag { slots: u8, contents: Vec<String> },
+}
+
+#[derive(TypeInfo, Debug, PartialEq, Default)]
+#[inspect(constructible)]
+#[inspect(default)]
+struct Settings {
+  volume: f32,
+  fullscreen: bool,
+}
+
+#[derive(TypeInfo, Debug, PartialEq)]
+#[inspect(constructible)]
+struct NoDefault {
+  inner: Settings,
+  required: (u8, char),
+}
+
+impl PartialEq for Meters {
+  fn eq(&self, other: &Self) -> bool {
+    self.0 == other.0
+  }
+}
+
+impl std::fmt::Debug for Meters {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Meters({})", self.0)
+  }
+}
+
+fn from_json<T: inspect::type_info::TypeInfoProvider + 'static>(
+  json: &str,
+) -> Result<T, serde_json::Error> {
+  let mut deserializer = serde_json::Deserializer::from_str(json);
+  let value = TypeSeed::of::<T>().deserialize(&mut deserializer)?;
+  Ok(value.downcast::<T>().ok().unwrap())
+}
+
+#[test]
+fn deserialize_round_trip() {
+  let save = SaveGame {
+    player: "ickk".to_string(),
+    health: 42,
+    inventory: vec![
+      Item::Potion,
+      Item::Sword(3),
+      Item::Bag {
+        slots: 4,
+        contents: vec!["rope".to_string()],
+      },
+    ],
+    settings: Settings {
+      volume: 0.5,
+      fullscreen: true,
+    },
+    dirty: false,
+    last_error: Err("oops".to_string()),
+    companion: Some(Box::new(Meters(2.5))),
+    position: [-4, 0, 9],
+  };
+  let json = serde_json::to_string(&Reflected(&save)).unwrap();
+  assert_eq!(from_json::<SaveGame>(&json).unwrap(), save);
+}
+
+#[test]
+fn deserialize_maps() {
+  use ::std::collections::{BTreeMap, HashMap};
+
+  let json = r#"{"b":[1,2],"a":[]}"#;
+  let map = from_json::<HashMap<String, Vec<u8>>>(json).unwrap();
+  assert_eq!(map["b"], [1, 2]);
+  assert_eq!(to_json(&map), json!({ "a": [], "b": [1, 2] }),);
+
+  let map = BTreeMap::from([(3u16, Some('c')), (1, None)]);
+  let json = serde_json::to_string(&Reflected(&map)).unwrap();
+  assert_eq!(json, r#"{"1":null,"3":"c"}"#);
+  assert_eq!(
+    from_json::<BTreeMap<u16, Option<char>>>(&json).unwrap(),
+    map
+  );
+}
+
+#[test]
+fn deserialize_defaults() {
+  let save = from_json::<SaveGame>(
+    r#"{"player":"a","hp":1,"inventory":[],"last_error":{"Ok":null},
+        "position":[0,0,0]}"#,
+  )
+  .unwrap();
+  assert_eq!(save.settings, Settings::default());
+  assert_eq!(save.companion, None);
+
+  let err = from_json::<NoDefault>(r#"{"inner":{}}"#).unwrap_err();
+  assert!(
+    err.to_string().contains("missing field `required`"),
+    "{err}"
+  );
+}
+
+#[test]
+fn deserialize_arrays() {
+  assert_eq!(from_json::<[u8; 2]>("[1,2]").unwrap(), [1, 2]);
+  assert_eq!(
+    from_json::<[Option<char>; 2]>(r#"[null,"c"]"#).unwrap(),
+    [None, Some('c')]
+  );
+  assert!(from_json::<[u8; 2]>("[1]")
+    .unwrap_err()
+    .to_string()
+    .contains("invalid length 1, expected an array of 2 `u8`"));
+  assert!(from_json::<[u8; 2]>("[1,2,3]").is_err());
+}
+
+#[test]
+fn deserialize_unknown_variant() {
+  let err = from_json::<Item>(r#""Shield""#).unwrap_err();
+  assert!(
+    err
+      .to_string()
+      .contains("unknown variant `Shield`, expected one of `Potion`"),
+    "{err}"
+  );
+}
+
+#[test]
+fn deserialize_in_place() {
+  let mut item = Item::Potion;
+  let mut deserializer =
+    serde_json::Deserializer::from_str(r#"{"Bag":{"slots":2}}"#);
+  ReflectedMut(&mut item)
+    .deserialize(&mut deserializer)
+    .unwrap();
+  assert_eq!(
+    item,
+    Item::Bag {
+      slots: 2,
+      contents: vec![],
+    }
+  );
+}
+
+#[test]
+fn deserialize_requires_opting_in() {
+  let err = from_json::<Role>(r#""Guest""#).err().unwrap();
+  assert!(err.to_string().contains("`#[inspect(constructible)]`"));
+}
+
+/// Offers every value as a newtype around `7u32`, whatever was asked for
+struct Newtype;
+
+impl<'de> ::serde::Deserializer<'de> for Newtype {
+  type Error = ::serde::de::value::Error;
+
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: ::serde::de::Visitor<'de>,
+  {
+    visitor.visit_newtype_struct(
+      ::serde::de::IntoDeserializer::<Self::Error>::into_deserializer(7u32),
+    )
+  }
+
+  ::serde::forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf option unit unit_struct newtype_struct seq tuple
+    tuple_struct map struct enum identifier ignored_any
+  }
+}
+
+#[test]
+fn deserialize_newtypes() {
+  #[derive(TypeInfo, Debug, PartialEq)]
+  #[inspect(constructible)]
+  struct Id(u32);
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  #[inspect(constructible)]
+  struct Pair(u32, u32);
+  #[allow(unused)]
+  #[derive(TypeInfo)]
+  #[inspect(constructible)]
+  struct Skipped(#[inspect(skip)] u32);
+
+  let id = TypeSeed::of::<Id>().deserialize(Newtype).unwrap();
+  assert_eq!(id.downcast::<Id>().ok(), Some(Id(7)));
+  for seed in [TypeSeed::of::<Pair>(), TypeSeed::of::<Skipped>()] {
+    let err = seed.deserialize(Newtype).err().unwrap();
+    assert!(err.to_string().contains("newtype struct"), "{err}");
+  }
+}
@@ -0,0 +1,112 @@
+use {
+  ::inspect::{
+    typescript::{self, TypeScript},
+    TypeInfo,
+  },
+  ::std::collections::{BTreeMap, HashMap},
+};
+
+/// A folder of files
+///
+/// Folders can be nested
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Folder {
+  /// The name of the folder
+  name: String,
+  parent: Option<Box<Folder>>,
+  entries: Vec<Entry>,
+  #[inspect(rename = "file-sizes")]
+  sizes: HashMap<String, u64>,
+  #[inspect(skip)]
+  cache: Vec<u8>,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+enum Entry {
+  /// An empty entry
+  #[default]
+  Empty,
+  File(String),
+  Link(String, Option<Kind>),
+  Owned {
+    owner: Id,
+    mode: (u16, bool),
+  },
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+  #[default]
+  Hard,
+  Soft,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+struct Id(u32);
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Counts(BTreeMap<Kind, usize>, BTreeMap<u8, Vec<Option<Id>>>);
+
+#[test]
+fn typescript_definitions() {
+  assert_eq!(
+    typescript::generate::<Folder>(),
+    r#"export type Kind =
+  | "Hard"
+  | "Soft";
+
+export type Id = number;
+
+export type Entry =
+  /** An empty entry */
+  | "Empty"
+  | { File: string }
+  | { Link: [string, Kind | null] }
+  | { Owned: { owner: Id; mode: [number, boolean] } };
+
+/**
+ * A folder of files
+ *
+ * Folders can be nested
+ */
+export interface Folder {
+  /** The name of the folder */
+  name: string;
+  parent?: Folder | null;
+  entries: Entry[];
+  "file-sizes": Record<string, number>;
+}
+"#
+  );
+}
+
+#[test]
+fn typescript_maps() {
+  assert_eq!(
+    typescript::generate::<Counts>().lines().last(),
+    Some(
+      "export type Counts = [Partial<Record<Kind, number>>, Record<number, \
+       (Id | null)[]>];"
+    )
+  );
+}
+
+#[test]
+fn typescript_defines_types_once() {
+  let mut definitions = TypeScript::new();
+  definitions
+    .add::<Entry>()
+    .add::<Vec<Folder>>()
+    .add::<Kind>();
+  let definitions = definitions.to_string();
+  assert_eq!(definitions.matches("export type Kind =").count(), 1);
+  assert_eq!(definitions.matches("export type Entry =").count(), 1);
+  assert!(
+    definitions.ends_with("  \"file-sizes\": Record<string, number>;\n}\n")
+  );
+}
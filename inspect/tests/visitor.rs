@@ -0,0 +1,176 @@
+use ::inspect::{
+  type_info::{
+    EnumVariantInfo, NamedFieldInfo, Primitive, Std, TypeVisitor, Walker,
+  },
+  TypeInfo,
+};
+
+#[allow(unused)]
+#[derive(TypeInfo)]
+struct Tree {
+  label: String,
+  children: Vec<Tree>,
+  weight: (u8, f32),
+  shape: Shape,
+}
+
+#[allow(unused)]
+#[derive(TypeInfo, Default)]
+enum Shape {
+  #[default]
+  Leaf,
+  Branch {
+    left: u16,
+    right: u16,
+  },
+}
+
+/// Records what it's called with, indented by depth
+#[derive(Default)]
+struct Trace(Vec<String>);
+
+impl Trace {
+  fn push(&mut self, walker: &Walker, line: String) {
+    self
+      .0
+      .push(format!("{}{line}", "  ".repeat(walker.depth())));
+  }
+
+  fn name(type_info: &TypeInfo) -> &str {
+    type_info.type_name().rsplit("::").next().unwrap()
+  }
+}
+
+impl TypeVisitor for Trace {
+  fn visit_cycle(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+  ) {
+    self.push(walker, format!("  cycle {}", Trace::name(type_info)));
+  }
+
+  fn visit_too_deep(
+    &mut self,
+    walker: &mut Walker,
+    type_info: &'static TypeInfo,
+  ) {
+    self.push(walker, format!("  too deep {}", Trace::name(type_info)));
+  }
+
+  fn visit_primitive(
+    &mut self,
+    walker: &mut Walker,
+    _: &'static TypeInfo,
+    primitive: &'static Primitive,
+  ) {
+    self.push(walker, format!("{primitive:?}"));
+  }
+
+  fn visit_variant(
+    &mut self,
+    walker: &mut Walker,
+    variant: &'static EnumVariantInfo,
+  ) {
+    self.push(walker, format!("variant {}", variant.name()));
+    walker.walk_variant(self, variant);
+  }
+
+  fn visit_named_field(
+    &mut self,
+    walker: &mut Walker,
+    field: &'static NamedFieldInfo,
+  ) {
+    self.push(walker, format!("field {}", field.field_name));
+    walker.walk(self, (field.type_info_fn)());
+  }
+}
+
+#[test]
+fn visitor_default_recursion() {
+  let mut trace = Trace::default();
+  Walker::new().walk(&mut trace, TypeInfo::of::<Tree>());
+  assert_eq!(
+    trace.0,
+    [
+      "field label",
+      "field children",
+      "    cycle Tree",
+      "field weight",
+      "    u8",
+      "    f32",
+      "field shape",
+      "  variant Leaf",
+      "  variant Branch",
+      "  field left",
+      "    u16",
+      "  field right",
+      "    u16",
+    ]
+  );
+}
+
+#[test]
+fn visitor_max_depth() {
+  let mut trace = Trace::default();
+  Walker::new()
+    .max_depth(1)
+    .walk(&mut trace, TypeInfo::of::<Tree>());
+  assert_eq!(
+    trace.0,
+    [
+      "field label",
+      "field children",
+      // cycles are found before the depth is checked
+      "    cycle Tree",
+      "field weight",
+      "    too deep u8",
+      "    too deep f32",
+      "field shape",
+      "  variant Leaf",
+      "  variant Branch",
+      "  field left",
+      "    too deep u16",
+      "  field right",
+      "    too deep u16",
+    ]
+  );
+
+  let mut trace = Trace::default();
+  Walker::new()
+    .max_depth(0)
+    .walk(&mut trace, TypeInfo::of::<u8>());
+  assert_eq!(trace.0, ["u8"]);
+}
+
+#[test]
+fn visitor_skipping_children() {
+  /// Counts the types it visits, without going into `Vec`s
+  #[derive(Default)]
+  struct Count(usize);
+
+  impl TypeVisitor for Count {
+    fn visit_type(
+      &mut self,
+      walker: &mut Walker,
+      type_info: &'static TypeInfo,
+    ) {
+      self.0 += 1;
+      walker.walk_type(self, type_info);
+    }
+
+    fn visit_std(
+      &mut self,
+      _: &mut Walker,
+      _: &'static TypeInfo,
+      _: &'static Std,
+    ) {
+    }
+  }
+
+  let mut count = Count::default();
+  Walker::new().walk(&mut count, TypeInfo::of::<Tree>());
+  // `Tree`, `String`, `Vec<Tree>`, `(u8, f32)`, `u8`, `f32`, `Shape`, `u16`
+  // twice
+  assert_eq!(count.0, 9);
+}
@@ -3,7 +3,7 @@ mod type_info;
 extern crate proc_macro;
 use ::proc_macro::TokenStream;
 
-#[proc_macro_derive(TypeInfo)]
+#[proc_macro_derive(TypeInfo, attributes(inspect))]
 pub fn derive_type_info_provider(tokens: TokenStream) -> TokenStream {
   type_info::derive(tokens)
 }
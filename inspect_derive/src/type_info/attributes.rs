@@ -0,0 +1,263 @@
+use {
+  ::proc_macro2::TokenStream as TokenStream2,
+  ::quote::{quote, ToTokens},
+  ::syn::{Attribute, Expr, ExprLit, Field, Ident, Lit, LitInt, LitStr, Meta},
+};
+
+/// The options given to a struct, union or enum with `#[inspect(..)]`, along
+/// with its doc comment and `#[repr(..)]`
+#[derive(Default)]
+pub struct TypeAttributes {
+  default: bool,
+  constructible: bool,
+  doc: Option<String>,
+  repr: Repr,
+}
+
+impl TypeAttributes {
+  pub fn of(attrs: &[Attribute]) -> Self {
+    let mut attributes = TypeAttributes {
+      doc: doc_of(attrs),
+      repr: Repr::of(attrs),
+      ..TypeAttributes::default()
+    };
+    for attr in inspect_attrs(attrs) {
+      attr
+        .parse_nested_meta(|meta| {
+          if meta.path.is_ident("default") {
+            attributes.default = true;
+            Ok(())
+          } else if meta.path.is_ident("constructible") {
+            attributes.constructible = true;
+            Ok(())
+          } else {
+            Err(meta.error("unsupported `#[inspect(..)]` type option"))
+          }
+        })
+        .unwrap_or_else(|err| panic!("{err}"));
+    }
+    attributes
+  }
+
+  /// The tokens of [`SizedInfo::default_fn`] for the type `ty`
+  pub fn default_fn(&self, ty: &TokenStream2) -> TokenStream2 {
+    if self.default {
+      quote!(Some(::inspect::type_info::internal::default_erased::<#ty>))
+    } else {
+      quote!(None)
+    }
+  }
+
+  /// Whether the type may be built from its fields at run-time, which it
+  /// opts in to with `#[inspect(constructible)]`
+  pub fn constructible(&self) -> bool {
+    self.constructible
+  }
+
+  /// The tokens of the type's doc comment, as an `Option<&'static str>`
+  pub fn doc(&self) -> TokenStream2 {
+    option_tokens(&self.doc)
+  }
+
+  /// The tokens of the type's [`Repr`](::inspect::type_info::Repr)
+  pub fn repr(&self) -> TokenStream2 {
+    self.repr.to_token_stream()
+  }
+
+  /// The integer type of an enum's discriminant, given with `#[repr(u8)]` etc
+  pub fn discriminant_type(&self) -> Option<&Ident> {
+    self.repr.primitive.as_ref()
+  }
+
+  /// Panics if any options were given that generic types don't support
+  pub fn reject_generic(&self) {
+    if self.default {
+      panic!("`#[inspect(default)]` is not supported for generic types (yet)");
+    }
+  }
+}
+
+/// The options given with `#[repr(..)]`
+#[derive(Default)]
+struct Repr {
+  c: bool,
+  transparent: bool,
+  primitive: Option<Ident>,
+  packed: Option<usize>,
+  align: Option<usize>,
+}
+
+impl Repr {
+  const PRIMITIVES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize",
+  ];
+
+  fn of(attrs: &[Attribute]) -> Self {
+    let mut repr = Repr::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+      attr
+        .parse_nested_meta(|meta| {
+          let Some(ident) = meta.path.get_ident() else {
+            return Err(meta.error("unrecognised repr"));
+          };
+          match ident.to_string().as_str() {
+            "C" => repr.c = true,
+            "transparent" => repr.transparent = true,
+            "packed" => {
+              repr.packed = Some(if meta.input.is_empty() {
+                1
+              } else {
+                let content;
+                ::syn::parenthesized!(content in meta.input);
+                content.parse::<LitInt>()?.base10_parse()?
+              })
+            },
+            "align" => {
+              let content;
+              ::syn::parenthesized!(content in meta.input);
+              repr.align = Some(content.parse::<LitInt>()?.base10_parse()?);
+            },
+            name if Repr::PRIMITIVES.contains(&name) => {
+              repr.primitive = Some(ident.clone())
+            },
+            _ => return Err(meta.error("unrecognised repr")),
+          }
+          Ok(())
+        })
+        .unwrap_or_else(|err| panic!("{err}"));
+    }
+    repr
+  }
+}
+
+impl ToTokens for Repr {
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    let Repr { c, transparent, .. } = self;
+    let primitive = match &self.primitive {
+      Some(primitive) => {
+        quote!(Some(::inspect::type_info::Primitive::#primitive))
+      },
+      None => quote!(None),
+    };
+    let packed = match self.packed {
+      Some(packed) => quote!(Some(#packed)),
+      None => quote!(None),
+    };
+    let align = match self.align {
+      Some(align) => quote!(Some(#align)),
+      None => quote!(None),
+    };
+    tokens.extend(quote! {
+      ::inspect::type_info::Repr {
+        c: #c,
+        transparent: #transparent,
+        primitive: #primitive,
+        packed: #packed,
+        align: #align,
+      }
+    });
+  }
+}
+
+/// The options given to a field with `#[inspect(..)]`
+#[derive(Default)]
+pub struct FieldAttributes {
+  skip: bool,
+  rename: Option<LitStr>,
+  proto: Option<u32>,
+  doc: Option<String>,
+}
+
+impl FieldAttributes {
+  pub fn of(field: &Field) -> Self {
+    let mut attributes = FieldAttributes {
+      doc: doc_of(&field.attrs),
+      ..FieldAttributes::default()
+    };
+    for attr in inspect_attrs(&field.attrs) {
+      attr
+        .parse_nested_meta(|meta| {
+          if meta.path.is_ident("skip") {
+            attributes.skip = true;
+            Ok(())
+          } else if meta.path.is_ident("rename") {
+            attributes.rename = Some(meta.value()?.parse()?);
+            Ok(())
+          } else if meta.path.is_ident("proto") {
+            let number = meta.value()?.parse::<LitInt>()?;
+            attributes.proto = Some(number.base10_parse()?);
+            Ok(())
+          } else {
+            Err(meta.error("unsupported `#[inspect(..)]` field option"))
+          }
+        })
+        .unwrap_or_else(|err| panic!("{err}"));
+    }
+    attributes
+  }
+}
+
+impl ToTokens for FieldAttributes {
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    let skip = self.skip;
+    let rename = match &self.rename {
+      Some(rename) => quote!(Some(#rename)),
+      None => quote!(None),
+    };
+    let proto = match self.proto {
+      Some(proto) => quote!(Some(#proto)),
+      None => quote!(None),
+    };
+    let doc = option_tokens(&self.doc);
+    tokens.extend(quote! {
+      ::inspect::type_info::FieldAttributes {
+        skip: #skip,
+        rename: #rename,
+        proto: #proto,
+        doc: #doc,
+      }
+    });
+  }
+}
+
+fn inspect_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+  attrs.iter().filter(|attr| attr.path().is_ident("inspect"))
+}
+
+/// The tokens of an item's doc comment, as an `Option<&'static str>`
+pub fn doc_tokens(attrs: &[Attribute]) -> TokenStream2 {
+  option_tokens(&doc_of(attrs))
+}
+
+/// The doc comment of an item, joined from its `#[doc = ".."]` attributes
+fn doc_of(attrs: &[Attribute]) -> Option<String> {
+  let lines = attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("doc"))
+    .filter_map(|attr| match &attr.meta {
+      Meta::NameValue(meta) => match &meta.value {
+        Expr::Lit(ExprLit {
+          lit: Lit::Str(line),
+          ..
+        }) => Some(line.value()),
+        _ => None,
+      },
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  let doc = lines
+    .iter()
+    .map(|line| line.strip_prefix(' ').unwrap_or(line))
+    .collect::<Vec<_>>()
+    .join("\n");
+  let doc = doc.trim();
+  (!doc.is_empty()).then(|| doc.to_string())
+}
+
+fn option_tokens(doc: &Option<String>) -> TokenStream2 {
+  match doc {
+    Some(doc) => quote!(Some(#doc)),
+    None => quote!(None),
+  }
+}
@@ -1,5 +1,8 @@
 use {
-  crate::type_info::make_static::make_static,
+  crate::type_info::{
+    attributes::{doc_tokens, FieldAttributes, TypeAttributes},
+    make_static::make_static,
+  },
   ::proc_macro2::{Span, TokenStream as TokenStream2},
   ::quote::{format_ident, quote},
   ::syn::{DataEnum, Fields, Generics, Ident, Index, Lifetime},
@@ -8,6 +11,7 @@ use {
 pub fn derive_enum(
   name: Ident,
   generics: Generics,
+  attributes: TypeAttributes,
   data_enum: &DataEnum,
 ) -> TokenStream2 {
   if generics.const_params().next().is_some()
@@ -17,7 +21,7 @@ pub fn derive_enum(
   }
 
   if generics.type_params().next().is_none() {
-    derive_regular_enum(name, generics, data_enum)
+    derive_regular_enum(name, generics, attributes, data_enum)
   } else {
     panic!("Generics are not supported for enums (yet)");
   }
@@ -26,6 +30,7 @@ pub fn derive_enum(
 fn derive_regular_enum(
   name: Ident,
   generics: Generics,
+  attributes: TypeAttributes,
   data_enum: &DataEnum,
 ) -> TokenStream2 {
   let generic_lifetimes = generics
@@ -37,6 +42,16 @@ fn derive_regular_enum(
     .map(|_| Lifetime::new("'static", Span::call_site()));
   let full_name = quote!(#name<#(#generic_lifetimes),*>);
   let full_name_static = quote!(#name<#(#static_lifetimes),*>);
+  let default_fn = attributes.default_fn(&full_name_static);
+  let doc = attributes.doc();
+  let repr = attributes.repr();
+  let constructible = attributes.constructible();
+  let discriminant_value = match attributes.discriminant_type() {
+    Some(int) => {
+      quote!(Some(unsafe { discriminant_value::<#int, _>(variant) }))
+    },
+    None => quote!(None),
+  };
 
   let variant_info_tokenstreams = data_enum
     .variants
@@ -44,6 +59,7 @@ fn derive_regular_enum(
     .map(|variant| {
       let variant_ident = variant.ident.clone();
       let variant_name = variant_ident.to_string();
+      let variant_doc = doc_tokens(&variant.attrs);
       match &variant.fields {
         Fields::Unit => {
           let full_variant_name = quote!(#name::#variant_ident);
@@ -55,7 +71,8 @@ fn derive_regular_enum(
               EnumVariantInfo::Unit {
                 variant_name: #variant_name,
                 variant_discriminant,
-                variant_discriminant_value: None,
+                variant_discriminant_value: #discriminant_value,
+                variant_doc: #variant_doc,
               }
             }
           }
@@ -81,6 +98,8 @@ fn derive_regular_enum(
             .iter()
             .map(|field| field.ty.clone())
             .collect::<Vec<_>>();
+          let field_attributes =
+            fields.unnamed.iter().map(FieldAttributes::of);
           let field_types_static = {
             let mut f = field_types.clone();
             f.iter_mut().for_each(make_static);
@@ -102,6 +121,7 @@ fn derive_regular_enum(
                         field_index: #field_indices,
                         field_offset: #field_idents - base,
                         type_info_fn: Provider::<#field_types_static>::type_info,
+                        attributes: #field_attributes,
                       },)*
                     ]
                     .into_boxed_slice()
@@ -112,7 +132,8 @@ fn derive_regular_enum(
               EnumVariantInfo::Tuple {
                 variant_name: #variant_name,
                 variant_discriminant,
-                variant_discriminant_value: None,
+                variant_discriminant_value: #discriminant_value,
+                variant_doc: #variant_doc,
                 field_infos,
               }
             }
@@ -143,6 +164,8 @@ fn derive_regular_enum(
             .iter()
             .map(|field| field.ty.clone())
             .collect::<Vec<_>>();
+          let field_attributes =
+            fields.named.iter().map(FieldAttributes::of);
           let field_types_static = {
             let mut f = field_types.clone();
             f.iter_mut().for_each(make_static);
@@ -168,6 +191,7 @@ fn derive_regular_enum(
                         field_name: #field_names,
                         field_offset: #field_ident_idents - base,
                         type_info_fn: Provider::<#field_types_static>::type_info,
+                        attributes: #field_attributes,
                       },)*
                     ]
                     .into_boxed_slice(),
@@ -178,7 +202,8 @@ fn derive_regular_enum(
               EnumVariantInfo::Struct {
                 variant_name: #variant_name,
                 variant_discriminant,
-                variant_discriminant_value: None,
+                variant_discriminant_value: #discriminant_value,
+                variant_doc: #variant_doc,
                 field_infos,
               }
             }
@@ -188,6 +213,38 @@ fn derive_regular_enum(
     })
     .collect::<Vec<_>>();
 
+  let variant_patterns = data_enum.variants.iter().map(|variant| {
+    let variant_ident = &variant.ident;
+    match &variant.fields {
+      Fields::Unit => quote!(#name::#variant_ident),
+      Fields::Unnamed(_) => quote!(#name::#variant_ident(..)),
+      Fields::Named(_) => quote!(#name::#variant_ident { .. }),
+    }
+  });
+  let variant_indices = 0..data_enum.variants.len();
+
+  let variant_constructors = data_enum.variants.iter().map(|variant| {
+    let variant_ident = &variant.ident;
+    let mut field_types = variant
+      .fields
+      .iter()
+      .map(|field| field.ty.clone())
+      .collect::<Vec<_>>();
+    field_types.iter_mut().for_each(make_static);
+    let field_values = field_types.iter().enumerate().map(
+      |(i, ty)| quote!(unsafe { fields.add(#i).read().cast::<#ty>().read() }),
+    );
+    match &variant.fields {
+      Fields::Unit => quote!(#name::#variant_ident),
+      Fields::Unnamed(_) => quote!(#name::#variant_ident(#(#field_values),*)),
+      Fields::Named(fields) => {
+        let field_idents = fields.named.iter().map(|field| &field.ident);
+        quote!(#name::#variant_ident { #(#field_idents: #field_values),* })
+      },
+    }
+  });
+  let constructor_indices = 0..data_enum.variants.len();
+
   quote! {
     unsafe impl<#(#generic_lifetimes),*>
     ::inspect::type_info::internal::ProviderOfTypeInfo<#full_name>
@@ -203,7 +260,10 @@ fn derive_regular_enum(
             ptr,
           },
           ::inspect::type_info::{
-            internal::{Provider, ProviderOfTypeInfo, leak_erase_discriminant},
+            internal::{
+              Provider, ProviderOfTypeInfo, discriminant_value, drop_erased,
+              leak_erase_discriminant,
+            },
             TypeInfo, Enum, IdInfo, SizedInfo, EnumInfo, EnumVariantInfo,
             AnonymousFieldInfo, NamedFieldInfo, DiscriminantErased,
           },
@@ -227,8 +287,29 @@ fn derive_regular_enum(
             sized: SizedInfo {
               size: size_of::<#name>(),
               align: align_of::<#name>(),
+              drop_fn: drop_erased::<#name>,
+              default_fn: #default_fn,
+            },
+            variants: EnumInfo {
+              variant_infos,
+              variant_index_fn: |value| {
+                match unsafe { &*value.cast::<#full_name_static>() } {
+                  #(#variant_patterns => #variant_indices,)*
+                }
+              },
+              construct_variant_fn: |value, variant_index, fields| {
+                // unused when none of the variants have fields
+                let _ = fields;
+                let variant: #full_name_static = match variant_index {
+                  #(#constructor_indices => #variant_constructors,)*
+                  _ => panic!("variant index out of bounds"),
+                };
+                unsafe { value.cast::<#full_name_static>().write(variant) };
+              },
             },
-            variants: EnumInfo { variant_infos },
+            repr: #repr,
+            constructible: #constructible,
+            doc: #doc,
           });
 
           info
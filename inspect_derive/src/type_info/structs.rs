@@ -1,5 +1,8 @@
 use {
-  super::make_static::make_static,
+  super::{
+    attributes::{FieldAttributes, TypeAttributes},
+    make_static::make_static,
+  },
   ::proc_macro2::{Span, TokenStream as TokenStream2},
   ::quote::quote,
   ::syn::{
@@ -11,6 +14,7 @@ use {
 pub fn derive_struct(
   name: Ident,
   generics: Generics,
+  attributes: TypeAttributes,
   data_struct: &DataStruct,
 ) -> TokenStream2 {
   if generics.const_params().next().is_some() {
@@ -19,18 +23,23 @@ pub fn derive_struct(
 
   if generics.type_params().next().is_none() {
     match &data_struct.fields {
-      Fields::Unit => derive_unit_struct(name),
-      Fields::Unnamed(fields) => derive_tuple_struct(name, generics, fields),
-      Fields::Named(fields) => derive_regular_struct(name, generics, fields),
+      Fields::Unit => derive_unit_struct(name, attributes),
+      Fields::Unnamed(fields) => {
+        derive_tuple_struct(name, generics, attributes, fields)
+      },
+      Fields::Named(fields) => {
+        derive_regular_struct(name, generics, attributes, fields)
+      },
     }
   } else {
+    attributes.reject_generic();
     match &data_struct.fields {
       Fields::Unit => unreachable!("Unit structs can't have generics"),
       Fields::Unnamed(fields) => {
-        derive_generic_tuple_struct(name, generics, fields)
+        derive_generic_tuple_struct(name, generics, attributes, fields)
       },
       Fields::Named(fields) => {
-        derive_generic_regular_struct(name, generics, fields)
+        derive_generic_regular_struct(name, generics, attributes, fields)
       },
     }
   }
@@ -41,7 +50,15 @@ pub fn derive_struct(
 /// ```ignore
 /// struct MyStruct;
 /// ```
-fn derive_unit_struct(name: Ident) -> TokenStream2 {
+fn derive_unit_struct(
+  name: Ident,
+  attributes: TypeAttributes,
+) -> TokenStream2 {
+  let default_fn = attributes.default_fn(&quote!(#name));
+  let doc = attributes.doc();
+  let repr = attributes.repr();
+  let constructible = attributes.constructible();
+
   quote! {
     unsafe impl ::inspect::type_info::internal::ProviderOfTypeInfo<#name>
     for ::inspect::type_info::internal::Provider<#name>
@@ -51,7 +68,10 @@ fn derive_unit_struct(name: Ident) -> TokenStream2 {
 
       fn type_info() -> &'static ::inspect::TypeInfo {
         use {
-          ::inspect::{TypeInfo, type_info::{IdInfo, SizedInfo, Struct}},
+          ::inspect::{
+            TypeInfo,
+            type_info::{IdInfo, SizedInfo, Struct, internal::drop_erased},
+          },
           ::core::{
             any::{TypeId, type_name},
             mem::{size_of, align_of}
@@ -68,7 +88,12 @@ fn derive_unit_struct(name: Ident) -> TokenStream2 {
             sized: SizedInfo {
               size: size_of::<#name>(),
               align: align_of::<#name>(),
+              drop_fn: drop_erased::<#name>,
+              default_fn: #default_fn,
             },
+            repr: #repr,
+            constructible: #constructible,
+            doc: #doc,
           })
         });
 
@@ -86,6 +111,7 @@ fn derive_unit_struct(name: Ident) -> TokenStream2 {
 fn derive_tuple_struct(
   name: Ident,
   generics: Generics,
+  attributes: TypeAttributes,
   fields: &FieldsUnnamed,
 ) -> TokenStream2 {
   let field_types = fields
@@ -103,6 +129,7 @@ fn derive_tuple_struct(
     .iter()
     .enumerate()
     .map(|(i, _field)| Index::from(i));
+  let field_attributes = fields.unnamed.iter().map(FieldAttributes::of);
   let generic_lifetimes = generics
     .lifetimes()
     .map(|param| param.lifetime.clone())
@@ -112,6 +139,10 @@ fn derive_tuple_struct(
     .map(|_| Lifetime::new("'static", Span::call_site()));
   let full_name = quote!(#name<#(#generic_lifetimes),*>);
   let full_name_static = quote!(#name<#(#static_lifetimes),*>);
+  let default_fn = attributes.default_fn(&full_name_static);
+  let doc = attributes.doc();
+  let repr = attributes.repr();
+  let constructible = attributes.constructible();
 
   quote! {
     unsafe impl<#(#generic_lifetimes),*>
@@ -124,7 +155,7 @@ fn derive_tuple_struct(
       fn type_info() -> &'static ::inspect::TypeInfo {
         use {
           ::inspect::type_info::{
-            internal::{Provider, ProviderOfTypeInfo},
+            internal::{Provider, ProviderOfTypeInfo, drop_erased},
             TypeInfo, Struct, IdInfo, SizedInfo, TupleStructInfo, AnonymousFieldInfo,
           },
           ::core::{
@@ -141,6 +172,7 @@ fn derive_tuple_struct(
                 field_index: #field_indices,
                 field_offset: offset_of!(#full_name_static, #field_indices),
                 type_info_fn: Provider::<#field_types_static>::type_info,
+                attributes: #field_attributes,
               }),*
             ].into_boxed_slice()
           );
@@ -153,10 +185,15 @@ fn derive_tuple_struct(
             sized: SizedInfo {
               size: size_of::<#full_name_static>(),
               align: align_of::<#full_name_static>(),
+              drop_fn: drop_erased::<#full_name_static>,
+              default_fn: #default_fn,
             },
             fields: TupleStructInfo {
               field_infos,
             },
+            repr: #repr,
+            constructible: #constructible,
+            doc: #doc,
           })
         });
 
@@ -177,6 +214,7 @@ fn derive_tuple_struct(
 fn derive_regular_struct(
   name: Ident,
   generics: Generics,
+  attributes: TypeAttributes,
   fields: &FieldsNamed,
 ) -> TokenStream2 {
   let field_types = fields
@@ -197,6 +235,7 @@ fn derive_regular_struct(
     .named
     .iter()
     .map(|field| field.ident.as_ref().unwrap().to_string());
+  let field_attributes = fields.named.iter().map(FieldAttributes::of);
   let generic_lifetimes = generics
     .lifetimes()
     .map(|param| param.lifetime.clone())
@@ -206,6 +245,10 @@ fn derive_regular_struct(
     .map(|_| Lifetime::new("'static", Span::call_site()));
   let full_name = quote!(#name<#(#generic_lifetimes),*>);
   let full_name_static = quote!(#name<#(#static_lifetimes),*>);
+  let default_fn = attributes.default_fn(&full_name_static);
+  let doc = attributes.doc();
+  let repr = attributes.repr();
+  let constructible = attributes.constructible();
 
   quote! {
     unsafe impl<#(#generic_lifetimes),*>
@@ -218,7 +261,7 @@ fn derive_regular_struct(
       fn type_info() -> &'static ::inspect::TypeInfo {
         use {
           ::inspect::type_info::{
-            internal::{Provider, ProviderOfTypeInfo},
+            internal::{Provider, ProviderOfTypeInfo, drop_erased},
             TypeInfo, Struct, IdInfo, SizedInfo, StructInfo, NamedFieldInfo,
           },
           ::core::{
@@ -235,6 +278,7 @@ fn derive_regular_struct(
                 field_name: #field_names,
                 field_offset: offset_of!(#full_name_static, #field_idents),
                 type_info_fn: Provider::<#field_types_static>::type_info,
+                attributes: #field_attributes,
               }),*
             ].into_boxed_slice()
           );
@@ -247,10 +291,15 @@ fn derive_regular_struct(
             sized: SizedInfo {
               size: size_of::<#full_name_static>(),
               align: align_of::<#full_name_static>(),
+              drop_fn: drop_erased::<#full_name_static>,
+              default_fn: #default_fn,
             },
             fields: StructInfo {
               field_infos,
             },
+            repr: #repr,
+            constructible: #constructible,
+            doc: #doc,
           })
         });
 
@@ -268,14 +317,19 @@ fn derive_regular_struct(
 fn derive_generic_tuple_struct(
   name: Ident,
   generics: Generics,
+  attributes: TypeAttributes,
   fields: &FieldsUnnamed,
 ) -> TokenStream2 {
+  let doc = attributes.doc();
+  let repr = attributes.repr();
+  let constructible = attributes.constructible();
   let field_types = fields.unnamed.iter().map(|field| &field.ty);
   let field_indices = fields
     .unnamed
     .iter()
     .enumerate()
     .map(|(i, _field)| Index::from(i));
+  let field_attributes = fields.unnamed.iter().map(FieldAttributes::of);
   let generic_types = generics
     .type_params()
     .map(|param| param.ident.clone())
@@ -313,7 +367,7 @@ fn derive_generic_tuple_struct(
       fn type_info() -> &'static ::inspect::TypeInfo {
         use {
           ::inspect::type_info::{
-            internal::{ConcurrentMap, Provider, ProviderOfTypeInfo},
+            internal::{ConcurrentMap, Provider, ProviderOfTypeInfo, drop_erased},
             TypeInfo, Struct, IdInfo, SizedInfo, TupleStructInfo, AnonymousFieldInfo,
           },
           ::core::{
@@ -333,6 +387,7 @@ fn derive_generic_tuple_struct(
                 field_index: #field_indices,
                 field_offset: offset_of!(#full_name, #field_indices),
                 type_info_fn: Provider::<#field_types>::type_info,
+                attributes: #field_attributes,
               }),*
             ].into_boxed_slice(),
           );
@@ -344,10 +399,15 @@ fn derive_generic_tuple_struct(
             sized: SizedInfo {
               size: size_of::<#full_name>(),
               align: align_of::<#full_name>(),
+              drop_fn: drop_erased::<#full_name>,
+              default_fn: None,
             },
             fields: TupleStructInfo {
               field_infos,
             },
+            repr: #repr,
+            constructible: #constructible,
+            doc: #doc,
           });
 
           Box::leak(Box::new(info))
@@ -368,8 +428,12 @@ fn derive_generic_tuple_struct(
 fn derive_generic_regular_struct(
   name: Ident,
   generics: Generics,
+  attributes: TypeAttributes,
   fields: &FieldsNamed,
 ) -> TokenStream2 {
+  let doc = attributes.doc();
+  let repr = attributes.repr();
+  let constructible = attributes.constructible();
   let field_types = fields.named.iter().map(|field| &field.ty);
   let field_idents = fields
     .named
@@ -379,6 +443,7 @@ fn derive_generic_regular_struct(
     .named
     .iter()
     .map(|field| field.ident.as_ref().unwrap().to_string());
+  let field_attributes = fields.named.iter().map(FieldAttributes::of);
 
   let generic_types = generics
     .type_params()
@@ -418,7 +483,7 @@ fn derive_generic_regular_struct(
       fn type_info() -> &'static ::inspect::TypeInfo {
         use {
           ::inspect::type_info::{
-            internal::{ConcurrentMap, Provider, ProviderOfTypeInfo},
+            internal::{ConcurrentMap, Provider, ProviderOfTypeInfo, drop_erased},
             TypeInfo, Struct, IdInfo, SizedInfo, StructInfo, NamedFieldInfo,
           },
           ::core::{
@@ -438,6 +503,7 @@ fn derive_generic_regular_struct(
                 field_name: #field_names,
                 field_offset: offset_of!(#full_name, #field_idents),
                 type_info_fn: Provider::<#field_types>::type_info,
+                attributes: #field_attributes,
               }),*
             ].into_boxed_slice(),
           );
@@ -449,10 +515,15 @@ fn derive_generic_regular_struct(
             sized: SizedInfo {
               size: size_of::<#full_name>(),
               align: align_of::<#full_name>(),
+              drop_fn: drop_erased::<#full_name>,
+              default_fn: None,
             },
             fields: StructInfo {
               field_infos,
             },
+            repr: #repr,
+            constructible: #constructible,
+            doc: #doc,
           });
 
           Box::leak(Box::new(info))
@@ -0,0 +1,121 @@
+use {
+  super::{
+    attributes::{FieldAttributes, TypeAttributes},
+    make_static::make_static,
+  },
+  ::proc_macro2::{Span, TokenStream as TokenStream2},
+  ::quote::quote,
+  ::syn::{DataUnion, Generics, Ident, Lifetime},
+};
+
+/// derive implementation for unions: i.e.
+///
+/// ```ignore
+/// union MyUnion {
+///   a: A,
+///   b: B,
+/// }
+/// ```
+pub fn derive_union(
+  name: Ident,
+  generics: Generics,
+  attributes: TypeAttributes,
+  data_union: &DataUnion,
+) -> TokenStream2 {
+  if generics.const_params().next().is_some()
+    | generics.type_params().next().is_some()
+  {
+    panic!("Generics are not supported for unions (yet)");
+  }
+
+  let fields = &data_union.fields;
+  let field_types_static = fields
+    .named
+    .iter()
+    .map(|field| {
+      let mut ty = field.ty.clone();
+      make_static(&mut ty);
+      ty
+    })
+    .collect::<Vec<_>>();
+  let field_idents = fields
+    .named
+    .iter()
+    .map(|field| field.ident.as_ref().unwrap());
+  let field_names = fields
+    .named
+    .iter()
+    .map(|field| field.ident.as_ref().unwrap().to_string());
+  let field_attributes = fields.named.iter().map(FieldAttributes::of);
+  let generic_lifetimes = generics
+    .lifetimes()
+    .map(|param| param.lifetime.clone())
+    .collect::<Vec<_>>();
+  let static_lifetimes = generic_lifetimes
+    .iter()
+    .map(|_| Lifetime::new("'static", Span::call_site()));
+  let full_name = quote!(#name<#(#generic_lifetimes),*>);
+  let full_name_static = quote!(#name<#(#static_lifetimes),*>);
+  let default_fn = attributes.default_fn(&full_name_static);
+  let doc = attributes.doc();
+  let repr = attributes.repr();
+  let constructible = attributes.constructible();
+
+  quote! {
+    unsafe impl<#(#generic_lifetimes),*>
+    ::inspect::type_info::internal::ProviderOfTypeInfo<#full_name>
+    for ::inspect::type_info::internal::Provider<#full_name>
+    {
+      type StaticTy = #full_name_static;
+      type StaticTySized = #full_name_static;
+
+      fn type_info() -> &'static ::inspect::TypeInfo {
+        use {
+          ::inspect::type_info::{
+            internal::{Provider, ProviderOfTypeInfo, drop_erased},
+            TypeInfo, Union, IdInfo, SizedInfo, UnionInfo, NamedFieldInfo,
+          },
+          ::core::{
+            any::{TypeId, type_name},
+            mem::{offset_of, size_of, align_of},
+          },
+          ::std::sync::LazyLock,
+        };
+
+        static INFO: LazyLock<TypeInfo> = LazyLock::new(|| {
+          let field_infos: &'static [NamedFieldInfo] = Box::leak(
+            vec![
+              #(NamedFieldInfo {
+                field_name: #field_names,
+                field_offset: offset_of!(#full_name_static, #field_idents),
+                type_info_fn: Provider::<#field_types_static>::type_info,
+                attributes: #field_attributes,
+              }),*
+            ].into_boxed_slice()
+          );
+
+          TypeInfo::Union(Union::Union {
+            id: IdInfo {
+              type_id: TypeId::of::<#full_name_static>(),
+              type_name: type_name::<#full_name_static>(),
+            },
+            sized: SizedInfo {
+              size: size_of::<#full_name_static>(),
+              align: align_of::<#full_name_static>(),
+              drop_fn: drop_erased::<#full_name_static>,
+              default_fn: #default_fn,
+            },
+            fields: UnionInfo {
+              field_infos,
+            },
+            repr: #repr,
+            constructible: #constructible,
+            doc: #doc,
+          })
+        });
+
+        &INFO
+      }
+    }
+  }
+}